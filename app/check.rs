@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+use rsplib::expr::{Name, Stmt};
+use rsplib::fmt::DisplayNamed;
+use rsplib::parser::ParseContext;
+
+use super::options::InputSource;
+
+/// Parses a truth assignment of the form `P=true, Q=false`, resolving names in the given context so that
+/// they match the names of the statement.
+fn parse_assignment(ctx: &mut ParseContext, text: &str) -> Result<BTreeMap<Name, bool>, String> {
+    let mut assignment = BTreeMap::new();
+
+    for entry in text.split(',').map(str::trim).filter(|it| !it.is_empty()) {
+        let Some((name, value)) = entry.split_once('=') else {
+            return Err(format!("Expected `<name>=<true|false>`, found `{entry}`"));
+        };
+
+        let name = ctx.name(name.trim()).map_err(|err| format!("{err}"))?;
+
+        let value = match value.trim() {
+            "true" => true,
+            "false" => false,
+            other => return Err(format!("Expected `true` or `false`, found `{other}`"))
+        };
+
+        assignment.insert(name, value);
+    }
+
+    Ok(assignment)
+}
+
+fn try_parse(input: InputSource, assignment: InputSource) -> Result<(Stmt, BTreeMap<Name, bool>, ParseContext), String> {
+    let mut ctx = ParseContext::new();
+
+    let name = input.describe();
+    let text = input.read_to_string().map_err(|err| format!("{name}: {err}"))?;
+    let stmt = ctx.stmt(text).map_err(|err| format!("{err}"))?;
+
+    let name = assignment.describe();
+    let text = assignment.read_to_string().map_err(|err| format!("{name}: {err}"))?;
+    let assignment = parse_assignment(&mut ctx, &text).map_err(|err| format!("{name}: {err}"))?;
+
+    Ok((stmt, assignment, ctx))
+}
+
+/// Checks whether a truth assignment is a counter-model of a statement, that is, whether it satisfies all
+/// premises and falsifies at least one conclusion.
+pub fn main(input: InputSource, assignment: InputSource) -> ExitCode {
+    let (stmt, assignment, ctx) = match try_parse(input, assignment) {
+        Ok(ok) => ok,
+        Err(err) => {
+            println!("{err}");
+
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let names = ctx.name_table();
+
+    match stmt.eval(&assignment) {
+        Some(false) => {
+            println!("countermodel");
+
+            ExitCode::SUCCESS
+        },
+
+        Some(true) => {
+            println!("no countermodel");
+
+            let (premises, _) = stmt.into();
+            let failing: Vec<_> = premises.iter().filter(|it| it.eval(&assignment) == Some(false)).collect();
+
+            if failing.is_empty() {
+                println!("All conclusions hold.");
+            }
+
+            for premise in failing {
+                println!("Premise does not hold: {}", premise.with_table(names));
+            }
+
+            ExitCode::FAILURE
+        },
+
+        None => {
+            println!("undefined");
+            println!("The statement has predicates with arguments, or predicates that are not assigned a value.");
+
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::process::ExitCode;
+
+use rsplib::expr::Stmt;
+use rsplib::nf::NormalForm;
+use rsplib::parser::Output;
+use rsplib::res::{find_model, resolve_nf, Proof, ProofResult, ResolverOptions};
+
+use crate::options::ConsistentOptions;
+
+use super::options::InputSource;
+use super::prove::{to_cnf, try_parse};
+
+/// Decides whether the premises of the given inputs are consistent, by trying to refute their conjunction.
+/// Conclusions are ignored. If the premises are inconsistent, every goal follows from them vacuously.
+pub fn main(inputs: Vec<InputSource>, options: ConsistentOptions) -> ExitCode {
+    let ConsistentOptions { cnf_mode, max_steps, heuristic, timeout } = options;
+
+    let Output { result, .. } = match try_parse(inputs) {
+        Ok(ok) => ok,
+        Err(err) => {
+            eprintln!("{err}");
+
+            return ExitCode::FAILURE;
+        },
+    };
+
+    // Without conclusions, the statement is refuted exactly when its premises are inconsistent
+    let premises = Stmt::from_implication(result.into_premises(), Vec::new());
+
+    // Tseitin CNF is only equisatisfiable, which is all that consistency depends on
+    let (cnf, _) = to_cnf(premises.provable_expr(), cnf_mode);
+
+    let opts = ResolverOptions {
+        heuristic,
+        max_steps: Some(max_steps).filter(|it| *it > 0),
+        timeout,
+        ..ResolverOptions::default()
+    };
+
+    let verdict = verdict(&cnf, opts);
+    println!("{verdict}");
+
+    if verdict == "consistent" {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Decides the consistency of a CNF, doing the same checks as `prove` before resolving. The premises are
+/// only reported consistent when that is certain, that is, when a model was found. Otherwise, exhausting
+/// the search space is reported as such.
+fn verdict(cnf: &NormalForm, opts: ResolverOptions) -> &'static str {
+    if cnf.detect_unit_contradiction() {
+        return "inconsistent";
+    }
+
+    // Unit propagation decides ground Horn CNFs
+    if let Some(proof) = cnf.solve_horn() {
+        return match proof {
+            Proof::Proven(_) => "inconsistent",
+            _ => "consistent",
+        };
+    }
+
+    match resolve_nf(cnf, opts) {
+        ProofResult::Proven(_) => "inconsistent",
+        ProofResult::Disproven(_) | ProofResult::Exhausted(_) if find_model(cnf.clauses()).is_some() => "consistent",
+        ProofResult::Disproven(_) | ProofResult::Exhausted(_) => "exhausted",
+        ProofResult::Undecided(_) => "undecided",
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+use rsplib::expr::{BExpr, Name, Stmt};
+use rsplib::fmt::{DisplayNamed, NameTable};
+use rsplib::nf::NormalForm;
+use rsplib::parser::{Output, ParseContext};
+use rsplib::res::{Proof, Resolver};
+
+use super::options::InputSource;
+
+fn try_parse(input: InputSource) -> Result<Output<(BExpr, BExpr)>, String> {
+    let input = input.read_to_string()?;
+    ParseContext::new().equivalence_output(input).map_err(|err| format!("{err}"))
+}
+
+/// Resolves the given statement. Returns [None] if it holds, or the counter-model if it does not hold. The
+/// counter-model is [None] as well if none could be built.
+fn counter_model(stmt: Stmt) -> Option<Option<BTreeMap<Name, bool>>> {
+    let mut resolver = Resolver::new();
+    resolver.should_build_model(true);
+    resolver.assume_cnf(NormalForm::equiv_cnf(stmt.refutable_expr()));
+
+    match resolver.step_indefinitely().proof {
+        Proof::Proven(_) => None,
+        Proof::Disproven { model } => Some(model),
+        Proof::Undecided => unreachable!("No candidates are discarded without limits"),
+    }
+}
+
+fn print_model(model: &BTreeMap<Name, bool>, names: &NameTable) {
+    let model = model.iter()
+        .map(|(name, value)| format!("{}={value}", name.with_table(names)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!("Countermodel: {model}");
+}
+
+/// Decides whether two expressions are logically equivalent, by proving that each entails the other.
+pub fn main(input: InputSource) -> ExitCode {
+    let Output { result: (lhs, rhs), name_table } = match try_parse(input) {
+        Ok(ok) => ok,
+        Err(err) => {
+            println!("{err}");
+
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let directions = [
+        Stmt::from_implication(vec![lhs.clone()], vec![rhs.clone()]),
+        Stmt::from_implication(vec![rhs], vec![lhs]),
+    ];
+
+    for stmt in directions {
+        let Some(model) = counter_model(stmt.clone()) else {
+            continue;
+        };
+
+        println!("not equivalent");
+        println!("{} does not hold", stmt.with_table(&name_table));
+
+        if let Some(model) = model {
+            print_model(&model, &name_table);
+        }
+
+        return ExitCode::FAILURE;
+    }
+
+    println!("equivalent");
+
+    ExitCode::SUCCESS
+}
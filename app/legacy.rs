@@ -8,6 +8,9 @@ use rsplib::legacy::parse::parse;
 use rsplib::legacy::proof::format_proof;
 use rsplib::legacy::reso::resolution;
 
+/// The maximum amount of distribution rounds before CNF conversion gives up.
+const MAX_CNF_ITERS: usize = 1000;
+
 #[deprecated] // Ultimately we need this code gone
 pub fn main(verbose: bool) -> ExitCode {
     // Parse stdin
@@ -20,7 +23,11 @@ pub fn main(verbose: bool) -> ExitCode {
 
     // Convert to CNF
     let (expr, name_table) = parsed.unwrap();
-    let cnf = dbg!(expr.to_cnf());
+    let Ok(cnf) = expr.to_cnf_bounded(MAX_CNF_ITERS) else {
+        println!("Could not convert to CNF within {MAX_CNF_ITERS} iterations");
+        return ExitCode::FAILURE;
+    };
+    let cnf = dbg!(cnf);
     let clauses = dbg!(Clause::from_cnf(&cnf));
 
     // Resolve
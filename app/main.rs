@@ -5,6 +5,10 @@ use options::Options;
 mod options;
 mod legacy;
 mod mgu;
+mod equiv;
+mod check;
+mod consistent;
+mod normalize;
 mod prove;
 
 fn main() -> ExitCode {
@@ -13,9 +17,13 @@ fn main() -> ExitCode {
     #[allow(deprecated)]
     match opts.run_mode {
         options::RunMode::Legacy(verbose) => legacy::main(verbose),
-        options::RunMode::Prove(input, (t, s, v, h)) => prove::main(input, t, s, v, false, h),
-        options::RunMode::Disprove(input, (t, s, v, h)) => prove::main(input, t, s, v, true, h),
+        options::RunMode::Prove(input, options) => prove::main(input, options, false),
+        options::RunMode::Disprove(input, options) => prove::main(input, options, true),
+        options::RunMode::Consistent(inputs, options) => consistent::main(inputs, options),
         options::RunMode::Mgu(input) => mgu::main(input),
+        options::RunMode::Equiv(input) => equiv::main(input),
+        options::RunMode::Check(input, assignment) => check::main(input, assignment),
+        options::RunMode::Normalize(input, target) => normalize::main(input, target),
 
         options::RunMode::Help => print_help(opts.base_command),
         options::RunMode::Error(err) => print_error(opts.base_command, err),
@@ -46,10 +54,13 @@ Usage: `{base} [<command> <arguments>]`
           -v                                Enables verbose mode.
 
     {base} (prove | disprove) ((-i | --stdin) | (-f | --file) <filename>
-            | [-r | --raw] <raw_input>) ((-v | --verbose) | (-q |
-            --quiet) | (-t | --tseitin) | (-s | --steps) <number> | (-H |
-            --heuristic) (naive | prefer_empty | symbol_count |
-            disjunct_count))*
+            | [-r | --raw] <raw_input>) ((-i | --stdin) | (-f | --file)
+            <filename> | (-r | --raw) <raw_input> | (-v | --verbose) | (-q |
+            --quiet) | (-t | --tseitin) | --auto-cnf | (-s | --steps)
+            <number> | (-H | --heuristic) (naive | prefer_empty |
+            symbol_count | disjunct_count) | (-T | --timeout) <seconds> |
+            --json | --dimacs-out | --proof | --max-clauses <number> |
+            --saturated | --watchdog <number> | --stats)*
         Prove (or disprove) a specific statement. The statement is an
         input of the form `P, Q, ... |- R, S, ...`, which proves the
         statements `R, S, ...` from the given premises `P, Q, ...`. The
@@ -58,20 +69,68 @@ Usage: `{base} [<command> <arguments>]`
         Both the `prove` and `disprove` command do the same, but `prove`
         will strive to refute the opposite of the statement whereas
         `disprove` will strive to refute the statement itself.
+        Multiple inputs may be given, in which case the premises of all
+        inputs are combined and the conclusions of the last input are
+        proven. Only the last input may have conclusions. Every input
+        must still contain the `|-`, so an input with only premises ends
+        in a trailing `|-`, as in `P, P -> Q |-`.
           -i   --stdin                      Read input from stdin.
           -f   --file           <path>      Read input from given file.
           -r   --raw            <input>     Use the given argument as raw
-                                            input. You may omit the `-r`.
+                                            input. You may omit the `-r`
+                                            for the first input.
           -v   --verbose                    Print extra information with
                                             the proof.
           -q   --quiet                      Print only `sat`, `unsat` or
                                             `undec` (see below).
+               --proof                      Print the resolution steps
+                                            that derive the empty clause
+                                            when a proof is found, even
+                                            with `-q`. Each step shows
+                                            the resolvent, the lines of
+                                            its parents and the unifier.
+               --saturated                  Print the clause set that
+                                            resolution ended with when
+                                            all possibilities were
+                                            explored (`exhausted`).
+                                            It holds the input clauses
+                                            and all resolvents derived
+                                            from them.
+               --max-clauses    <number>    Restrict the amount of clauses
+                                            the prover holds in memory.
+                                            When exceeded, the heaviest
+                                            candidate clauses are
+                                            discarded. If this happened,
+                                            the prover reports
+                                            `undecided` rather than
+                                            `exhausted`.
+               --watchdog       <number>    Stop with `undecided` when
+                                            the prover seems not to
+                                            terminate, that is, when it
+                                            picked this many clauses
+                                            without finding a lighter
+                                            one while its queue of
+                                            candidates kept growing.
+               --stats                      Print a compact line of
+                                            resolution statistics and
+                                            the time spent resolving,
+                                            even with `-q`. Cannot be
+                                            combined with `--json`.
           -t   --tseitin                    Convert the proof to Tseitin
                                             CNF rather than equivalent
                                             CNF.
+               --auto-cnf                   Use equivalent CNF, unless it
+                                            has clauses of more than 16
+                                            literals, in which case
+                                            Tseitin CNF is used. With
+                                            `-v`, the CNF that was used
+                                            is printed.
           -s   --steps          <number>    Restrict the prover to a 
                                             specific amount of resolution
                                             steps.
+          -T   --timeout        <seconds>   Restrict the prover to a
+                                            specific amount of time. May
+                                            be combined with `-s`.
           -H   --heuristic      <heuristic> Use a specific heuristic to
                                             determine clause priority.
                                             `naive` assigns all clauses
@@ -96,6 +155,17 @@ Usage: `{base} [<command> <arguments>]`
                                             `disjunct_count_plus_depth`
                                             is a sum of `disjunct_count`
                                             and `depth`.
+               --json                       Print the result as a JSON
+                                            object, with fields `result`,
+                                            `clauses_learned`, `steps`
+                                            and `proof`. Cannot be
+                                            combined with `-v` or `-q`.
+               --dimacs-out                 Print the CNF that would be
+                                            resolved and exit without
+                                            resolving. Ground CNFs are
+                                            printed in DIMACS format,
+                                            other CNFs print one clause
+                                            per line.
         The output starts with one of 3 keywords, with the following 
         meanings:
           proven                            A proof was found.
@@ -107,6 +177,84 @@ Usage: `{base} [<command> <arguments>]`
                                             after a limited amount of
                                             steps.
 
+    {base} consistent ((-i | --stdin) | (-f | --file) <filename> | [-r |
+            --raw] <raw_input>) ((-i | --stdin) | (-f | --file) <filename>
+            | (-r | --raw) <raw_input> | (-t | --tseitin) | --auto-cnf |
+            (-s | --steps) <number> | (-H | --heuristic) <heuristic> | (-T
+            | --timeout) <seconds>)*
+        Check whether the premises of a statement are consistent, that
+        is, whether they can all hold at once. Inputs are given as with
+        `prove`, but their conclusions are ignored. Any statement with
+        inconsistent premises is proven vacuously, regardless of its
+        conclusions.
+          -i   --stdin                      Read input from stdin.
+          -f   --file           <path>      Read input from given file.
+          -r   --raw            <input>     Use the given argument as raw
+                                            input. You may omit the `-r`
+                                            for the first input.
+          -t   --tseitin                    Convert the premises to
+                                            Tseitin CNF rather than
+                                            equivalent CNF.
+               --auto-cnf                   Choose the CNF as with
+                                            `prove`.
+          -s   --steps          <number>    Restrict the prover to a 
+                                            specific amount of resolution
+                                            steps.
+          -T   --timeout        <seconds>   Restrict the prover to a
+                                            specific amount of time. May
+                                            be combined with `-s`.
+          -H   --heuristic      <heuristic> Use a specific heuristic, as
+                                            with `prove`.
+        The output is one of 4 keywords, with the following meanings:
+          consistent                        The premises are consistent,
+                                            a model was found.
+          inconsistent                      The premises contradict each
+                                            other.
+          exhausted                         All possibilities were
+                                            explored without finding a
+                                            contradiction, but no model
+                                            was found.
+          undecided                         The prover was undecided 
+                                            after a limited amount of
+                                            steps.
+
+    {base} check ((-i | --stdin) | (-f | --file) <filename> | [-r |
+            --raw] <raw_input>) ((-i | --stdin) | (-f | --file) <filename>
+            | [-r | --raw] <raw_assignment>)
+        Check whether a truth assignment is a countermodel of a statement,
+        that is, whether it satisfies all premises and falsifies at least
+        one conclusion. The statement is given as with `prove`, the
+        assignment is an input of the form `P=true, Q=false, ...`. All
+        predicates in the statement must be nullary and assigned a value.
+        The output is one of 3 keywords, with the following meanings:
+          countermodel                      The assignment is a
+                                            countermodel.
+          no countermodel                   The assignment is not a
+                                            countermodel.
+          undefined                         The statement could not be
+                                            evaluated.
+
+    {base} normalize ((-i | --stdin) | (-f | --file) <filename> | [-r |
+            --raw] <raw_input>) [--to (cnf | dnf | nnf | skolem | prenex |
+            tseitin)]
+        Convert an expression to a normal form and print it. The input is
+        a single expression, not a statement with `|-`.
+          -i   --stdin                      Read input from stdin.
+          -f   --file           <path>      Read input from given file.
+          -r   --raw            <input>     Use the given argument as raw
+                                            input. You may omit the `-r`.
+               --to             <form>      The normal form to convert to.
+                                            `cnf` and `dnf` are the
+                                            equivalent conjunctive and
+                                            disjunctive normal forms.
+                                            `nnf` is the negation normal
+                                            form. `skolem` is the Skolem
+                                            form, with the universal
+                                            quantifiers dropped. `prenex`
+                                            is the prenex form. `tseitin`
+                                            is the Tseitin CNF. Defaults
+                                            to `cnf`.
+
     {base} mgu ((-i | --stdin) | (-f | --file) <filename> | [-r | --raw]
             <raw_input>)
         Find a most general unifier of an equivalence. The equivalence is
@@ -115,6 +263,29 @@ Usage: `{base} [<command> <arguments>]`
         MGU never exists for such expressions. Use the `:x` syntax to
         denote a variable named `x`, as the syntax `x` will denote a
         constant.
+        If an MGU exists, it is printed along with the unified result of
+        both sides. Otherwise, the subterms that cannot be unified are
+        printed.
+          -i   --stdin                      Read input from stdin.
+          -f   --file           <path>      Read input from given file.
+          -r   --raw            <input>     Use the given argument as raw
+                                            input. You may omit the `-r`.
+
+    {base} equiv ((-i | --stdin) | (-f | --file) <filename> | [-r |
+            --raw] <raw_input>)
+        Decide whether two boolean expressions are logically equivalent.
+        The input is of the form `P === Q`, like with `mgu`, but `P` and
+        `Q` are read as boolean expressions. Equivalence is decided by
+        proving both `P |- Q` and `Q |- P`, so this may not terminate on
+        expressions with quantifiers.
+        The output is one of 2 keywords, with the following meanings:
+          equivalent                        Both expressions entail each
+                                            other.
+          not equivalent                    One of the expressions does
+                                            not entail the other. The
+                                            failing direction is printed,
+                                            along with a countermodel if
+                                            one could be found.
           -i   --stdin                      Read input from stdin.
           -f   --file           <path>      Read input from given file.
           -r   --raw            <input>     Use the given argument as raw
@@ -3,7 +3,8 @@ use std::process::ExitCode;
 use rsplib::expr::AExpr;
 use rsplib::fmt::DisplayNamed;
 use rsplib::parser::{Output, ParseContext};
-use rsplib::uni::Unifier;
+use rsplib::fmt::NameTable;
+use rsplib::uni::{Unifiable, Unifier, UnifyError};
 
 use super::options::InputSource;
 
@@ -22,19 +23,33 @@ pub fn main(input: InputSource) -> ExitCode {
         },
     };
 
-    let unifier = Unifier::mgu(&result.0, &result.1);
+    let unifier = Unifier::mgu_explain(&result.0, &result.1);
     
     match unifier {
-        None => {
+        Err(err) => {
             println!("MGU = None");
 
+            match err {
+                UnifyError::FunctionClash(f, g) => println!("Cannot unify function {} with {}", f.with_table(&name_table), g.with_table(&name_table)),
+                UnifyError::ArityMismatch(f, l, r) => println!("Cannot unify function {} applied to {l} and to {r} arguments", f.with_table(&name_table)),
+                UnifyError::OccursCheck(x, r) => println!("Cannot unify {} with {}, as it occurs in it", AExpr::var(x).with_table(&name_table), r.with_table(&name_table)),
+                UnifyError::ShapeMismatch => println!("Both sides must have the same amount of arguments"),
+            }
+
             ExitCode::FAILURE
         },
 
-        Some(uni) => {
+        Ok(uni) => {
             println!("MGU = {}", uni.with_table(&name_table));
+            println!("Unified = {}", format_args(&result.0.unify(&uni), &name_table));
             
             ExitCode::SUCCESS
         },
     }
+}
+
+/// Formats an argument list with the given names, in the form `a, b, ...`.
+fn format_args(args: &[AExpr], names: &NameTable) -> String {
+    let args: Vec<_> = args.iter().map(|it| it.with_table(names).to_string()).collect();
+    args.join(", ")
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+use std::process::ExitCode;
+
+use rsplib::expr::BExpr;
+use rsplib::fmt::DisplayNamed;
+use rsplib::nf::{prenex, skolemise, NormalForm};
+use rsplib::parser::{Output, ParseContext};
+
+use super::options::{InputSource, NormalizeTarget};
+
+fn try_parse(input: InputSource) -> Result<Output<BExpr>, String> {
+    let input = input.read_to_string()?;
+    ParseContext::new().bexpr_output(input).map_err(|err| format!("{err}"))
+}
+
+pub fn main(input: InputSource, target: NormalizeTarget) -> ExitCode {
+    let Output { result, name_table } = match try_parse(input) {
+        Ok(ok) => ok,
+        Err(err) => {
+            println!("{err}");
+
+            return ExitCode::FAILURE;
+        },
+    };
+
+    match target {
+        NormalizeTarget::Cnf => println!("{}", NormalForm::equiv_cnf(result).with_table(&name_table)),
+        NormalizeTarget::Dnf => println!("{}", NormalForm::equiv_dnf(result).with_table(&name_table)),
+        NormalizeTarget::Tseitin => println!("{}", NormalForm::tseitin_cnf(result).with_table(&name_table)),
+        NormalizeTarget::Nnf => println!("{}", result.to_nnf().with_table(&name_table)),
+        NormalizeTarget::Prenex => println!("{}", prenex(result).with_table(&name_table)),
+        NormalizeTarget::Skolem => println!("{}", skolemise(result).with_table(&name_table)),
+    }
+
+    ExitCode::SUCCESS
+}
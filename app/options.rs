@@ -1,5 +1,6 @@
 use std::env::{self, Args};
 use std::mem::replace;
+use std::time::Duration;
 use std::{fs, io};
 
 use rsplib::res::Heuristic;
@@ -12,6 +13,37 @@ pub enum Verbosity {
     Verbose
 }
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum OutputFormat {
+    Text,
+    Json
+}
+
+/// How the `prove` and `disprove` commands convert their input to CNF.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum CnfMode {
+    /// Always use the equivalent CNF.
+    Equiv,
+
+    /// Always use the Tseitin CNF.
+    Tseitin,
+
+    /// Use the equivalent CNF if its clauses stay within a size bound, otherwise fall back to the
+    /// Tseitin CNF.
+    Auto
+}
+
+/// The form that the `normalize` command converts its input to.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum NormalizeTarget {
+    Cnf,
+    Dnf,
+    Nnf,
+    Skolem,
+    Prenex,
+    Tseitin
+}
+
 pub enum InputSource {
     Raw(String),
     File(String),
@@ -26,13 +58,73 @@ impl InputSource {
             InputSource::Stdin => io::read_to_string(io::stdin()).map_err(|err| err.to_string()),
         }
     }
+
+    pub fn describe(&self) -> String {
+        match self {
+            InputSource::Raw(_) => "raw input".into(),
+            InputSource::File(name) => name.clone(),
+            InputSource::Stdin => "stdin".into(),
+        }
+    }
+}
+
+/// The options of the `prove` and `disprove` commands.
+pub struct ProveOptions {
+    /// How to convert the input to CNF.
+    pub cnf_mode: CnfMode,
+
+    /// The maximum amount of resolution steps, or 0 for no limit.
+    pub max_steps: usize,
+
+    pub verbosity: Verbosity,
+    pub heuristic: Heuristic,
+    pub format: OutputFormat,
+
+    /// Whether to only print the CNF in DIMACS format, without resolving.
+    pub dimacs_out: bool,
+
+    /// The maximum amount of time to resolve, if any.
+    pub timeout: Option<Duration>,
+
+    /// Whether to print the proof, even when quiet.
+    pub print_proof: bool,
+
+    /// The maximum amount of clauses, if any.
+    pub max_clauses: Option<usize>,
+
+    /// Whether to print the saturated clause set when no proof is found.
+    pub print_saturated: bool,
+
+    /// The window of the watchdog, if any.
+    pub watchdog: Option<usize>,
+
+    /// Whether to print a line of resolution statistics.
+    pub print_stats: bool
+}
+
+/// The options of the `consistent` command.
+pub struct ConsistentOptions {
+    /// How to convert the premises to CNF.
+    pub cnf_mode: CnfMode,
+
+    /// The maximum amount of resolution steps, or 0 for no limit.
+    pub max_steps: usize,
+
+    pub heuristic: Heuristic,
+
+    /// The maximum amount of time to resolve, if any.
+    pub timeout: Option<Duration>
 }
 
 pub enum RunMode {
     Legacy(bool),
-    Prove(InputSource, (bool, usize, Verbosity, Heuristic)),
-    Disprove(InputSource, (bool, usize, Verbosity, Heuristic)),
+    Prove(Vec<InputSource>, ProveOptions),
+    Disprove(Vec<InputSource>, ProveOptions),
     Mgu(InputSource),
+    Equiv(InputSource),
+    Consistent(Vec<InputSource>, ConsistentOptions),
+    Check(InputSource, InputSource),
+    Normalize(InputSource, NormalizeTarget),
     Help,
     Error(String)
 }
@@ -101,7 +193,8 @@ impl OptsParser {
         }
     }
 
-    fn input_source(&mut self) -> TriResult<InputSource, String> {
+    /// Reads an input source that is explicitly marked with `-i`, `-f` or `-r`.
+    fn explicit_input_source(&mut self) -> TriResult<InputSource, String> {
         match self.next_str() {
             Some("-i") | Some("--stdin") => {
                 self.shift();
@@ -128,11 +221,18 @@ impl OptsParser {
             },
 
             _ => {
-                Ok(InputSource::Raw(self.string()?))
+                Err(None)
             },
         }
     }
 
+    fn input_source(&mut self) -> TriResult<InputSource, String> {
+        match self.explicit_input_source() {
+            Err(None) => Ok(InputSource::Raw(self.string()?)),
+            res => res
+        }
+    }
+
     fn legacy_input(&mut self) -> TriResult<bool, String> {
         match self.next_str() {
             Some("-v") => {
@@ -166,6 +266,20 @@ impl OptsParser {
         }
     }
 
+    fn output_format(&mut self) -> TriResult<OutputFormat, String> {
+        match self.next_str() {
+            Some("--json") => {
+                self.shift();
+
+                Ok(OutputFormat::Json)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
     fn heuristic(&mut self) -> TriResult<Heuristic, String> {
         match self.next_str() {
             Some("-H" | "--heuristic") => {
@@ -230,18 +344,113 @@ impl OptsParser {
         }
     }
 
-    fn tseitin(&mut self) -> TriResult<bool, String> {
+    fn cnf_mode(&mut self) -> TriResult<CnfMode, String> {
         match self.next_str() {
             Some("-t" | "--tseitin") => {
                 self.shift();
 
-                Ok(true)
+                Ok(CnfMode::Tseitin)
             },
 
             Some("--equiv") => {
                 self.shift();
 
-                Ok(false)
+                Ok(CnfMode::Equiv)
+            },
+
+            Some("--auto-cnf") => {
+                self.shift();
+
+                Ok(CnfMode::Auto)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
+    fn dimacs_out(&mut self) -> TriResult<bool, String> {
+        match self.next_str() {
+            Some("--dimacs-out") => {
+                self.shift();
+
+                Ok(true)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
+    fn normalize_target(&mut self) -> TriResult<NormalizeTarget, String> {
+        match self.next_str() {
+            Some("--to") => {
+                self.shift();
+
+                let target = match self.next_str() {
+                    Some("cnf") => NormalizeTarget::Cnf,
+                    Some("dnf") => NormalizeTarget::Dnf,
+                    Some("nnf") => NormalizeTarget::Nnf,
+                    Some("skolem") => NormalizeTarget::Skolem,
+                    Some("prenex") => NormalizeTarget::Prenex,
+                    Some("tseitin") => NormalizeTarget::Tseitin,
+
+                    Some(str) => {
+                        return Err(None).with_error(format!("Unknown normal form: {str}"));
+                    },
+
+                    None => {
+                        return Err(None).with_error("Usage of --to: `--to (cnf | dnf | nnf | skolem | prenex | tseitin)`".into());
+                    }
+                };
+
+                self.shift();
+
+                Ok(target)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
+    fn print_proof(&mut self) -> TriResult<bool, String> {
+        match self.next_str() {
+            Some("--proof") => {
+                self.shift();
+
+                Ok(true)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
+    fn print_saturated(&mut self) -> TriResult<bool, String> {
+        match self.next_str() {
+            Some("--saturated") => {
+                self.shift();
+
+                Ok(true)
+            },
+
+            _ => {
+                Err(None)
+            },
+        }
+    }
+
+    fn print_stats(&mut self) -> TriResult<bool, String> {
+        match self.next_str() {
+            Some("--stats") => {
+                self.shift();
+
+                Ok(true)
             },
 
             _ => {
@@ -265,16 +474,78 @@ impl OptsParser {
         }
     }
 
-    fn prove_options(&mut self) -> TriResult<(bool, usize, Verbosity, Heuristic), String> {
-        let mut tseitin = false;
+    fn max_clauses(&mut self) -> TriResult<usize, String> {
+        match self.next_str() {
+            Some("--max-clauses") => {
+                self.shift();
+                if let Some(n) = self.next_str().and_then(|e| e.parse::<usize>().ok()) {
+                    self.shift();
+                    Ok(n)
+                } else {
+                    Err(None).with_error("Usage of --max-clauses: `--max-clauses <number>`".into())
+                }
+            }
+            _ => Err(None),
+        }
+    }
+
+    fn watchdog(&mut self) -> TriResult<usize, String> {
+        match self.next_str() {
+            Some("--watchdog") => {
+                self.shift();
+                if let Some(n) = self.next_str().and_then(|e| e.parse::<usize>().ok()).filter(|n| *n > 0) {
+                    self.shift();
+                    Ok(n)
+                } else {
+                    Err(None).with_error("Usage of --watchdog: `--watchdog <number>`".into())
+                }
+            }
+            _ => Err(None),
+        }
+    }
+
+    fn timeout(&mut self) -> TriResult<Duration, String> {
+        match self.next_str() {
+            Some("-T" | "--timeout") => {
+                self.shift();
+                if let Some(t) = self.next_str().and_then(|e| e.parse::<f64>().ok()).and_then(|e| Duration::try_from_secs_f64(e).ok()) {
+                    self.shift();
+                    Ok(t)
+                } else {
+                    Err(None).with_error("Usage of -T: `-T <seconds>`".into())
+                }
+            }
+            _ => Err(None),
+        }
+    }
+
+    fn prove_options(&mut self, inputs: &mut Vec<InputSource>) -> TriResult<ProveOptions, String> {
+        let mut cnf_mode = CnfMode::Equiv;
         let mut steps = 0usize;
         let mut verbosity = Verbosity::Normal;
         let mut heuristic = Heuristic::SymbolCount;
+        let mut format = OutputFormat::Text;
+        let mut dimacs_out = false;
+        let mut timeout = None;
+        let mut print_proof = false;
+        let mut max_clauses = None;
+        let mut print_saturated = false;
+        let mut watchdog = None;
+        let mut print_stats = false;
 
         loop {
-            match self.tseitin() {
+            match self.explicit_input_source() {
+                Ok(t) => {
+                    inputs.push(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.cnf_mode() {
                 Ok(t) => {
-                    tseitin = t;
+                    cnf_mode = t;
                     continue;
                 },
                 Err(None) => {},
@@ -308,7 +579,161 @@ impl OptsParser {
                 Err(Some(e)) => return Err(Some(e)),
             }
 
-            break Ok((tseitin, steps, verbosity, heuristic))
+            match self.output_format() {
+                Ok(t) => {
+                    format = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.dimacs_out() {
+                Ok(t) => {
+                    dimacs_out = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.timeout() {
+                Ok(t) => {
+                    timeout = Some(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.print_proof() {
+                Ok(t) => {
+                    print_proof = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.max_clauses() {
+                Ok(t) => {
+                    max_clauses = Some(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.print_saturated() {
+                Ok(t) => {
+                    print_saturated = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.watchdog() {
+                Ok(t) => {
+                    watchdog = Some(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.print_stats() {
+                Ok(t) => {
+                    print_stats = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            if format == OutputFormat::Json && verbosity != Verbosity::Normal {
+                break TriRes::err("Option --json cannot be combined with --quiet or --verbose".into());
+            }
+
+            if format == OutputFormat::Json && print_stats {
+                break TriRes::err("Option --json cannot be combined with --stats".into());
+            }
+
+            break Ok(ProveOptions {
+                cnf_mode,
+                max_steps: steps,
+                verbosity,
+                heuristic,
+                format,
+                dimacs_out,
+                timeout,
+                print_proof,
+                max_clauses,
+                print_saturated,
+                watchdog,
+                print_stats
+            })
+        }
+    }
+
+    fn consistent_options(&mut self, inputs: &mut Vec<InputSource>) -> TriResult<ConsistentOptions, String> {
+        let mut cnf_mode = CnfMode::Equiv;
+        let mut steps = 0usize;
+        let mut heuristic = Heuristic::SymbolCount;
+        let mut timeout = None;
+
+        loop {
+            match self.explicit_input_source() {
+                Ok(t) => {
+                    inputs.push(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.cnf_mode() {
+                Ok(t) => {
+                    cnf_mode = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.max_steps() {
+                Ok(t) => {
+                    steps = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.heuristic() {
+                Ok(t) => {
+                    heuristic = t;
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            match self.timeout() {
+                Ok(t) => {
+                    timeout = Some(t);
+                    continue;
+                },
+                Err(None) => {},
+                Err(Some(e)) => return Err(Some(e)),
+            }
+
+            break Ok(ConsistentOptions {
+                cnf_mode,
+                max_steps: steps,
+                heuristic,
+                timeout
+            })
         }
     }
 
@@ -324,18 +749,22 @@ impl OptsParser {
             Some("prove") => {
                 self.shift();
 
-                Ok(RunMode::Prove(
-                    self.input_source().with_error(format!("Usage: `{} prove (-i | -f <filename> | [-r] <raw_input>) [<options>]`", self.base_command))?,
-                    self.prove_options()?
-                ))
+                let mut inputs = vec![
+                    self.input_source().with_error(format!("Usage: `{} prove (-i | -f <filename> | [-r] <raw_input>) [<options>]`", self.base_command))?
+                ];
+                let options = self.prove_options(&mut inputs)?;
+
+                Ok(RunMode::Prove(inputs, options))
             },
             Some("disprove") => {
                 self.shift();
 
-                Ok(RunMode::Disprove(
-                    self.input_source().with_error(format!("Usage: `{} disprove (-i | -f <filename> | [-r] <raw_input>) [<options>]`", self.base_command))?,
-                    self.prove_options()?
-                ))
+                let mut inputs = vec![
+                    self.input_source().with_error(format!("Usage: `{} disprove (-i | -f <filename> | [-r] <raw_input>) [<options>]`", self.base_command))?
+                ];
+                let options = self.prove_options(&mut inputs)?;
+
+                Ok(RunMode::Disprove(inputs, options))
             },
             Some("mgu") => {
                 self.shift();
@@ -344,6 +773,46 @@ impl OptsParser {
                     self.input_source().with_error(format!("Usage: `{} mgu (-i | -f <filename> | [-r] <raw_input>)`", self.base_command))?
                 ))
             },
+            Some("equiv") => {
+                self.shift();
+
+                Ok(RunMode::Equiv(
+                    self.input_source().with_error(format!("Usage: `{} equiv (-i | -f <filename> | [-r] <raw_input>)`", self.base_command))?
+                ))
+            },
+            Some("consistent") => {
+                self.shift();
+
+                let mut inputs = vec![
+                    self.input_source().with_error(format!("Usage: `{} consistent (-i | -f <filename> | [-r] <raw_input>) [<options>]`", self.base_command))?
+                ];
+                let options = self.consistent_options(&mut inputs)?;
+
+                Ok(RunMode::Consistent(inputs, options))
+            },
+            Some("check") => {
+                self.shift();
+
+                let usage = format!("Usage: `{} check (-i | -f <filename> | [-r] <raw_input>) (-i | -f <filename> | [-r] <raw_assignment>)`", self.base_command);
+
+                let input = self.input_source().with_error(usage.clone())?;
+                let assignment = self.input_source().with_error(usage)?;
+
+                Ok(RunMode::Check(input, assignment))
+            },
+            Some("normalize") => {
+                self.shift();
+
+                let input = self.input_source().with_error(format!("Usage: `{} normalize (-i | -f <filename> | [-r] <raw_input>) [--to <form>]`", self.base_command))?;
+
+                let target = match self.normalize_target() {
+                    Ok(target) => target,
+                    Err(None) => NormalizeTarget::Cnf,
+                    Err(Some(e)) => return Err(Some(e)),
+                };
+
+                Ok(RunMode::Normalize(input, target))
+            },
             Some("help") => {
                 self.shift();
 
@@ -1,68 +1,227 @@
 use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-use rsplib::expr::Stmt;
-use rsplib::fmt::DisplayNamed;
+use rsplib::expr::{BExpr, Stmt};
+use rsplib::fmt::{DisplayNamed, NameTable};
 use rsplib::nf::NormalForm;
 use rsplib::parser::{Output, ParseContext};
-use rsplib::res::{Heuristic, Proof, Resolver};
+use rsplib::res::{Deduction, Proof, ProofResult, Resolver, ResolverResult, ResolverStats};
 
-use crate::options::Verbosity;
+use crate::options::{CnfMode, OutputFormat, ProveOptions, Verbosity};
 
 use super::options::InputSource;
 
-fn try_parse(input: InputSource) -> Result<Output<Stmt>, String> {
-    let input = input.read_to_string()?;
-    ParseContext::new().stmt_output(input).map_err(|err| format!("{err}"))
+/// Parses all inputs into one statement, resolving names consistently across inputs. Premises
+/// accumulate over all inputs, and the conclusions of the last input form the goal. Only the last
+/// input may have conclusions.
+pub fn try_parse(inputs: Vec<InputSource>) -> Result<Output<Stmt>, String> {
+    let mut ctx = ParseContext::new();
+    let mut premises = Vec::new();
+    let mut conclusions = Vec::new();
+
+    let count = inputs.len();
+    for (i, input) in inputs.into_iter().enumerate() {
+        let name = input.describe();
+        let text = input.read_to_string().map_err(|err| format!("{name}: {err}"))?;
+        let stmt = ctx.stmt(text).map_err(|err| {
+            if count > 1 {
+                format!("{name}: {err}")
+            } else {
+                format!("{err}")
+            }
+        })?;
+
+        let (p, c) = stmt.into();
+
+        if i + 1 < count && !c.is_empty() {
+            return Err(format!("{name}: Only the last input may have conclusions"));
+        }
+
+        premises.extend(p);
+        conclusions = c;
+    }
+
+    Ok(Output {
+        result: Stmt::from_implication(premises, conclusions),
+        name_table: ctx.name_table().clone()
+    })
 }
 
-pub fn main(input: InputSource, tseitin: bool, max_steps: usize, verbosity: Verbosity, prefer_counterproof: bool, heuristic: Heuristic) -> ExitCode {
-    let Output { result, name_table } = match try_parse(input) {
+/// The maximum amount of literals per clause of an equivalent CNF in [CnfMode::Auto].
+const AUTO_CNF_MAX_CLAUSE_LEN: usize = 16;
+
+/// Converts the given expression to CNF as the given mode specifies. Returns the CNF along with a
+/// description of the conversion that was used.
+pub fn to_cnf(expr: BExpr, mode: CnfMode) -> (NormalForm, String) {
+    match mode {
+        CnfMode::Equiv => (NormalForm::equiv_cnf(expr), "equivalent CNF".into()),
+        CnfMode::Tseitin => (NormalForm::tseitin_cnf(expr), "Tseitin CNF".into()),
+        CnfMode::Auto => match NormalForm::equiv_cnf_bounded(expr.clone(), AUTO_CNF_MAX_CLAUSE_LEN) {
+            Ok(cnf) => (cnf, "equivalent CNF".into()),
+            Err(err) => (NormalForm::tseitin_cnf(expr), format!("Tseitin CNF, as the equivalent {err}")),
+        },
+    }
+}
+
+pub fn main(inputs: Vec<InputSource>, options: ProveOptions, prefer_counterproof: bool) -> ExitCode {
+    let ProveOptions {
+        cnf_mode,
+        max_steps,
+        verbosity,
+        heuristic,
+        format,
+        dimacs_out,
+        timeout,
+        print_proof,
+        max_clauses,
+        print_saturated,
+        watchdog,
+        print_stats: show_stats
+    } = options;
+
+    let Output { result, name_table } = match try_parse(inputs) {
         Ok(ok) => ok,
         Err(err) => {
-            eprintln!("{err}");
+            if format == OutputFormat::Json {
+                println!("{{\"result\":\"error\",\"message\":{}}}", json_string(&err));
+            } else {
+                eprintln!("{err}");
+            }
 
             return ExitCode::FAILURE;
         },
     };
 
-    // Statement
-    let stmt = if prefer_counterproof {
-        result.provable_expr()
-    } else {
-        result.refutable_expr()
-    };
-    
-    // CNF
-    let cnf = if tseitin {
-        NormalForm::tseitin_cnf(stmt)
+    // Statements that are trivially valid need no resolution. In disprove mode, the statement is
+    // refuted the other way around, so this does not apply.
+    let trivial = if prefer_counterproof || dimacs_out {
+        None
     } else {
-        NormalForm::equiv_cnf(stmt)
+        result.trivial_result()
     };
 
-    if let Verbosity::Verbose = verbosity {
-        println!("Input CNF has {} clauses", cnf.len())
-    }
-
     // Resolver
     let mut resolver = Resolver::new();
     resolver.set_heuristic(heuristic);
-    resolver.assume_cnf(cnf);
+    resolver.set_max_clauses(max_clauses);
+    resolver.set_stall_window(watchdog);
+    resolver.should_stop_when_stalled(true);
+
+    let mut elapsed = Duration::ZERO;
 
-    // Resolution
-    let result = if max_steps == 0 {
-        resolver.step_indefinitely()
+    let result = if let Some(proof) = trivial {
+        if let Verbosity::Verbose = verbosity {
+            println!("Statement is trivial, no resolution needed")
+        }
+
+        Some(ResolverResult { proof, deductions_made: 0, learning_order: Vec::new() })
     } else {
-        if let Some(r) = resolver.step_n_times(max_steps) {
-            r
+        // Statement
+        let stmt = if prefer_counterproof {
+            result.provable_expr()
         } else {
-            println!("undecided");
+            result.refutable_expr()
+        };
+
+        // CNF
+        let (cnf, strategy) = to_cnf(stmt, cnf_mode);
+
+        if dimacs_out {
+            match cnf.to_dimacs(&name_table) {
+                Some(dimacs) => print!("{dimacs}"),
+                None => {
+                    for clause in cnf.clauses() {
+                        println!("{}", clause.with_table(&name_table));
+                    }
+                }
+            }
+
+            return ExitCode::SUCCESS;
+        }
+
+        if let Verbosity::Verbose = verbosity {
+            println!("Using {strategy}");
+            println!("Input CNF has {}", cnf.stats())
+        }
+
+        if let Some((p, n)) = cnf.unit_contradiction() {
+            if let Verbosity::Verbose = verbosity {
+                println!("Input CNF has complementary unit clauses, resolving only those")
+            }
+
+            // The pair resolves to the empty clause in one step, which gives the proof
+            resolver.assume(p.clone());
+            resolver.assume(n.clone());
+
+            Some(resolver.step_indefinitely())
+        } else if let Some(proof) = horn_proof(&cnf, print_saturated) {
+            if let Verbosity::Verbose = verbosity {
+                println!("Input CNF is ground and Horn, solved by unit propagation")
+            }
+
+            Some(ResolverResult { proof, deductions_made: 0, learning_order: Vec::new() })
+        } else {
+            resolver.assume_cnf(cnf);
+
+            // Resolution
+            let started = Instant::now();
+            let deadline = timeout.map(|it| started + it);
+            let result = match (max_steps, deadline) {
+                (0, None) if watchdog.is_some() => resolver.step_n_times(usize::MAX),
+                (0, None) => Some(resolver.step_indefinitely()),
+                (0, Some(deadline)) => resolver.step_until(deadline),
+                (n, None) => resolver.step_n_times(n),
+                (n, Some(deadline)) => resolver.step_n_times_until(n, deadline),
+            };
+
+            elapsed = started.elapsed();
+            result
+        }
+    };
+
+    // When candidates were discarded to stay within the clause limit, exhausting the search space
+    // proves nothing, so the resolver reports it as undecided
+    let outcome = ProofResult::new(result.as_ref(), resolver.stats(), prefer_counterproof);
+    let clause_limit_reached = matches!(&result, Some(ResolverResult { proof: Proof::Undecided, .. }))
+        && outcome.stats().discarded_by_clause_limit > 0;
+
+    let result = match result {
+        Some(r) if !matches!(outcome, ProofResult::Undecided(_)) => r,
+        _ => {
+            let stats = outcome.stats();
+
+            if format == OutputFormat::Json {
+                println!(
+                    "{{\"result\":\"{outcome}\",\"clauses_learned\":{},\"steps\":{},\"clause_limit_reached\":{clause_limit_reached},\"stalled\":{},\"proof\":[]}}",
+                    stats.learning_order.len(),
+                    stats.deductions_made,
+                    resolver.is_stalled()
+                );
+
+                return ExitCode::FAILURE;
+            }
+
+            println!("{outcome}");
+
+            if clause_limit_reached && verbosity >= Verbosity::Normal {
+                println!("Clause limit reached, {} candidates were discarded.", stats.discarded_by_clause_limit);
+            }
+
+            if resolver.is_stalled() && verbosity >= Verbosity::Normal {
+                println!("Resolution seems not to terminate, stopped after {} deductions.", stats.deductions_made);
+            }
 
             if let Verbosity::Verbose = verbosity {
                 println!("Clauses in learning order:");
-                for clause in resolver.stats().learning_order {
+                for clause in &stats.learning_order {
                     println!("  - {}", clause.with_table(&name_table));
                 }
-                println!("No proof found after {max_steps} deductions.");
+                println!("No proof found after {} deductions.", stats.deductions_made);
+                print_stats(stats);
+            }
+
+            if show_stats {
+                print_stats_line(stats, elapsed);
             }
 
             return ExitCode::FAILURE;
@@ -71,15 +230,32 @@ pub fn main(input: InputSource, tseitin: bool, max_steps: usize, verbosity: Verb
 
     let n = result.deductions_made;
 
+    if format == OutputFormat::Json {
+        let deductions = match &result.proof {
+            Proof::Proven(deductions) => deductions.as_slice(),
+            Proof::Disproven { .. } | Proof::Undecided => [].as_slice(),
+        };
+
+        let saturated = match &result.proof {
+            Proof::Disproven { .. } if print_saturated => format!(",\"saturated\":{}", json_clauses(&resolver.saturated_cnf(), &name_table)),
+            _ => String::new()
+        };
+
+        println!(
+            "{{\"result\":\"{outcome}\",\"clauses_learned\":{},\"steps\":{n},\"proof\":{}{saturated}}}",
+            result.learning_order.len(),
+            json_proof(deductions, &name_table)
+        );
+
+        return ExitCode::SUCCESS;
+    }
+
     match result.proof {
         Proof::Proven(deductions) => {
-            if prefer_counterproof {
-                println!("disproven");
-            } else {
-                println!("proven");
-            }
+            println!("{outcome}");
 
-            if verbosity >= Verbosity::Normal {
+            // Trivial statements are proven without deductions
+            if (print_proof || verbosity >= Verbosity::Normal) && !deductions.is_empty() {
                 println!("Refutation proof using resolution:");
 
                 let mut line = 0usize;
@@ -95,11 +271,19 @@ pub fn main(input: InputSource, tseitin: bool, max_steps: usize, verbosity: Verb
                     println!("  - {}", clause.with_table(&name_table));
                 }
                 println!("{n} deductions made.");
+                print_stats(&resolver.stats());
             }
         },
 
-        Proof::Disproven => {
-            println!("exhausted");
+        Proof::Disproven { .. } => {
+            println!("{outcome}");
+
+            if print_saturated {
+                println!("Saturated clause set:");
+                for clause in resolver.saturated_cnf().clauses() {
+                    println!("  - {}", clause.with_table(&name_table));
+                }
+            }
 
             if verbosity >= Verbosity::Verbose {
                 println!("Clauses in learning order:");
@@ -107,9 +291,110 @@ pub fn main(input: InputSource, tseitin: bool, max_steps: usize, verbosity: Verb
                     println!("  - {}", clause.with_table(&name_table));
                 }
                 println!("{n} deductions made.");
+                print_stats(&resolver.stats());
             }
-        }
+        },
+
+        Proof::Undecided => unreachable!("Undecided results are reported above")
+    }
+
+    if show_stats {
+        print_stats_line(outcome.stats(), elapsed);
     }
 
     ExitCode::SUCCESS
-}
\ No newline at end of file
+}
+
+/// Solves the CNF by unit propagation if it is a ground Horn CNF, see [NormalForm::solve_horn]. This is
+/// skipped when the saturated clause set is requested, since only resolution produces one.
+fn horn_proof(cnf: &NormalForm, print_saturated: bool) -> Option<Proof> {
+    if print_saturated {
+        return None;
+    }
+
+    cnf.solve_horn()
+}
+
+/// Prints the statistics of a resolver, in verbose mode.
+fn print_stats(stats: &ResolverStats) {
+    println!("Statistics:");
+    println!("  Resolvents produced:    {}", stats.resolvents_produced);
+    println!("  Max clause length:      {}", stats.max_clause_len);
+    println!("  Avg clause length:      {:.2}", stats.avg_clause_len());
+    println!("  Tautologies discarded:  {}", stats.tautologies_discarded);
+    println!("  Subsumed clauses:       {}", stats.subsumed_dropped);
+    println!("  Peak frontier size:     {}", stats.peak_frontier);
+    println!("  Clause limit discards:  {}", stats.discarded_by_clause_limit);
+    println!("  MGU attempts:           {}", stats.mgu_attempts);
+    println!("  MGU successes:          {}", stats.mgu_successes);
+}
+
+/// Prints a compact line of resolution statistics along with the time spent resolving, for `--stats`.
+fn print_stats_line(stats: &ResolverStats, elapsed: Duration) {
+    println!(
+        "Stats: learned={} tautologies={} subsumed={} mgu_attempts={} mgu_successes={} peak_frontier={} time={:.3}ms",
+        stats.learning_order.len(),
+        stats.tautologies_discarded,
+        stats.subsumed_dropped,
+        stats.mgu_attempts,
+        stats.mgu_successes,
+        stats.peak_frontier,
+        elapsed.as_secs_f64() * 1000.0
+    );
+}
+
+/// Formats the clauses of a CNF as a JSON array of strings.
+fn json_clauses(cnf: &NormalForm, names: &NameTable) -> String {
+    let clauses: Vec<_> = cnf.clauses().iter()
+        .map(|clause| json_string(&clause.with_table(names).to_string()))
+        .collect();
+
+    format!("[{}]", clauses.join(","))
+}
+
+/// Formats the deductions of a proof as a JSON array of steps.
+fn json_proof(deductions: &[Deduction], names: &NameTable) -> String {
+    let steps: Vec<_> = deductions.iter().enumerate().map(|(line, ded)| match ded {
+        Deduction::Premise { clause } => format!(
+            "{{\"line\":{line},\"rule\":\"premise\",\"clause\":{}}}",
+            json_string(&clause.with_table(names).to_string())
+        ),
+
+        Deduction::Resolve { clause, a_line, b_line, resolvee } => format!(
+            "{{\"line\":{line},\"rule\":\"resolution\",\"clause\":{},\"parents\":[{a_line},{b_line}],\"unifier\":{}}}",
+            json_string(&clause.with_table(names).to_string()),
+            json_string(&resolvee.mgu.with_table(names).to_string())
+        ),
+
+        Deduction::Magic { clause } => format!(
+            "{{\"line\":{line},\"rule\":\"magic\",\"clause\":{}}}",
+            json_string(&clause.with_table(names).to_string())
+        ),
+
+        Deduction::QED { line_with_bottom } => format!(
+            "{{\"line\":{line},\"rule\":\"qed\",\"parents\":[{line_with_bottom}]}}"
+        ),
+    }).collect();
+
+    format!("[{}]", steps.join(","))
+}
+
+/// Formats a string as a JSON string literal.
+fn json_string(str: &str) -> String {
+    let mut out = String::from("\"");
+
+    for c in str.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
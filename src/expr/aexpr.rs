@@ -8,6 +8,7 @@ use crate::uni::{Unifiable, Unifier};
 use super::{Name, Names};
 
 /// A non-boolean expression. Unlike [BExpr](super::BExpr), this does not evaluate to true or false.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
 pub enum AExpr {
     /// A bound variable. These are generated by the skolemisation of quantifiers.
@@ -36,6 +37,54 @@ impl AExpr {
     pub fn fun(name: Name, args: Vec<AExpr>) -> AExpr {
         AExpr::Fun(name, args)
     }
+
+    /// The depth of this term. A variable or constant has depth 1, and a function has depth 1 plus
+    /// the maximum depth of its arguments. E.g. `f(:x, g(a))` has depth 3.
+    pub fn depth(&self) -> usize {
+        match self {
+            AExpr::Var(_) => 1,
+            AExpr::Fun(_, args) => 1 + args.iter().map(AExpr::depth).max().unwrap_or(0)
+        }
+    }
+
+    /// The amount of symbols in this term, counting every occurrence of a variable or function. E.g.
+    /// `f(:x, g(:x))` has 4 symbols.
+    pub fn symbol_count(&self) -> usize {
+        match self {
+            AExpr::Var(_) => 1,
+            AExpr::Fun(_, args) => 1 + args.iter().map(AExpr::symbol_count).sum::<usize>()
+        }
+    }
+
+    /// Returns whether this term is ground, that is, whether no variable occurs anywhere in it. E.g.
+    /// `f(a, g(b))` is ground but `f(a, g(:x))` is not.
+    pub fn is_ground(&self) -> bool {
+        match self {
+            AExpr::Var(_) => false,
+            AExpr::Fun(_, args) => args.iter().all(AExpr::is_ground)
+        }
+    }
+
+    /// Renames the function symbol `from` to `to` throughout this term. Unlike [unification][Unifiable::unify],
+    /// this replaces function symbols rather than variables. E.g. renaming `f` to `g` in `f(:x, f(a))` gives
+    /// `g(:x, g(a))`.
+    pub fn rename_fun(self, from: Name, to: Name) -> AExpr {
+        match self {
+            AExpr::Var(name) => AExpr::Var(name),
+            AExpr::Fun(name, args) => AExpr::Fun(
+                if name == from { to } else { name },
+                args.into_iter().map(|it| it.rename_fun(from, to)).collect()
+            )
+        }
+    }
+
+    /// [Offsets][Name::offset] every name in this term, including variables, by the given base name.
+    pub fn shift_names(self, base: Name) -> AExpr {
+        match self {
+            AExpr::Var(name) => AExpr::Var(name.offset(base)),
+            AExpr::Fun(name, args) => AExpr::Fun(name.offset(base), args.into_iter().map(|it| it.shift_names(base)).collect())
+        }
+    }
 }
 
 impl Default for AExpr {
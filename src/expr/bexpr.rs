@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 use std::mem::replace;
 use std::ops::BitAnd;
@@ -17,6 +18,7 @@ use super::Names;
 use super::Vars;
 
 /// A Boolean expression, i.e. any expression that evaluates to true or false.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
 pub enum BExpr {
     /// The true constant. i.e. the undoubted tautology whose truth nobody could possibly ever question.
@@ -127,12 +129,230 @@ impl BExpr {
         }
     }
 
+    /// [Offsets][Name::offset] every name in this [BExpr] by the given base name. This includes predicates,
+    /// functions and variables, both bound and unbound.
+    pub fn shift_names(self, base: Name) -> BExpr {
+        match self {
+            BExpr::True => BExpr::True,
+            BExpr::False => BExpr::False,
+            BExpr::Pred(name, args) => BExpr::Pred(name.offset(base), args.into_iter().map(|it| it.shift_names(base)).collect()),
+
+            BExpr::And(lhs, rhs) => lhs.shift_names(base) & rhs.shift_names(base),
+            BExpr::Or(lhs, rhs) => lhs.shift_names(base) | rhs.shift_names(base),
+            BExpr::Not(rhs) => !rhs.shift_names(base),
+
+            BExpr::All(name, rhs) => BExpr::all(name.offset(base), rhs.shift_names(base)),
+            BExpr::Some(name, rhs) => BExpr::some(name.offset(base), rhs.shift_names(base)),
+        }
+    }
+
+    /// Collects the predicates used in this [BExpr] along with their arities. A name that is used with
+    /// different amounts of arguments occurs once for every arity.
+    pub fn predicates(&self) -> BTreeSet<(Name, usize)> {
+        let mut preds = BTreeSet::new();
+        self.collect_symbols(&mut preds, &mut BTreeSet::new());
+        preds
+    }
+
+    /// Collects the functions used in the arguments of the predicates in this [BExpr] along with their
+    /// arities. Constants are functions of arity 0. Variables are not included.
+    pub fn functions(&self) -> BTreeSet<(Name, usize)> {
+        let mut funs = BTreeSet::new();
+        self.collect_symbols(&mut BTreeSet::new(), &mut funs);
+        funs
+    }
+
+    fn collect_symbols(&self, preds: &mut BTreeSet<(Name, usize)>, funs: &mut BTreeSet<(Name, usize)>) {
+        match self {
+            BExpr::True | BExpr::False => {},
+            BExpr::Pred(name, args) => {
+                preds.insert((*name, args.len()));
+
+                for arg in args {
+                    collect_functions(arg, funs);
+                }
+            },
+
+            BExpr::And(lhs, rhs) | BExpr::Or(lhs, rhs) => {
+                lhs.collect_symbols(preds, funs);
+                rhs.collect_symbols(preds, funs);
+            },
+            BExpr::Not(rhs) | BExpr::All(_, rhs) | BExpr::Some(_, rhs) => rhs.collect_symbols(preds, funs),
+        }
+    }
+
     /// Tests whether this [BExpr] is a sentence, that is, whether it contains no
     /// unbound variables.
     pub fn is_sentence(&self) -> bool {
         let vars: Vec<_> = self.vars();
         vars.is_empty()
     }
+
+    /// Evaluates this [BExpr] under the given truth assignment of nullary predicates. This
+    /// returns [None] if this expression contains a predicate with arguments, or a nullary predicate
+    /// that is not assigned a truth value. Quantifiers are evaluated by evaluating their body, which
+    /// is sound as their variable can then not be used.
+    pub fn eval(&self, assignment: &BTreeMap<Name, bool>) -> Option<bool> {
+        match self {
+            BExpr::True => Some(true),
+            BExpr::False => Some(false),
+
+            BExpr::Pred(name, args) => {
+                if !args.is_empty() {
+                    return None;
+                }
+
+                assignment.get(name).copied()
+            },
+
+            BExpr::And(lhs, rhs) => Some(lhs.eval(assignment)? & rhs.eval(assignment)?),
+            BExpr::Or(lhs, rhs) => Some(lhs.eval(assignment)? | rhs.eval(assignment)?),
+            BExpr::Not(rhs) => Some(!rhs.eval(assignment)?),
+
+            BExpr::All(_, rhs) | BExpr::Some(_, rhs) => rhs.eval(assignment),
+        }
+    }
+
+    /// Lowers derived connectives, such as implication (`->`), equivalence (`<->`) and exclusive
+    /// disjunction (`^`), into `&`, `|` and `!`. Currently, [BExpr] has no variants for these
+    /// connectives, since [BExpr::im], [BExpr::revim] and [BExpr::equiv] already lower them upon
+    /// construction, so this is a no-op. Any variant for a derived connective that is added to
+    /// [BExpr] must be lowered here.
+    pub fn desugar(self) -> BExpr {
+        match self {
+            BExpr::And(lhs, rhs) => lhs.desugar() & rhs.desugar(),
+            BExpr::Or(lhs, rhs) => lhs.desugar() | rhs.desugar(),
+            BExpr::Not(rhs) => !rhs.desugar(),
+
+            BExpr::All(name, rhs) => BExpr::all(name, rhs.desugar()),
+            BExpr::Some(name, rhs) => BExpr::some(name, rhs.desugar()),
+
+            e => e
+        }
+    }
+
+    /// Simplifies this [BExpr] by folding away `true` and `false` constants and trivial redundancies,
+    /// without changing its meaning. Specifically, it applies:
+    /// - Identity: `P & true` becomes `P` and `P | false` becomes `P`.
+    /// - Annihilation: `P & false` becomes `false` and `P | true` becomes `true`.
+    /// - Idempotence: `P & P` becomes `P` and `P | P` becomes `P`.
+    /// - Negation: `!!P` becomes `P`, `!true` becomes `false` and `!false` becomes `true`.
+    /// - Quantification: `all x: true` becomes `true`, and likewise for `false` and `some`.
+    ///
+    /// This does not attempt to find a minimal equivalent expression.
+    pub fn simplify(self) -> BExpr {
+        match self {
+            BExpr::And(lhs, rhs) => match (lhs.simplify(), rhs.simplify()) {
+                (BExpr::False, _) | (_, BExpr::False) => BExpr::False,
+                (BExpr::True, e) | (e, BExpr::True) => e,
+                (l, r) if l == r => l,
+                (l, r) => l & r
+            },
+
+            BExpr::Or(lhs, rhs) => match (lhs.simplify(), rhs.simplify()) {
+                (BExpr::True, _) | (_, BExpr::True) => BExpr::True,
+                (BExpr::False, e) | (e, BExpr::False) => e,
+                (l, r) if l == r => l,
+                (l, r) => l | r
+            },
+
+            BExpr::Not(rhs) => match rhs.simplify() {
+                BExpr::True => BExpr::False,
+                BExpr::False => BExpr::True,
+                BExpr::Not(e) => *e,
+                e => !e
+            },
+
+            BExpr::All(name, rhs) => match rhs.simplify() {
+                e @ (BExpr::True | BExpr::False) => e,
+                e => BExpr::all(name, e)
+            },
+            BExpr::Some(name, rhs) => match rhs.simplify() {
+                e @ (BExpr::True | BExpr::False) => e,
+                e => BExpr::some(name, e)
+            },
+
+            e => e
+        }
+    }
+
+    /// Converts this [BExpr] into Negation Normal Form. That is, all negations are moved inwards using
+    /// DeMorgan's laws until they only apply to predicates, and double negations are removed. E.g.
+    /// `!(P & !all x: Q(x))` becomes `!P | all x: Q(x)` and `!some x: P(x)` becomes `all x: !P(x)`.
+    /// Quantifiers are kept in place.
+    pub fn to_nnf(self) -> BExpr {
+        match self {
+            BExpr::And(lhs, rhs) => lhs.to_nnf() & rhs.to_nnf(),
+            BExpr::Or(lhs, rhs) => lhs.to_nnf() | rhs.to_nnf(),
+            BExpr::Not(rhs) => rhs.negated_nnf(),
+
+            BExpr::All(name, rhs) => BExpr::all(name, rhs.to_nnf()),
+            BExpr::Some(name, rhs) => BExpr::some(name, rhs.to_nnf()),
+
+            e => e
+        }
+    }
+
+    /// Converts the negation of this [BExpr] into Negation Normal Form.
+    fn negated_nnf(self) -> BExpr {
+        match self {
+            BExpr::True => BExpr::False,
+            BExpr::False => BExpr::True,
+
+            BExpr::And(lhs, rhs) => lhs.negated_nnf() | rhs.negated_nnf(),
+            BExpr::Or(lhs, rhs) => lhs.negated_nnf() & rhs.negated_nnf(),
+            BExpr::Not(rhs) => rhs.to_nnf(),
+
+            BExpr::All(name, rhs) => BExpr::some(name, rhs.negated_nnf()),
+            BExpr::Some(name, rhs) => BExpr::all(name, rhs.negated_nnf()),
+
+            e => !e
+        }
+    }
+
+    /// Renames the variables bound by quantifiers apart. That is, every quantifier in this
+    /// [BExpr] gets a fresh name that is not used anywhere else in the expression, and all the
+    /// occurrences of the variable it binds are renamed accordingly. E.g. `(all x: P(x)) & (all x: Q(x))`
+    /// becomes `(all x1: P(x1)) & (all x2: Q(x2))`.
+    /// 
+    /// Variables that are not bound by a quantifier are not renamed.
+    pub fn rename_bound_apart(self) -> BExpr {
+        let mut next_name = self.free();
+        self.rename_bound_apart_from(&mut next_name)
+    }
+
+    fn rename_bound_apart_from(self, next_name: &mut Name) -> BExpr {
+        match self {
+            BExpr::And(lhs, rhs) => lhs.rename_bound_apart_from(next_name) & rhs.rename_bound_apart_from(next_name),
+            BExpr::Or(lhs, rhs) => lhs.rename_bound_apart_from(next_name) | rhs.rename_bound_apart_from(next_name),
+            BExpr::Not(rhs) => !rhs.rename_bound_apart_from(next_name),
+
+            // Unification leaves variables that are rebound by nested quantifiers untouched, so
+            // only the occurrences bound by this quantifier are renamed.
+            BExpr::All(name, rhs) => {
+                let fresh = next_name.incr();
+                let rhs = rhs.unify(&Unifier::singleton(name, AExpr::var(fresh)));
+                BExpr::all(fresh, rhs.rename_bound_apart_from(next_name))
+            },
+            BExpr::Some(name, rhs) => {
+                let fresh = next_name.incr();
+                let rhs = rhs.unify(&Unifier::singleton(name, AExpr::var(fresh)));
+                BExpr::some(fresh, rhs.rename_bound_apart_from(next_name))
+            },
+
+            e => e
+        }
+    }
+}
+
+fn collect_functions(expr: &AExpr, funs: &mut BTreeSet<(Name, usize)>) {
+    if let AExpr::Fun(name, args) = expr {
+        funs.insert((*name, args.len()));
+
+        for arg in args {
+            collect_functions(arg, funs);
+        }
+    }
 }
 
 impl Default for BExpr {
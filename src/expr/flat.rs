@@ -0,0 +1,243 @@
+use super::{AExpr, BExpr, Name};
+
+/// A Boolean expression in which conjunctions and disjunctions are n-ary rather than binary. Chains of
+/// the same connective, like `P & Q & R`, which form a deep tree of binary [BExpr::And]s, are collapsed
+/// into a single [FlatBExpr::And] node. Other than that, this is the same as a [BExpr].
+///
+/// A [FlatBExpr] is obtained with [BExpr::flatten], and converted back with [FlatBExpr::unflatten]. Both
+/// conversions use an explicit stack rather than recursion, so that they can handle arbitrarily long
+/// chains of connectives without overflowing the call stack.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
+pub enum FlatBExpr {
+    /// The true constant, see [BExpr::True].
+    True,
+
+    /// The false constant, see [BExpr::False].
+    False,
+
+    /// A predicate, see [BExpr::Pred].
+    Pred(Name, Vec<AExpr>),
+
+    /// A conjunction of any amount of subexpressions, none of which is a conjunction itself. An empty
+    /// conjunction is true.
+    And(Vec<FlatBExpr>),
+
+    /// A disjunction of any amount of subexpressions, none of which is a disjunction itself. An empty
+    /// disjunction is false.
+    Or(Vec<FlatBExpr>),
+
+    /// The inverse of a subexpression, see [BExpr::Not].
+    Not(Box<FlatBExpr>),
+
+    /// A universally quantified subexpression, see [BExpr::All].
+    All(Name, Box<FlatBExpr>),
+
+    /// An existentially quantified subexpression, see [BExpr::Some].
+    Some(Name, Box<FlatBExpr>),
+}
+
+/// A pending step in the conversion between [BExpr] and [FlatBExpr]. Inputs are visited, and their
+/// converted subexpressions are collected until the node that contains them can be built.
+enum Task<I> {
+    Visit(I),
+    And(usize),
+    Or(usize),
+    Not,
+    All(Name),
+    Some(Name),
+}
+
+impl BExpr {
+    /// Converts this expression into a [FlatBExpr], by collapsing chains of conjunctions and chains of
+    /// disjunctions into n-ary nodes. The operands keep their order.
+    pub fn flatten(self) -> FlatBExpr {
+        let mut tasks = vec![Task::Visit(self)];
+        let mut out = Vec::new();
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(BExpr::True) => out.push(FlatBExpr::True),
+                Task::Visit(BExpr::False) => out.push(FlatBExpr::False),
+                Task::Visit(BExpr::Pred(name, args)) => out.push(FlatBExpr::Pred(name, args)),
+
+                Task::Visit(e @ BExpr::And(_, _)) => {
+                    let operands = chain_operands(e, true);
+                    tasks.push(Task::And(operands.len()));
+                    tasks.extend(operands.into_iter().rev().map(Task::Visit));
+                },
+
+                Task::Visit(e @ BExpr::Or(_, _)) => {
+                    let operands = chain_operands(e, false);
+                    tasks.push(Task::Or(operands.len()));
+                    tasks.extend(operands.into_iter().rev().map(Task::Visit));
+                },
+
+                Task::Visit(BExpr::Not(e)) => {
+                    tasks.push(Task::Not);
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::Visit(BExpr::All(name, e)) => {
+                    tasks.push(Task::All(name));
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::Visit(BExpr::Some(name, e)) => {
+                    tasks.push(Task::Some(name));
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::And(n) => {
+                    let operands = out.split_off(out.len() - n);
+                    out.push(FlatBExpr::And(operands));
+                },
+
+                Task::Or(n) => {
+                    let operands = out.split_off(out.len() - n);
+                    out.push(FlatBExpr::Or(operands));
+                },
+
+                Task::Not => {
+                    let e = out.pop().unwrap();
+                    out.push(FlatBExpr::Not(Box::new(e)));
+                },
+
+                Task::All(name) => {
+                    let e = out.pop().unwrap();
+                    out.push(FlatBExpr::All(name, Box::new(e)));
+                },
+
+                Task::Some(name) => {
+                    let e = out.pop().unwrap();
+                    out.push(FlatBExpr::Some(name, Box::new(e)));
+                },
+            }
+        }
+
+        out.pop().unwrap()
+    }
+}
+
+impl FlatBExpr {
+    /// Converts this expression back into a [BExpr]. N-ary conjunctions and disjunctions become
+    /// left-leaning chains of binary connectives, so that `P & Q & R` becomes `(P & Q) & R`, the way
+    /// it is parsed. An empty conjunction becomes `true`, and an empty disjunction becomes `false`.
+    pub fn unflatten(self) -> BExpr {
+        let mut tasks = vec![Task::Visit(self)];
+        let mut out = Vec::new();
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(FlatBExpr::True) => out.push(BExpr::True),
+                Task::Visit(FlatBExpr::False) => out.push(BExpr::False),
+                Task::Visit(FlatBExpr::Pred(name, args)) => out.push(BExpr::Pred(name, args)),
+
+                Task::Visit(FlatBExpr::And(operands)) => {
+                    tasks.push(Task::And(operands.len()));
+                    tasks.extend(operands.into_iter().rev().map(Task::Visit));
+                },
+
+                Task::Visit(FlatBExpr::Or(operands)) => {
+                    tasks.push(Task::Or(operands.len()));
+                    tasks.extend(operands.into_iter().rev().map(Task::Visit));
+                },
+
+                Task::Visit(FlatBExpr::Not(e)) => {
+                    tasks.push(Task::Not);
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::Visit(FlatBExpr::All(name, e)) => {
+                    tasks.push(Task::All(name));
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::Visit(FlatBExpr::Some(name, e)) => {
+                    tasks.push(Task::Some(name));
+                    tasks.push(Task::Visit(*e));
+                },
+
+                Task::And(n) => {
+                    let operands = out.split_off(out.len() - n);
+                    out.push(operands.into_iter().reduce(|l, r| l & r).unwrap_or(BExpr::True));
+                },
+
+                Task::Or(n) => {
+                    let operands = out.split_off(out.len() - n);
+                    out.push(operands.into_iter().reduce(|l, r| l | r).unwrap_or(BExpr::False));
+                },
+
+                Task::Not => {
+                    let e = out.pop().unwrap();
+                    out.push(!e);
+                },
+
+                Task::All(name) => {
+                    let e = out.pop().unwrap();
+                    out.push(BExpr::All(name, Box::new(e)));
+                },
+
+                Task::Some(name) => {
+                    let e = out.pop().unwrap();
+                    out.push(BExpr::Some(name, Box::new(e)));
+                },
+            }
+        }
+
+        out.pop().unwrap()
+    }
+
+    /// Returns whether this expression has no quantifiers. Like the conversions, this does not recurse.
+    pub fn is_quantifier_free(&self) -> bool {
+        let mut pending = vec![self];
+
+        while let Some(e) = pending.pop() {
+            match e {
+                FlatBExpr::True | FlatBExpr::False | FlatBExpr::Pred(_, _) => {},
+                FlatBExpr::And(operands) | FlatBExpr::Or(operands) => pending.extend(operands),
+                FlatBExpr::Not(e) => pending.push(e),
+                FlatBExpr::All(_, _) | FlatBExpr::Some(_, _) => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl From<BExpr> for FlatBExpr {
+    fn from(value: BExpr) -> Self {
+        value.flatten()
+    }
+}
+
+impl From<FlatBExpr> for BExpr {
+    fn from(value: FlatBExpr) -> Self {
+        value.unflatten()
+    }
+}
+
+/// Collects the operands of a chain of conjunctions (if `and` is set) or disjunctions, in order. The
+/// operands are not flattened themselves.
+fn chain_operands(e: BExpr, and: bool) -> Vec<BExpr> {
+    let mut pending = vec![e];
+    let mut operands = Vec::new();
+
+    while let Some(e) = pending.pop() {
+        match e {
+            BExpr::And(lhs, rhs) if and => {
+                pending.push(*rhs);
+                pending.push(*lhs);
+            },
+
+            BExpr::Or(lhs, rhs) if !and => {
+                pending.push(*rhs);
+                pending.push(*lhs);
+            },
+
+            e => operands.push(e)
+        }
+    }
+
+    operands
+}
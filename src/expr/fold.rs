@@ -0,0 +1,90 @@
+use super::{AExpr, BExpr, Name};
+
+/// A pass over a [BExpr] that computes a value of type `T` from the bottom up, see [BExpr::fold].
+///
+/// The `combine_*` methods and [BExprFolder::fold_const] and [BExprFolder::fold_pred] must be
+/// implemented, and determine the value of each node from the values of its subexpressions. The other
+/// `fold_*` methods are called when a node is visited, and by default fold the subexpressions and then
+/// combine their values. They can be overridden to inspect the subexpressions before folding, or to not
+/// fold them at all.
+///
+/// For example, counting the predicates in an expression only requires `fold_pred` to return 1,
+/// `fold_const` to return 0, and the `combine_*` methods to add up their operands.
+pub trait BExprFolder<T> {
+    /// Called on [BExpr::True] and [BExpr::False].
+    fn fold_const(&mut self, value: bool) -> T;
+
+    /// Called on a [BExpr::Pred].
+    fn fold_pred(&mut self, name: Name, args: &[AExpr]) -> T;
+
+    /// Combines the values of the operands of a [BExpr::And].
+    fn combine_and(&mut self, lhs: T, rhs: T) -> T;
+
+    /// Combines the values of the operands of a [BExpr::Or].
+    fn combine_or(&mut self, lhs: T, rhs: T) -> T;
+
+    /// Determines the value of a [BExpr::Not] from the value of its operand.
+    fn combine_not(&mut self, rhs: T) -> T;
+
+    /// Determines the value of a [BExpr::All] from the value of its body.
+    fn combine_all(&mut self, name: Name, rhs: T) -> T;
+
+    /// Determines the value of a [BExpr::Some] from the value of its body.
+    fn combine_some(&mut self, name: Name, rhs: T) -> T;
+
+    /// Called on a [BExpr::And]. By default, this folds both operands, left first, and combines them
+    /// with [BExprFolder::combine_and].
+    fn fold_and(&mut self, lhs: &BExpr, rhs: &BExpr) -> T where Self: Sized {
+        let lhs = lhs.fold(self);
+        let rhs = rhs.fold(self);
+        self.combine_and(lhs, rhs)
+    }
+
+    /// Called on a [BExpr::Or]. By default, this folds both operands, left first, and combines them
+    /// with [BExprFolder::combine_or].
+    fn fold_or(&mut self, lhs: &BExpr, rhs: &BExpr) -> T where Self: Sized {
+        let lhs = lhs.fold(self);
+        let rhs = rhs.fold(self);
+        self.combine_or(lhs, rhs)
+    }
+
+    /// Called on a [BExpr::Not]. By default, this folds the operand and passes it to
+    /// [BExprFolder::combine_not].
+    fn fold_not(&mut self, rhs: &BExpr) -> T where Self: Sized {
+        let rhs = rhs.fold(self);
+        self.combine_not(rhs)
+    }
+
+    /// Called on a [BExpr::All]. By default, this folds the body and passes it to
+    /// [BExprFolder::combine_all].
+    fn fold_all(&mut self, name: Name, rhs: &BExpr) -> T where Self: Sized {
+        let rhs = rhs.fold(self);
+        self.combine_all(name, rhs)
+    }
+
+    /// Called on a [BExpr::Some]. By default, this folds the body and passes it to
+    /// [BExprFolder::combine_some].
+    fn fold_some(&mut self, name: Name, rhs: &BExpr) -> T where Self: Sized {
+        let rhs = rhs.fold(self);
+        self.combine_some(name, rhs)
+    }
+}
+
+impl BExpr {
+    /// Folds this [BExpr] into a value using the given [BExprFolder], by calling the `fold_*` method
+    /// that corresponds to the variant of this expression.
+    pub fn fold<T>(&self, f: &mut impl BExprFolder<T>) -> T {
+        match self {
+            BExpr::True => f.fold_const(true),
+            BExpr::False => f.fold_const(false),
+            BExpr::Pred(name, args) => f.fold_pred(*name, args),
+
+            BExpr::And(lhs, rhs) => f.fold_and(lhs, rhs),
+            BExpr::Or(lhs, rhs) => f.fold_or(lhs, rhs),
+            BExpr::Not(rhs) => f.fold_not(rhs),
+
+            BExpr::All(name, rhs) => f.fold_all(*name, rhs),
+            BExpr::Some(name, rhs) => f.fold_some(*name, rhs),
+        }
+    }
+}
@@ -3,9 +3,13 @@ mod aexpr;
 mod bexpr;
 mod stmt;
 mod eqs;
+mod flat;
+mod fold;
 
 pub use name::*;
 pub use aexpr::*;
 pub use bexpr::*;
 pub use stmt::*;
 pub use eqs::*;
+pub use flat::*;
+pub use fold::*;
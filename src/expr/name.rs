@@ -9,6 +9,7 @@ use crate::util::fold::{Max, Min};
 /// A name is a value that can be used in place of a name. Names have a full order.
 /// To obtain a name, use [Name::any]. This will give any name. To obtain a name that is
 /// distinct from another name, call [Name::succ].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Clone, Copy)]
 pub struct Name(u64);
 
@@ -27,6 +28,13 @@ impl Name {
     pub fn incr(&mut self) -> Name {
         replace(self, self.succ())
     }
+
+    /// Offsets this name by the given base name. Offsetting all names of a problem by a name that is
+    /// higher than all names of another problem makes the names of both problems disjoint.
+    /// Panics if the offset name is out of range.
+    pub fn offset(&self, base: Name) -> Name {
+        Name(self.0.checked_add(base.0).expect("Name offset out of range"))
+    }
 }
 
 impl Default for Name {
@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display};
 
 use crate::fmt::{write_comma_separated, DisplayNamed, NameTable};
 
-use super::{BExpr, Names, Vars};
+use super::{BExpr, Name, Names, Vars};
 
 /// A logical statement.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Hash)]
 pub struct Stmt {
     premises: Vec<BExpr>,
@@ -42,6 +44,17 @@ impl Stmt {
         self.conclusions
     }
 
+    /// [Offsets][Name::offset] every name in this statement by the given base name. To merge two
+    /// independently parsed statements, shift the names of one by the [free name][Names::free] of the
+    /// other, so that their names do not collide. The [NameTable] of the shifted statement can be
+    /// shifted along with [NameTable::shift_names].
+    pub fn shift_names(self, base: Name) -> Stmt {
+        Self {
+            premises: self.premises.into_iter().map(|it| it.shift_names(base)).collect(),
+            conclusions: self.conclusions.into_iter().map(|it| it.shift_names(base)).collect(),
+        }
+    }
+
     /// Returns a refutable [BExpr] representing this statement, that is, it returns
     /// an expression whose unsatisfiability proves this statement.
     pub fn refutable_expr(self) -> BExpr {
@@ -59,6 +72,99 @@ impl Stmt {
 
         return p & c;
     }
+
+    /// Returns the expression that is refuted to prove this statement, without consuming the
+    /// statement. This is the conjunction of all premises and the negated conjunction of all
+    /// conclusions, as given by [Stmt::refutable_expr]. It can be converted to CNF with any
+    /// converter before handing it to the resolver.
+    pub fn as_refutation_bexpr(&self) -> BExpr {
+        self.clone().refutable_expr()
+    }
+
+    /// Returns the expression that is refuted to disprove this statement, without consuming the
+    /// statement. This is the conjunction of all premises and all conclusions, as given by
+    /// [Stmt::provable_expr].
+    pub fn as_disproof_bexpr(&self) -> BExpr {
+        self.clone().provable_expr()
+    }
+
+    /// Evaluates this statement under the given truth assignment of nullary predicates. The
+    /// statement holds under the assignment if not all premises hold or if all conclusions hold.
+    /// In particular, this returns `Some(false)` if the assignment is a counter-model of this
+    /// statement. Like [BExpr::eval], this returns [None] if any of the premises or conclusions
+    /// cannot be evaluated.
+    pub fn eval(&self, assignment: &BTreeMap<Name, bool>) -> Option<bool> {
+        let mut premises = true;
+        for premise in &self.premises {
+            premises &= premise.eval(assignment)?;
+        }
+
+        let mut conclusions = true;
+        for conclusion in &self.conclusions {
+            conclusions &= conclusion.eval(assignment)?;
+        }
+
+        Some(!premises | conclusions)
+    }
+}
+
+/// A builder for [Stmt]s, that adds premises and conclusions incrementally. It keeps a [NameTable]
+/// to allocate readable names, so that statements can be built without parsing them from text.
+#[derive(Debug, Clone)]
+pub struct StmtBuilder {
+    premises: Vec<BExpr>,
+    conclusions: Vec<BExpr>,
+    names: NameTable
+}
+
+impl StmtBuilder {
+    /// Creates a new builder with no premises, no conclusions and an empty [NameTable].
+    pub fn new() -> Self {
+        Self::with_names(NameTable::new())
+    }
+
+    /// Creates a new builder with no premises and no conclusions, that allocates names in the
+    /// given [NameTable].
+    pub fn with_names(names: NameTable) -> Self {
+        Self {
+            premises: Vec::new(),
+            conclusions: Vec::new(),
+            names
+        }
+    }
+
+    /// Gets the name for the given identifier, see [NameTable::name_for].
+    pub fn name(&mut self, identifier: &str) -> Name {
+        self.names.name_for(identifier)
+    }
+
+    /// Borrows the [NameTable] of this builder.
+    pub fn names(&self) -> &NameTable {
+        &self.names
+    }
+
+    /// Adds a premise to the statement.
+    pub fn premise(&mut self, premise: BExpr) -> &mut Self {
+        self.premises.push(premise);
+        self
+    }
+
+    /// Adds a conclusion to the statement.
+    pub fn conclusion(&mut self, conclusion: BExpr) -> &mut Self {
+        self.conclusions.push(conclusion);
+        self
+    }
+
+    /// Builds the [Stmt], along with the [NameTable] that holds the names used in it.
+    pub fn build(self) -> (Stmt, NameTable) {
+        (Stmt::from_implication(self.premises, self.conclusions), self.names)
+    }
+}
+
+impl Default for StmtBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 fn to_conj(mut expr: Vec<BExpr>) -> BExpr {
@@ -97,4 +97,40 @@ impl NameTable {
     pub fn write_entry(&self, name: &Name) -> String {
         return self.entry(name).write(name);
     }
+
+    /// Get the identifier of a name, if the name table has an entry with an identifier for it.
+    pub fn name_of(&self, name: &Name) -> Option<&str> {
+        self.table.get(name)?.identifier().map(String::as_str)
+    }
+
+    /// Returns this name table with all names [offset][Name::offset] by the given base name, to go with
+    /// an expression whose names have been shifted the same way.
+    pub fn shift_names(self, base: Name) -> NameTable {
+        Self {
+            table: self.table.into_iter().map(|(name, entry)| (name.offset(base), entry)).collect()
+        }
+    }
+
+    /// Get the [unbound][NameEntry::Unbound] name with the given identifier. If no such name
+    /// exists, a new name is allocated that is not used by any other entry in the name table. This
+    /// allows building expressions programmatically, with readable names, without going through
+    /// the parser.
+    pub fn name_for(&mut self, identifier: &str) -> Name {
+        let existing = self.table.iter().find(|(_, entry)| match entry {
+            NameEntry::Unbound(id) => id == identifier,
+            _ => false
+        });
+
+        if let Some((name, _)) = existing {
+            return *name;
+        }
+
+        let name = match self.table.last_key_value() {
+            Some((last, _)) => last.succ(),
+            None => Name::any()
+        };
+
+        self.add_unbound(name, identifier.to_string());
+        name
+    }
 }
\ No newline at end of file
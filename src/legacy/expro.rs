@@ -266,6 +266,29 @@ impl Expr {
 
         this
     }
+
+    /// Like [to_cnf], but gives up after `max_iters` rounds of [recursive_distribute]. Returns an error if the
+    /// expression did not reach conjunctive normal form within that many rounds.
+    #[allow(clippy::result_unit_err)]
+    pub fn to_cnf_bounded(&self, max_iters: usize) -> Result<Self, ()> {
+        let mut this = self.clone();
+
+        this = this.demorgan_pos();
+
+        for _ in 0..max_iters {
+            if this.is_cnf() {
+                return Ok(this);
+            }
+
+            this = this.recursive_distribute();
+        }
+
+        if this.is_cnf() {
+            Ok(this)
+        } else {
+            Err(())
+        }
+    }
 }
 
 impl Unifiable for Expr {
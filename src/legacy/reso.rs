@@ -1,4 +1,5 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
 
 use super::cnf::{Clause, Atom};
 use super::expro::Term;
@@ -225,6 +226,14 @@ impl Resolution {
     }
 }
 
+impl Display for Resolution {
+    /// Writes `proven` when the statement was proven, and `exhausted` otherwise, like
+    /// [ProofResult](crate::res::ProofResult) does.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(if self.satisfied { "exhausted" } else { "proven" })
+    }
+}
+
 
 
 
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use crate::fmt::{DisplayNamed, NameTable};
+
+use super::*;
+
+impl NormalForm {
+    /// Writes this normal form, interpreted as CNF, in the DIMACS CNF format that is understood by
+    /// most SAT solvers. Each distinct atom is numbered in order of appearance, and the atom each number
+    /// stands for is listed in a comment line. DIMACS only supports propositional problems, so this
+    /// returns [None] if any of the clauses contains variables.
+    pub fn to_dimacs(&self, names: &NameTable) -> Option<String> {
+        if !self.is_ground() {
+            return None;
+        }
+
+        let mut ids = BTreeMap::new();
+        let mut comments = String::new();
+        let mut body = String::new();
+
+        for clause in self.clauses() {
+            for (name, args, neg) in clause.iter_literals() {
+                let atom = Atom::Pred(name, args.clone());
+                let sign = if neg { -1i64 } else { 1i64 };
+
+                let next = ids.len() as i64 + 1;
+                let id = *ids.entry(atom.clone()).or_insert_with(|| {
+                    comments.push_str(&format!("c {next} {}\n", atom.with_table(names)));
+                    next
+                });
+
+                body.push_str(&format!("{} ", id * sign));
+            }
+
+            body.push_str("0\n");
+        }
+
+        Some(format!("{comments}p cnf {} {}\n{body}", ids.len(), self.len()))
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use crate::expr::{AExpr, BExpr, Name, Names};
+
+use super::*;
+
+/// The symbols used in a problem, along with their arities. It designates one binary predicate as
+/// the equality predicate, see [NormalForm::add_equality_axioms].
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Signature {
+    /// The name of the binary equality predicate.
+    pub equality: Name,
+
+    /// The function symbols and their arities. Constants are functions of arity 0.
+    pub functions: BTreeMap<Name, usize>,
+
+    /// The predicate symbols and their arities, except the equality predicate.
+    pub predicates: BTreeMap<Name, usize>
+}
+
+impl Signature {
+    /// Creates a new signature with no symbols besides the given equality predicate.
+    pub fn new(equality: Name) -> Self {
+        Self {
+            equality,
+            functions: BTreeMap::new(),
+            predicates: BTreeMap::new()
+        }
+    }
+
+    /// Collects the signature of all symbols used in the given [NormalForm].
+    pub fn of(nf: &NormalForm, equality: Name) -> Self {
+        let mut sig = Self::new(equality);
+
+        for clause in nf.clauses() {
+            for (name, args, _) in clause.iter_literals() {
+                if name != equality {
+                    sig.predicates.insert(name, args.len());
+                }
+
+                for arg in args {
+                    sig.add_functions(arg);
+                }
+            }
+        }
+
+        sig
+    }
+
+    fn add_functions(&mut self, expr: &AExpr) {
+        if let AExpr::Fun(name, args) = expr {
+            self.functions.insert(*name, args.len());
+
+            for arg in args {
+                self.add_functions(arg);
+            }
+        }
+    }
+
+    fn symbols(&self) -> Vec<Name> {
+        let mut symbols = vec![self.equality];
+        symbols.extend(self.functions.keys());
+        symbols.extend(self.predicates.keys());
+        symbols
+    }
+}
+
+impl BExpr {
+    /// Collects the [Signature] of all symbols used in this [BExpr], like [Signature::of] does for a
+    /// [NormalForm]. See [BExpr::predicates] and [BExpr::functions]. When a name is used with different
+    /// amounts of arguments, the signature holds the largest arity.
+    pub fn signature(&self, equality: Name) -> Signature {
+        let mut sig = Signature::new(equality);
+
+        for (name, arity) in self.predicates() {
+            if name != equality {
+                sig.predicates.insert(name, arity);
+            }
+        }
+
+        sig.functions.extend(self.functions());
+        sig
+    }
+}
+
+impl NormalForm {
+    /// Adds the axioms of equality to this CNF, for the equality predicate and symbols of the given
+    /// [Signature]. These are:
+    /// - Reflexivity: `:x = :x`.
+    /// - Symmetry: `:x != :y | :y = :x`.
+    /// - Transitivity: `:x != :y | :y != :z | :x = :z`.
+    /// - Congruence for each function `f` of arity `n > 0`:
+    ///   `:x1 != :y1 | ... | :xn != :yn | f(:x1, ..., :xn) = f(:y1, ..., :yn)`.
+    /// - Congruence for each predicate `P` of arity `n > 0`:
+    ///   `:x1 != :y1 | ... | :xn != :yn | !P(:x1, ..., :xn) | P(:y1, ..., :yn)`.
+    ///
+    /// With these axioms, resolution can reason about equality without paramodulation. The
+    /// variables in the axioms get names that are not used in this CNF or the signature.
+    pub fn add_equality_axioms(&mut self, signature: &Signature) {
+        let mut next = (&*self, signature.symbols()).free();
+
+        let eq = |a: &AExpr, b: &AExpr| Atom::Pred(signature.equality, vec![a.clone(), b.clone()]);
+
+        let x = AExpr::var(next.incr());
+        let y = AExpr::var(next.incr());
+        let z = AExpr::var(next.incr());
+
+        // Reflexivity
+        self.add(Clause::from_pos(eq(&x, &x)));
+
+        // Symmetry
+        self.add(Clause::from_slices([eq(&y, &x)], [eq(&x, &y)]));
+
+        // Transitivity
+        self.add(Clause::from_slices([eq(&x, &z)], [eq(&x, &y), eq(&y, &z)]));
+
+        let congruence = |arity: usize, next: &mut Name| {
+            let xs: Vec<_> = (0..arity).map(|_| AExpr::var(next.incr())).collect();
+            let ys: Vec<_> = (0..arity).map(|_| AExpr::var(next.incr())).collect();
+
+            let mut clause = Clause::new();
+            for (x, y) in xs.iter().zip(&ys) {
+                clause.add_neg(eq(x, y));
+            }
+
+            (clause, xs, ys)
+        };
+
+        for (f, arity) in &signature.functions {
+            if *arity == 0 {
+                continue;
+            }
+
+            let (mut clause, xs, ys) = congruence(*arity, &mut next);
+            clause.add_pos(eq(&AExpr::fun(*f, xs), &AExpr::fun(*f, ys)));
+            self.add(clause);
+        }
+
+        for (p, arity) in &signature.predicates {
+            if *arity == 0 {
+                continue;
+            }
+
+            let (mut clause, xs, ys) = congruence(*arity, &mut next);
+            clause.add_neg(Atom::Pred(*p, xs));
+            clause.add_pos(Atom::Pred(*p, ys));
+            self.add(clause);
+        }
+    }
+}
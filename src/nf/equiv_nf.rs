@@ -1,25 +1,34 @@
 use crate::expr::*;
 use super::*;
 
-/// Concatenate two clause sets, i.e. take the union of the two.
-/// In CNF this is the AND of two CNFs, in DNF this is the OR of two DNFs.
-fn concat(l: Clauses, r: Clauses) -> Clauses {
-    l.iter().chain(r.iter()).map(|it| it.clone()).collect()
-}
-
 /// Distribute two clause sets, i.e. concatenate the cartesian product. 
 /// In CNF this is the OR of two CNFs, in DNF this is the AND of two DNFs.
-fn distribute(l: Clauses, r: Clauses) -> Clauses {
+/// Non-disjoint clauses are removed unless the conversion keeps them.
+fn distribute(l: Clauses, r: Clauses, conv: &Conversion) -> Result<Clauses, CnfTooLarge> {
     l.iter()
      .flat_map(|le| r.iter().map(|re| le.clone().concat(re.clone()))) // Cartesian product concat
-     .filter(|e| e.is_disjoint()) // Remove tautologies
+     .filter(|e| conv.keep_tautologies || e.is_disjoint()) // Remove tautologies
+     .map(|e| conv.check(e))
      .collect()
 }
 
-/// Inverts the literals in a clause set, i.e. flips all the logical NOT signs.
-/// This converts a CNF into the DNF of its inverse, and a DNF into the CNF of its inverse.
-fn invert(c: Clauses) -> Clauses {
-    c.into_iter().map(|it| it.reverse()).collect() 
+/// Splits a clause into a clause set of single-literal clauses.
+fn split(c: Clause) -> Clauses {
+    let (pos, neg) = c.into_atoms();
+
+    let pos = pos.iter_preds().map(|(name, args)| Clause::from_pos(Atom::Pred(name, args.clone())));
+    let neg = neg.iter_preds().map(|(name, args)| Clause::from_neg(Atom::Pred(name, args.clone())));
+
+    pos.chain(neg).collect()
+}
+
+/// Converts a DNF into an equivalent CNF, or a CNF into an equivalent DNF, by distributing
+/// the clauses over eachother.
+pub fn redistribute(c: Clauses) -> Clauses {
+    c.into_iter().fold(
+        Clauses::from([Clause::new()]),
+        |acc, clause| distribute(acc, split(clause), &Conversion::UNBOUNDED).expect("Conversion is unbounded")
+    )
 }
 
 // fn demorgan_pos(e: BExpr) -> BExpr {
@@ -103,34 +112,163 @@ fn invert(c: Clauses) -> Clauses {
 //     }
 // }
 
+/// The error returned by [NormalForm::equiv_cnf_bounded] when converting an expression would produce a
+/// clause with more literals than allowed.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CnfTooLarge {
+    /// The maximum amount of literals per clause that was allowed.
+    pub max_clause_len: usize,
+
+    /// The amount of literals of the first clause that exceeded the limit.
+    pub clause_len: usize
+}
+
+impl Display for CnfTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CNF has a clause of {} literals, but at most {} are allowed", self.clause_len, self.max_clause_len)
+    }
+}
+
+/// The settings of a conversion to CNF or DNF.
+struct Conversion {
+    keep_tautologies: bool,
+    max_clause_len: usize
+}
+
+impl Conversion {
+    const UNBOUNDED: Self = Self { keep_tautologies: false, max_clause_len: usize::MAX };
+
+    /// Returns the given clause, or [CnfTooLarge] if it has too many literals.
+    fn check(&self, clause: Clause) -> Result<Clause, CnfTooLarge> {
+        if clause.len() > self.max_clause_len {
+            return Err(CnfTooLarge { max_clause_len: self.max_clause_len, clause_len: clause.len() });
+        }
+
+        Ok(clause)
+    }
+}
+
 /// Converts an expression into CNF.
 pub fn cnf(e: BExpr) -> Clauses {
-    match e {
-        BExpr::True => Clauses::new(),
-        BExpr::False => Clauses::from([Clause::new()]),
-        BExpr::Pred(name, args) => Clauses::from([Atom::Pred(name, args).into()]),
-        BExpr::And(lhs, rhs) => concat(cnf(*lhs), cnf(*rhs)),
-        BExpr::Or(lhs, rhs) => distribute(cnf(*lhs), cnf(*rhs)),
-        BExpr::Not(rhs) => invert(dnf(*rhs)),
-
-        _ => {
-            panic!("Expression not Skolemised")
-        },
-    }
+    convert(prepare(e), true, &Conversion::UNBOUNDED).expect("Conversion is unbounded")
+}
+
+/// Converts an expression into CNF, without removing tautological clauses.
+pub fn cnf_keep_tautologies(e: BExpr) -> Clauses {
+    let conv = Conversion { keep_tautologies: true, ..Conversion::UNBOUNDED };
+    convert(prepare(e), true, &conv).expect("Conversion is unbounded")
+}
+
+/// Converts an expression into CNF, failing as soon as a clause with more than the given amount
+/// of literals is produced.
+pub fn cnf_bounded(e: BExpr, max_clause_len: usize) -> Result<Clauses, CnfTooLarge> {
+    let conv = Conversion { max_clause_len, ..Conversion::UNBOUNDED };
+    convert(prepare(e), true, &conv)
 }
 
 /// Converts an expression into DNF.
 pub fn dnf(e: BExpr) -> Clauses {
-    match e {
-        BExpr::False => Clauses::new(),
-        BExpr::True => Clauses::from([Clause::new()]),
-        BExpr::Pred(name, args) => Clauses::from([Atom::Pred(name, args).into()]),
-        BExpr::And(lhs, rhs) => distribute(dnf(*lhs), dnf(*rhs)),
-        BExpr::Or(lhs, rhs) => concat(dnf(*lhs), dnf(*rhs)),
-        BExpr::Not(rhs) => invert(cnf(*rhs)),
-
-        _ => {
-            panic!("Expression not Skolemised")
-        },
+    convert(prepare(e), false, &Conversion::UNBOUNDED).expect("Conversion is unbounded")
+}
+
+/// Flattens and, if it has quantifiers, Skolemises an expression. Skolemisation is recursive, so it is
+/// skipped for quantifier-free expressions, which allows to convert arbitrarily deep propositional
+/// expressions.
+fn prepare(e: BExpr) -> FlatBExpr {
+    let e = e.flatten();
+
+    if e.is_quantifier_free() {
+        return e;
+    }
+
+    skolemise::skolemise(e.unflatten()).flatten()
+}
+
+/// A pending step in [convert].
+enum Task {
+    /// Converts an expression, into CNF if the first flag is set, otherwise into DNF. If the second
+    /// flag is set, the negation of the expression is converted.
+    Visit(FlatBExpr, bool, bool),
+
+    /// Concatenates the last given amount of converted clause sets.
+    Concat(usize),
+
+    /// Distributes the last given amount of converted clause sets over eachother.
+    Distribute(usize),
+}
+
+/// Converts a flattened expression into CNF if `cnf` is set, otherwise into DNF, with the given
+/// conversion settings. Negations are moved inwards along the way, by DeMorgan's law, so the clause
+/// limit applies to negated subexpressions as well.
+///
+/// The expression is traversed with an explicit stack rather than by recursion, so that deeply nested
+/// expressions do not overflow the call stack.
+fn convert(e: FlatBExpr, cnf: bool, conv: &Conversion) -> Result<Clauses, CnfTooLarge> {
+    let mut tasks = vec![Task::Visit(e, cnf, false)];
+    let mut out: Vec<Clauses> = Vec::new();
+
+    while let Some(task) = tasks.pop() {
+        match task {
+            // In CNF, true has no clauses and false has the empty clause, and vice versa in DNF
+            Task::Visit(e @ (FlatBExpr::True | FlatBExpr::False), cnf, neg) => {
+                let value = (e == FlatBExpr::True) != neg;
+
+                out.push(if value == cnf { Clauses::new() } else { Clauses::from([Clause::new()]) });
+            },
+
+            Task::Visit(FlatBExpr::Pred(name, args), _, neg) => {
+                let atom = Atom::Pred(name, args);
+                let clause = if neg { Clause::from_neg(atom) } else { Clause::from_pos(atom) };
+
+                out.push(Clauses::from([conv.check(clause)?]));
+            },
+
+            // In CNF, conjunctions concatenate and disjunctions distribute, and vice versa in DNF. By
+            // DeMorgan, a negated conjunction is a disjunction of negations and vice versa.
+            Task::Visit(FlatBExpr::And(operands), cnf, neg) => {
+                let concat = cnf != neg;
+
+                tasks.push(if concat { Task::Concat(operands.len()) } else { Task::Distribute(operands.len()) });
+                tasks.extend(operands.into_iter().rev().map(|it| Task::Visit(it, cnf, neg)));
+            },
+
+            Task::Visit(FlatBExpr::Or(operands), cnf, neg) => {
+                let concat = cnf == neg;
+
+                tasks.push(if concat { Task::Concat(operands.len()) } else { Task::Distribute(operands.len()) });
+                tasks.extend(operands.into_iter().rev().map(|it| Task::Visit(it, cnf, neg)));
+            },
+
+            Task::Visit(FlatBExpr::Not(e), cnf, neg) => {
+                tasks.push(Task::Visit(*e, cnf, !neg));
+            },
+
+            Task::Visit(FlatBExpr::All(..) | FlatBExpr::Some(..), _, _) => {
+                panic!("Expression not Skolemised")
+            },
+
+            Task::Concat(n) => {
+                // Extend the largest set, so that long chains do not copy their clauses over and over
+                let mut sets = out.split_off(out.len() - n);
+                sets.sort_by_key(|it| std::cmp::Reverse(it.len()));
+
+                let mut sets = sets.into_iter();
+                let mut acc = sets.next().unwrap_or_default();
+                for clauses in sets {
+                    acc.extend(clauses);
+                }
+                out.push(acc);
+            },
+
+            Task::Distribute(n) => {
+                let mut acc = Clauses::from([Clause::new()]);
+                for clauses in out.split_off(out.len() - n) {
+                    acc = distribute(acc, clauses, conv)?;
+                }
+                out.push(acc);
+            },
+        }
     }
+
+    Ok(out.pop().unwrap())
 }
@@ -1,10 +1,14 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
 use std::mem::replace;
 
 use crate::expr::{AExpr, Name, Names, Vars};
 use crate::nf::Atom;
 use crate::uni::Unifiable;
 
+// Serialized as a list of atoms, so that deserialization goes through `insert_pred` and
+// never produces a predicate name that maps to an empty set of arguments.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "Vec<Atom>", into = "Vec<Atom>"))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Hash)]
 pub struct PredicateIndex {
     preds: BTreeMap<Name, BTreeSet<Vec<AExpr>>>
@@ -118,10 +122,8 @@ impl PredicateIndex {
     }
 
     /// Retrns an [Iterator] that iterates all predicates in the index.
-    pub fn iter_preds(&self) -> impl Iterator<Item = (Name, &Vec<AExpr>)> {
-        self.preds.iter().flat_map(|(name, set)| {
-            set.iter().map(|args| (*name, args))
-        })
+    pub fn iter_preds(&self) -> Preds<'_> {
+        Preds { names: self.preds.iter(), args: None }
     }
 
 
@@ -143,6 +145,25 @@ impl PredicateIndex {
         self.preds.is_empty() || self.preds.values().all(|it| it.is_empty())
     }
 
+    /// Tests whether this [PredicateIndex] is a subset of another. This is
+    /// true if every predicate in this index also appears in the other.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        for (name, set) in self.preds.iter() {
+            if set.is_empty() {
+                continue;
+            }
+
+            match other.get_preds(name) {
+                Some(other_set) => if !set.is_subset(other_set) {
+                    return false;
+                },
+                None => return false
+            }
+        }
+
+        true
+    }
+
     /// Tests whether this [PredicateIndex] is disjoint from another. This
     /// is true if no predicate in this indx appears in the other, and vice versa.
     pub fn is_disjoint(&self, other: &Self) -> bool {
@@ -158,6 +179,68 @@ impl PredicateIndex {
     }
 }
 
+impl<'a> IntoIterator for &'a PredicateIndex {
+    type Item = (Name, &'a Vec<AExpr>);
+    type IntoIter = Preds<'a>;
+
+    /// Iterates all predicates in the index, see [PredicateIndex::iter_preds].
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_preds()
+    }
+}
+
+impl IntoIterator for PredicateIndex {
+    type Item = Atom;
+    type IntoIter = IntoAtoms;
+
+    /// Iterates all predicates in the index as [Atom]s.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoAtoms { names: self.preds.into_iter(), args: None }
+    }
+}
+
+/// An [Iterator] over the predicates in a [PredicateIndex], see [PredicateIndex::iter_preds].
+pub struct Preds<'a> {
+    names: btree_map::Iter<'a, Name, BTreeSet<Vec<AExpr>>>,
+    args: Option<(Name, btree_set::Iter<'a, Vec<AExpr>>)>
+}
+
+impl<'a> Iterator for Preds<'a> {
+    type Item = (Name, &'a Vec<AExpr>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((name, args)) = &mut self.args && let Some(args) = args.next() {
+                return Some((*name, args));
+            }
+
+            let (name, set) = self.names.next()?;
+            self.args = Some((*name, set.iter()));
+        }
+    }
+}
+
+/// An [Iterator] that moves the predicates out of a [PredicateIndex] as [Atom]s.
+pub struct IntoAtoms {
+    names: btree_map::IntoIter<Name, BTreeSet<Vec<AExpr>>>,
+    args: Option<(Name, btree_set::IntoIter<Vec<AExpr>>)>
+}
+
+impl Iterator for IntoAtoms {
+    type Item = Atom;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((name, args)) = &mut self.args && let Some(args) = args.next() {
+                return Some(Atom::Pred(*name, args));
+            }
+
+            let (name, set) = self.names.next()?;
+            self.args = Some((name, set.into_iter()));
+        }
+    }
+}
+
 impl From<Atom> for PredicateIndex {
     fn from(value: Atom) -> Self {
         let mut new = Self::new();
@@ -196,6 +279,12 @@ impl From<BTreeSet<Atom>> for PredicateIndex {
     }
 }
 
+impl From<PredicateIndex> for Vec<Atom> {
+    fn from(value: PredicateIndex) -> Self {
+        value.iter_preds().map(|(name, args)| Atom::Pred(name, args.clone())).collect()
+    }
+}
+
 impl<const N: usize> From<[Atom; N]> for PredicateIndex {
     fn from(value: [Atom; N]) -> Self {
         let mut new = Self::new();
@@ -217,7 +306,7 @@ impl Names for PredicateIndex {
 
 impl Vars for PredicateIndex {
     fn vars<A>(&self) -> A where A : FromIterator<Name> {
-        return self.preds.values().flat_map(|it| it.names::<Vec<_>>()).collect();
+        return self.preds.values().flat_map(|it| it.vars::<Vec<_>>()).collect();
     }
 }
 
@@ -1,11 +1,15 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 use crate::expr::{AExpr, BExpr, Name, Names, Vars};
 use crate::fmt::{write_comma_separated, DisplayNamed, NameTable};
-use crate::uni::Unifiable;
+use crate::uni::{Unifiable, Unifier};
 
-pub use index::PredicateIndex;
+pub use index::{IntoAtoms, PredicateIndex, Preds};
+pub use equality::Signature;
+pub use equiv_nf::CnfTooLarge;
+pub use skolemise::{prenex, skolemise, skolemise_antiprenex};
 
 pub type Atoms = BTreeSet<Atom>;
 pub type Clauses = BTreeSet<Clause>;
@@ -23,7 +27,14 @@ mod skolemise;
 /// Module for atom indexing.
 mod index;
 
+/// Module for exporting CNF in DIMACS format.
+mod dimacs;
+
+/// Module for the axioms of equality.
+mod equality;
+
 /// An atomic expression. Atoms are the leaves of a [BExpr] tree.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub enum Atom {
     Pred(Name, Vec<AExpr>)
@@ -36,6 +47,7 @@ pub enum Atom {
 /// called the *positive set* and the *negative set*.
 /// 
 /// For example, the disjunctive clause `P | !Q | R` is represented as `pos: {P, R}, neg: {Q}`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct Clause {
     pos: PredicateIndex,
@@ -45,11 +57,81 @@ pub struct Clause {
 /// Normal Form: A conjunction or disjunction of clauses. Depending on context, it can represent
 /// either Conjunctive Normal Form (CNF) or Disjunctive Normal Form (DNF).
 /// This struct simply represents a set of [Clause]s, therefore it can act both as CNF and DNF.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct NormalForm {
     clauses: Clauses
 }
 
+impl Atom {
+    /// Creates a predicate atom with the given name and arguments.
+    pub fn pred(name: Name, args: Vec<AExpr>) -> Atom {
+        Atom::Pred(name, args)
+    }
+
+    /// The name of the predicate of this atom.
+    pub fn name(&self) -> Name {
+        match self {
+            Atom::Pred(name, _) => *name
+        }
+    }
+
+    /// The arguments of this atom.
+    pub fn args(&self) -> &[AExpr] {
+        match self {
+            Atom::Pred(_, args) => args
+        }
+    }
+
+    /// The amount of arguments of this atom.
+    pub fn arity(&self) -> usize {
+        self.args().len()
+    }
+
+    /// The maximum depth of the terms in this atom, see [AExpr::depth]. An atom without arguments
+    /// has depth 0.
+    pub fn depth(&self) -> usize {
+        match self {
+            Atom::Pred(_, args) => args.iter().map(AExpr::depth).max().unwrap_or(0)
+        }
+    }
+
+    /// The amount of symbols in this atom, counting the predicate and every symbol in its arguments,
+    /// see [AExpr::symbol_count].
+    pub fn symbol_count(&self) -> usize {
+        match self {
+            Atom::Pred(_, args) => 1 + args.iter().map(AExpr::symbol_count).sum::<usize>()
+        }
+    }
+
+    /// Returns whether all arguments of this atom are ground, see [AExpr::is_ground].
+    pub fn is_ground(&self) -> bool {
+        match self {
+            Atom::Pred(_, args) => args.iter().all(AExpr::is_ground)
+        }
+    }
+
+    /// Renames the function symbol `from` to `to` in the arguments of this atom, see [AExpr::rename_fun].
+    /// The predicate name is left as is.
+    pub fn rename_fun(self, from: Name, to: Name) -> Atom {
+        match self {
+            Atom::Pred(name, args) => Atom::Pred(name, args.into_iter().map(|it| it.rename_fun(from, to)).collect())
+        }
+    }
+}
+
+/// Statistics about the size of a [NormalForm], see [NormalForm::stats].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct NfStats {
+    /// The amount of clauses, see [NormalForm::clause_count].
+    pub clause_count: usize,
+
+    /// The amount of literals, see [NormalForm::literal_count].
+    pub literal_count: usize,
+
+    /// The names of all predicates, see [NormalForm::predicate_names].
+    pub predicate_names: BTreeSet<Name>
+}
 
 impl Clause {
     /// Constructs a new empty clause.
@@ -139,6 +221,48 @@ impl Clause {
         (self.pos, self.neg)
     }
 
+    /// Iterates over the literals in this clause, first the positive ones and then the negative ones.
+    /// Each literal is given as the name and arguments of its atom, along with whether the literal is
+    /// negative.
+    pub fn iter_literals(&self) -> impl Iterator<Item = (Name, &Vec<AExpr>, bool)> {
+        let pos = self.pos.iter_preds().map(|(name, args)| (name, args, false));
+        let neg = self.neg.iter_preds().map(|(name, args)| (name, args, true));
+
+        pos.chain(neg)
+    }
+
+    /// Returns the amount of literals in this clause.
+    pub fn len(&self) -> usize {
+        self.pos.iter_preds().count() + self.neg.iter_preds().count()
+    }
+
+    /// Returns whether all literals of this clause are ground, see [AExpr::is_ground].
+    pub fn is_ground(&self) -> bool {
+        self.iter_literals().all(|(_, args, _)| args.iter().all(AExpr::is_ground))
+    }
+
+    /// The maximum depth of the terms in this clause, see [AExpr::depth]. A clause without terms
+    /// has depth 0.
+    pub fn depth(&self) -> usize {
+        self.iter_literals()
+            .flat_map(|(_, args, _)| args.iter().map(AExpr::depth))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// If this clause is a unit clause, that is, it has exactly one literal, this returns the atom
+    /// of this literal, along with whether the literal is negative. Otherwise, [None] is returned.
+    pub fn unit(&self) -> Option<(Atom, bool)> {
+        if self.len() != 1 {
+            return None;
+        }
+
+        let neg = self.pos.is_empty();
+        let index = if neg { &self.neg } else { &self.pos };
+
+        index.iter_preds().next().map(|(name, args)| (Atom::Pred(name, args.clone()), neg))
+    }
+
     /// Concatenates two clauses. This creates a new clause whose positive set is the union
     /// of the two positive sets of this and the given clause, and whose negative set is the
     /// union of the two negative sets of this and the given clause.
@@ -158,6 +282,35 @@ impl Clause {
     }
 
 
+    /// Tests if this clause is a Horn clause, that is, it has at most one positive literal.
+    pub fn is_horn(&self) -> bool {
+        self.pos.iter_preds().count() <= 1
+    }
+
+    /// Formats this clause in the style of a Prolog rule, if it is a [Horn clause][Clause::is_horn]. The
+    /// positive literal is the head and the negative literals form the body: `P(:x) :- Q(:x), R().`. A
+    /// clause without negative literals is a fact, `P(a()).`, and a clause without a positive literal is a
+    /// goal, `:- Q(a()).`. The empty clause is the goal `:- true.`. Clauses that are not Horn clauses are
+    /// formatted as usual.
+    pub fn fmt_prolog(&self, names: &NameTable) -> String {
+        if !self.is_horn() {
+            return self.with_table(names).to_string();
+        }
+
+        let head = self.pos.iter_preds().next().map(|(name, args)| Atom::Pred(name, args.clone()));
+        let body = self.neg.iter_preds()
+            .map(|(name, args)| Atom::Pred(name, args.clone()).with_table(names).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match head {
+            Some(head) if body.is_empty() => format!("{}.", head.with_table(names)),
+            Some(head) => format!("{} :- {body}.", head.with_table(names)),
+            None if body.is_empty() => ":- true.".to_string(),
+            None => format!(":- {body}."),
+        }
+    }
+
     /// Tests if this clause is disjoint. That is, it tests if the positive and negative
     /// sets of the clause are disjoint. When these sets are not disjoint, there is at least
     /// one atom that appears as both a positive and a negative literal in this clause.
@@ -168,6 +321,118 @@ impl Clause {
         self.pos.is_disjoint(&self.neg)
     }
 
+    /// Tests whether every literal of this clause also appears in the other clause, with the same
+    /// polarity. In a disjunctive clause (CNF) this means this clause propositionally subsumes the
+    /// other. See [PredicateIndex::is_subset].
+    pub fn is_subset_of(&self, other: &Clause) -> bool {
+        self.pos.is_subset(&other.pos) && self.neg.is_subset(&other.neg)
+    }
+
+    /// Applies the given unifier to this clause and returns the result, unless the result is a
+    /// tautology, in which case [None] is returned. A clause is a tautology when an atom appears both
+    /// positively and negatively after substitution, see [Clause::is_disjoint]. Atoms that become
+    /// equal after substitution are merged.
+    pub fn apply_and_simplify(self, unifier: &Unifier) -> Option<Clause> {
+        let clause = self.unify(unifier);
+
+        if clause.is_disjoint() {
+            Some(clause)
+        } else {
+            None
+        }
+    }
+
+    /// Tests if this clause has an instance that is not disjoint. That is, it tests whether any
+    /// atom in the positive set unifies with any atom of the same predicate in the negative set.
+    /// For example, `P(:x) | !P(a)` is disjoint, but its instance `P(a) | !P(a)` is not.
+    /// 
+    /// Note that a clause for which this returns `true` is not necessarily a tautology itself, only
+    /// the instance obtained by the found unifier is. A non-disjoint clause always passes this test.
+    pub fn is_tautology_modulo_unification(&self) -> bool {
+        self.pos.iter_pred_names().any(|name| {
+            let (Some(pos), Some(neg)) = (self.pos.get_preds(name), self.neg.get_preds(name)) else {
+                return false;
+            };
+
+            pos.iter().any(|p| neg.iter().any(|n| Unifier::mgu(p, n).is_some()))
+        })
+    }
+
+    /// Renames the variables of this clause to a canonical sequence of names, starting at [Name::any],
+    /// in order of first occurrence. The literals are traversed in an order that does not depend on
+    /// variable names, so that clauses that only differ in the names of their variables (alpha-variants)
+    /// become equal. E.g. `P(:x) | !Q(:x, :y)` and `P(:z) | !Q(:z, :x)` both become `P(:v0) | !Q(:v0, :v1)`,
+    /// where `:v0` and `:v1` are the first two names.
+    /// 
+    /// Literals that are equal up to variable names cannot be told apart, so the order in which they are
+    /// traversed depends on their variables. Alpha-variants with multiple such literals, like
+    /// `P(:x, :y) | P(:y, :x)`, may therefore be canonicalized differently.
+    /// 
+    /// Note that the canonical names may coincide with names used elsewhere.
+    pub fn canonicalize(self) -> Clause {
+        let mut literals: Vec<_> = self.iter_literals().map(|(name, args, neg)| (neg, name, args)).collect();
+
+        literals.sort_by_cached_key(|(neg, name, args)| (*neg, *name, args.iter().map(erase_vars).collect::<Vec<_>>()));
+
+        let mut renaming = BTreeMap::new();
+        let mut next_name = Name::any();
+        for (_, _, args) in &literals {
+            for arg in args.iter() {
+                collect_renaming(arg, &mut renaming, &mut next_name);
+            }
+        }
+
+        let mut clause = Clause::new();
+        for (neg, name, args) in literals {
+            let atom = Atom::Pred(name, args.iter().map(|it| rename_vars(it, &renaming)).collect());
+
+            if neg {
+                clause.add_neg(atom);
+            } else {
+                clause.add_pos(atom);
+            }
+        }
+
+        clause
+    }
+
+    /// Computes a hash of this clause that does not depend on the names of its variables, by hashing
+    /// its [canonical form](Clause::canonicalize). Alpha-variants thus hash equally, with the same caveat
+    /// as for [Clause::canonicalize]. The hash does not vary between runs of the same build, but since it
+    /// uses [DefaultHasher], whose algorithm is unspecified, it may change between Rust releases.
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.clone().canonicalize().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Transforms every literal of this clause with the given function. The function receives the atom of
+    /// each literal along with whether the literal is negative, like [Clause::iter_literals], and returns
+    /// the new atom and polarity. Literals that become equal are merged.
+    pub fn map_atoms<F>(self, mut f: F) -> Clause where F : FnMut(Atom, bool) -> (Atom, bool) {
+        let pos = self.pos.into_iter().map(|atom| (atom, false));
+        let neg = self.neg.into_iter().map(|atom| (atom, true));
+
+        let mut clause = Clause::new();
+        for (atom, neg) in pos.chain(neg) {
+            let (atom, neg) = f(atom, neg);
+
+            if neg {
+                clause.add_neg(atom);
+            } else {
+                clause.add_pos(atom);
+            }
+        }
+
+        clause
+    }
+
+    /// Renames the function symbol `from` to `to` in all literals of this clause, see [AExpr::rename_fun].
+    /// Literals that become equal after renaming are merged.
+    pub fn rename_fun(self, from: Name, to: Name) -> Clause {
+        self.map_atoms(|atom, neg| (atom.rename_fun(from, to), neg))
+    }
+
     /// Returns the reverse of this clause, swapping the positive and negative sets. This has
     /// the effect of turning a conjunctive clause into an inverted disjunctive clause, and
     /// a disjunctive clause into an inverted conjunctive clause.
@@ -244,12 +509,43 @@ impl NormalForm {
         self.clauses.is_empty()
     }
 
+    /// Returns whether all clauses in this normal form are Horn clauses, see [Clause::is_horn].
+    /// Horn CNFs can be solved by unit propagation alone.
+    pub fn is_horn(&self) -> bool {
+        self.clauses.iter().all(Clause::is_horn)
+    }
+
     /// Returns whether any clause in this normal form is empty. In CNF, this means the whole CNF is
     /// a contradiction. In DNF, this means the whole DNF is a tautology. See [Clause::is_empty].
     pub fn has_empty_clause(&self) -> bool {
         self.clauses.iter().any(|it| it.is_empty())
     }
 
+    /// Interprets this normal form as a DNF and tests whether it is satisfiable, that is, whether any of
+    /// its clauses is satisfiable when interpreted as a conjunction. A ground conjunctive clause is
+    /// satisfiable if and only if it is [disjoint][Clause::is_disjoint]. Since variables in a DNF are
+    /// universally quantified, a conjunctive clause with variables like `P(:x) & !P(a)` may be
+    /// contradictory even if it is disjoint. Such a clause is only considered satisfiable when no
+    /// predicate appears both positively and negatively in it.
+    /// 
+    /// For ground DNFs this is an exact satisfiability test, and it is cheap once the DNF is known. For
+    /// other DNFs, `false` means that no clause could be shown to be satisfiable.
+    pub fn is_dnf_satisfiable(&self) -> bool {
+        self.clauses.iter().any(|clause| {
+            if clause.is_ground() {
+                clause.is_disjoint()
+            } else {
+                clause.pos.iter_pred_names().all(|name| !clause.neg.contains_preds(name))
+            }
+        })
+    }
+
+    /// Returns the clauses in this normal form that are not disjoint. In CNF these are the
+    /// tautological clauses, in DNF these are the contradictory clauses. See [Clause::is_disjoint].
+    pub fn non_disjoint_clauses(&self) -> impl Iterator<Item = &Clause> {
+        self.clauses.iter().filter(|it| !it.is_disjoint())
+    }
+
     /// Returns whether any clause in this normal form is disjoint. See [Clause::is_disjoint].
     pub fn has_disjoint_clause(&self) -> bool {
         self.clauses.iter().any(|it| it.is_disjoint())
@@ -262,17 +558,249 @@ impl NormalForm {
         self.clauses.retain(|it| it.is_disjoint());
     }
 
+    /// Returns whether all clauses in this normal form are ground, see [Clause::is_ground].
+    pub fn is_ground(&self) -> bool {
+        self.clauses.iter().all(Clause::is_ground)
+    }
+
     /// Returns the amount of clauses in this normal form.
     pub fn len(&self) -> usize {
         self.clauses.len()
     }
 
+    /// Returns the amount of clauses in this normal form. Same as [NormalForm::len].
+    pub fn clause_count(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns the amount of literals in this normal form, summed over all clauses. See [Clause::len].
+    pub fn literal_count(&self) -> usize {
+        self.clauses.iter().map(Clause::len).sum()
+    }
+
+    /// Returns the names of all predicates used in this normal form, positively or negatively.
+    pub fn predicate_names(&self) -> BTreeSet<Name> {
+        self.clauses.iter()
+            .flat_map(|it| it.pos.iter_pred_names().chain(it.neg.iter_pred_names()))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the [NfStats] of this normal form.
+    pub fn stats(&self) -> NfStats {
+        NfStats {
+            clause_count: self.clause_count(),
+            literal_count: self.literal_count(),
+            predicate_names: self.predicate_names()
+        }
+    }
+
+    /// Removes all clauses containing a pure literal from this CNF, and returns how many clauses
+    /// were removed. A literal is pure if its predicate appears only positively or only negatively
+    /// in the whole CNF. Such a predicate can be interpreted as always true (or always false), which
+    /// satisfies every clause it appears in, so removing these clauses preserves satisfiability but
+    /// not equivalence.
+    /// 
+    /// Purity is decided by predicate name rather than by atom, which keeps the simplification sound
+    /// in first-order logic: `P(a) | Q` and `!P(:x)` share the predicate `P` in complementary forms
+    /// so neither literal is pure, even though `P(a)` and `P(:x)` are different atoms.
+    /// 
+    /// Removing clauses may make other literals pure, so this repeats until no more pure literals
+    /// are found.
+    pub fn eliminate_pure_literals(&mut self) -> usize {
+        let before = self.clauses.len();
+
+        loop {
+            let mut pos_names = BTreeSet::new();
+            let mut neg_names = BTreeSet::new();
+
+            for clause in &self.clauses {
+                pos_names.extend(clause.pos.iter_pred_names().copied());
+                neg_names.extend(clause.neg.iter_pred_names().copied());
+            }
+
+            let pure: BTreeSet<Name> = pos_names.symmetric_difference(&neg_names).copied().collect();
+
+            if pure.is_empty() {
+                break;
+            }
+
+            self.clauses.retain(|clause| {
+                let (pos, neg) = clause.atoms();
+                !pos.iter_pred_names().chain(neg.iter_pred_names()).any(|name| pure.contains(name))
+            });
+        }
+
+        before - self.clauses.len()
+    }
+
+    /// Removes all clauses that are subsumed by another clause in this normal form, and returns how
+    /// many clauses were removed. A clause `A` subsumes a clause `B` if the literals of `A` are a
+    /// subset of the literals of `B`, in which case `B` is redundant: in CNF, `B` is implied by `A`,
+    /// and in DNF, `B` implies `A`. Either way, removing `B` results in an equivalent normal form.
+    /// 
+    /// Only propositional subsumption is considered, that is, no unification is attempted. This
+    /// takes `O(n^2)` subset checks for `n` clauses.
+    pub fn remove_subsumed(&mut self) -> usize {
+        let before = self.clauses.len();
+
+        // Visit the smallest clauses first, a clause can only be subsumed by a clause
+        // with at most the same amount of literals.
+        let mut clauses: Vec<Clause> = std::mem::take(&mut self.clauses).into_iter().collect();
+        clauses.sort_by_key(|clause| clause.len());
+
+        let mut kept: Vec<Clause> = Vec::new();
+
+        for clause in clauses {
+            let subsumed = kept.iter().any(|it| it.is_subset_of(&clause));
+
+            if !subsumed {
+                kept.push(clause);
+            }
+        }
+
+        self.clauses = kept.into_iter().collect();
+
+        before - self.clauses.len()
+    }
+
+    /// Simplifies this CNF by unit propagation, also known as the one-literal rule. It repeatedly
+    /// takes a unit clause `(L)`, removes every clause containing `L`, and removes `!L` from every
+    /// remaining clause. The result is equisatisfiable, but not equivalent, to the original CNF.
+    /// 
+    /// To stay sound in first-order logic, only ground unit clauses are propagated, and only literals
+    /// that are syntactically equal to the unit are affected. E.g. the unit `(P(a))` removes the
+    /// literal `!P(a)` but not `!P(:x)`.
+    /// 
+    /// When propagation derives the empty clause, the CNF is a contradiction. In that case, the CNF
+    /// is replaced by a CNF with just the empty clause and [Err] is returned.
+    #[allow(clippy::result_unit_err)]
+    pub fn unit_propagate(&mut self) -> Result<(), ()> {
+        loop {
+            if self.has_empty_clause() {
+                self.clauses = Clauses::from([Clause::new()]);
+                return Err(());
+            }
+
+            let unit = self.clauses.iter()
+                .filter_map(|clause| clause.unit())
+                .find(|(atom, _)| atom.is_ground());
+
+            let Some((atom, neg)) = unit else {
+                return Ok(());
+            };
+
+            let clauses = std::mem::take(&mut self.clauses);
+
+            for mut clause in clauses {
+                let (same, complement) = if neg {
+                    (clause.neg.contains(&atom), clause.pos.contains(&atom))
+                } else {
+                    (clause.pos.contains(&atom), clause.neg.contains(&atom))
+                };
+
+                if same {
+                    // The clause is satisfied by the unit
+                    continue;
+                }
+
+                if complement {
+                    if neg {
+                        clause.remove_pos(&atom);
+                    } else {
+                        clause.remove_neg(&atom);
+                    }
+                }
+
+                self.clauses.insert(clause);
+            }
+        }
+    }
+
+    /// Returns whether this CNF contains a pair of complementary unit clauses, like `(P(a))` and
+    /// `(!P(a))`, in which case it is a contradiction. This is a cheap check that can be done before
+    /// resolving, as a resolver would derive the empty clause from such a pair in one step.
+    /// 
+    /// Syntactically equal pairs are found in `O(n log n)` time for `n` unit clauses. For units with
+    /// variables, the negative unit is renamed apart from the positive unit and the two are unified,
+    /// so `(P(:x))` and `(!P(f(:x)))` are complementary too. This takes `O(n^2)` unification attempts
+    /// in the worst case, but only units with the same predicate name are unified.
+    ///
+    /// See [NormalForm::unit_contradiction] to find the pair.
+    pub fn detect_unit_contradiction(&self) -> bool {
+        self.unit_contradiction().is_some()
+    }
+
+    /// Finds a pair of complementary unit clauses, as detected by [NormalForm::detect_unit_contradiction].
+    /// Returns the positive unit clause and the negative unit clause, if there is such a pair. Resolving
+    /// the two derives the empty clause.
+    pub fn unit_contradiction(&self) -> Option<(&Clause, &Clause)> {
+        let mut pos = BTreeMap::new();
+        let mut neg = BTreeMap::new();
+
+        for clause in &self.clauses {
+            if let Some((atom, is_neg)) = clause.unit() {
+                if is_neg {
+                    neg.insert(atom, clause);
+                } else {
+                    pos.insert(atom, clause);
+                }
+            }
+        }
+
+        if let Some((p, n)) = pos.iter().find_map(|(atom, p)| neg.get(atom).map(|n| (*p, *n))) {
+            return Some((p, n));
+        }
+
+        let first_free = Names::free(self);
+
+        pos.iter().find_map(|(p, p_clause)| {
+            let Atom::Pred(p_name, _) = p;
+
+            neg.iter().find(|(n, _)| {
+                let Atom::Pred(n_name, n_args) = n;
+
+                // Equal ground atoms were found above, and distinct ground atoms never unify
+                if p_name != n_name || p.is_ground() && n.is_ground() {
+                    return false;
+                }
+
+                let mut renaming = BTreeMap::new();
+                let mut next_name = first_free;
+                for arg in n_args {
+                    collect_renaming(arg, &mut renaming, &mut next_name);
+                }
+
+                let n = Atom::Pred(*n_name, n_args.iter().map(|it| rename_vars(it, &renaming)).collect());
+
+                Unifier::mgu(p, &n).is_some()
+            }).map(|(_, n_clause)| (*p_clause, *n_clause))
+        })
+    }
+
     /// Computes an equivalent Conjunctive Normal Form. It does this by
     /// rewriting the expression using DeMorgan's law and distribution
     /// properties. Finding an equivalent CNF is an NP-hard problem, this
     /// operation will take `O(2^n)` time and space complexity.
     pub fn equiv_cnf(expr: BExpr) -> NormalForm {
-        equiv_nf::cnf(skolemise::skolemise(expr)).into()
+        equiv_nf::cnf(expr).into()
+    }
+
+    /// Computes an equivalent Conjunctive Normal Form like [NormalForm::equiv_cnf], but gives up with
+    /// [CnfTooLarge] as soon as distribution produces a clause with more than `max_clause_len`
+    /// literals. Since the equivalent CNF may be exponentially large, this allows to attempt it
+    /// safely and fall back to [NormalForm::tseitin_cnf] when it fails.
+    pub fn equiv_cnf_bounded(expr: BExpr, max_clause_len: usize) -> Result<NormalForm, CnfTooLarge> {
+        equiv_nf::cnf_bounded(expr, max_clause_len).map(NormalForm::from)
+    }
+
+    /// Computes an equivalent Conjunctive Normal Form like [NormalForm::equiv_cnf], but without
+    /// removing tautological clauses. This is meant for inspecting a conversion: tautologies are
+    /// exactly the clauses that are not [disjoint][Clause::is_disjoint], and they can be removed
+    /// afterwards with [NormalForm::retain_only_disjoint]. The [Resolver][crate::res::Resolver]
+    /// drops tautological premises when it assumes them.
+    pub fn equiv_cnf_keep_tautologies(expr: BExpr) -> NormalForm {
+        equiv_nf::cnf_keep_tautologies(expr).into()
     }
 
     /// Computes an equivalent Disjunctive Normal Form. It does this by
@@ -280,29 +808,94 @@ impl NormalForm {
     /// properties. Finding an equivalent DNF is an NP-hard problem, this
     /// operation will take `O(2^n)` time and space complexity.
     pub fn equiv_dnf(expr: BExpr) -> NormalForm {
-        equiv_nf::dnf(skolemise::skolemise(expr)).into()
+        equiv_nf::dnf(expr).into()
+    }
+
+    /// Converts this normal form, interpreted as DNF, into an equivalent CNF. It does this by
+    /// distributing the conjunctive clauses over eachother. Like [NormalForm::equiv_cnf], this
+    /// operation will take `O(2^n)` time and space complexity.
+    pub fn to_cnf_from_dnf(self) -> NormalForm {
+        equiv_nf::redistribute(self.clauses).into()
+    }
+
+    /// Converts this normal form, interpreted as CNF, into an equivalent DNF. It does this by
+    /// distributing the disjunctive clauses over eachother. Like [NormalForm::equiv_dnf], this
+    /// operation will take `O(2^n)` time and space complexity.
+    pub fn to_dnf_from_cnf(self) -> NormalForm {
+        equiv_nf::redistribute(self.clauses).into()
     }
 
     /// Computes an equisatisfiable, but not equivalent, Conjunctive Normal Form.
     /// It does this using Tseitin's transformation. The resulting CNF is satisfiable
     /// if and only if this expression is satisfiable. Finding the Tseitin
     /// transformation is a P problem, this operation will take `O(n)` time and
-    /// space complexity.
+    /// space complexity. Quantified expressions are skolemised first.
     pub fn tseitin_cnf(expr: BExpr) -> NormalForm {
-        tseitin_nf::cnf(skolemise::skolemise(expr)).into()
+        tseitin_nf::cnf(expr).into()
     }
 
     /// Computes an equisatisfiable, but not equivalent, Disjunctive Normal Form.
     /// It does this using Tseitin's transformation. The resulting DNF is satisfiable
     /// if and only if this expression is satisfiable. Finding the Tseitin
     /// transformation is a P problem, this operation will take `O(n)` time and
-    /// space complexity.
+    /// space complexity. Quantified expressions are skolemised first.
     pub fn tseitin_dnf(expr: BExpr) -> NormalForm {
-        tseitin_nf::dnf(skolemise::skolemise(expr)).into()
+        tseitin_nf::dnf(expr).into()
     }
 }
 
 
+/// Applies the given unifier to a set of clauses in one pass. Unlike [Unifiable::unify], clauses that
+/// use no variable the unifier substitutes are moved into the result as they are, rather than being
+/// rebuilt. This makes applying a unifier with few substitutions to many clauses considerably cheaper.
+pub fn unify_clauses(clauses: Clauses, unifier: &Unifier) -> Clauses {
+    if unifier.is_empty() {
+        return clauses;
+    }
+
+    clauses.into_iter()
+        .map(|clause| {
+            if clause.vars::<Vec<_>>().iter().any(|var| unifier.substitutes(var)) {
+                clause.unify(unifier)
+            } else {
+                clause
+            }
+        })
+        .collect()
+}
+
+/// Replaces all variables in the given expression with the same name.
+fn erase_vars(e: &AExpr) -> AExpr {
+    match e {
+        AExpr::Var(_) => AExpr::Var(Name::any()),
+        AExpr::Fun(name, args) => AExpr::Fun(*name, args.iter().map(erase_vars).collect())
+    }
+}
+
+/// Assigns the next name to every variable in the given expression that was not assigned a name yet,
+/// in order of first occurrence.
+fn collect_renaming(e: &AExpr, renaming: &mut BTreeMap<Name, Name>, next_name: &mut Name) {
+    match e {
+        AExpr::Var(name) => {
+            renaming.entry(*name).or_insert_with(|| next_name.incr());
+        },
+        AExpr::Fun(_, args) => {
+            for arg in args {
+                collect_renaming(arg, renaming, next_name);
+            }
+        }
+    }
+}
+
+/// Renames the variables in the given expression. Unlike unification, this allows a variable to be
+/// renamed to a variable that is renamed itself.
+fn rename_vars(e: &AExpr, renaming: &BTreeMap<Name, Name>) -> AExpr {
+    match e {
+        AExpr::Var(name) => AExpr::Var(renaming[name]),
+        AExpr::Fun(name, args) => AExpr::Fun(*name, args.iter().map(|it| rename_vars(it, renaming)).collect())
+    }
+}
+
 fn union<T>(l: BTreeSet<T>, r: BTreeSet<T>) -> BTreeSet<T> where T : Ord {
     l.into_iter().chain(r.into_iter()).collect()
 }
@@ -337,6 +930,24 @@ impl From<Clauses> for NormalForm {
     }
 }
 
+impl<'a> IntoIterator for &'a NormalForm {
+    type Item = &'a Clause;
+    type IntoIter = std::collections::btree_set::Iter<'a, Clause>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.clauses.iter()
+    }
+}
+
+impl IntoIterator for NormalForm {
+    type Item = Clause;
+    type IntoIter = std::collections::btree_set::IntoIter<Clause>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_clauses().into_iter()
+    }
+}
+
 impl FromIterator<Clause> for NormalForm {
     fn from_iter<T: IntoIterator<Item = Clause>>(iter: T) -> Self {
         Self {
@@ -425,7 +1036,7 @@ impl Unifiable for Clause {
 
 impl Unifiable for NormalForm {
     fn unify(mut self, unifier: &crate::uni::Unifier) -> Self {
-        self.clauses = self.clauses.unify(unifier);
+        self.clauses = unify_clauses(self.clauses, unifier);
         self
     }
     
@@ -507,6 +1118,17 @@ impl Display for NormalForm {
     }
 }
 
+impl Display for NfStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "{} clauses, {} literals, {} predicates",
+            self.clause_count,
+            self.literal_count,
+            self.predicate_names.len()
+        )
+    }
+}
+
 impl Debug for Atom {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Debug::fmt(&self.with_table(&NameTable::new()), f)
@@ -7,21 +7,78 @@ use crate::uni::{Unifiable, Unifier};
 use Quantifier::*;
 
 
-/// Skolemises the expression. This goes in five steps:
+/// Skolemises the expression. This goes in six steps:
 /// - First, any quantifier of which the variable was ignored, is simply
 ///   removed. E.g., `all x: P` becomes `P` and `some x: P` becomes `P`.
 /// - Second, all the negations are moved inwards using DeMorgan's laws.
-/// - Third, the expression is converted into Prenex Form, in which all
+/// - Third, the quantifiers are pushed inwards as far as possible, see
+///   [miniscope], and the variables they bind are renamed apart, see
+///   [BExpr::rename_bound_apart].
+/// - Fourth, the expression is converted into Prenex Form, in which all
 ///   quantifiers come before the rest of the expression.
-/// - Fourth, existential quantifiers are replaced with Skolem functions,
+/// - Fifth, existential quantifiers are replaced with Skolem functions,
 ///   leaving an expression in Skolem Form, a special case of Prenex Form
 ///   in which there are only universal quantifiers.
-/// - Fifth, all the universal quantifiers are dropped, leaving all
+/// - Sixth, all the universal quantifiers are dropped, leaving all
 ///   variables that were bound by these quantifiers unbound.
 pub fn skolemise(e: BExpr) -> BExpr {
-    PrenexForm::from(e).skolemise()
+    let prenex = PrenexForm::from(e);
+    let mut next_name = prenex.free();
+    prenex.skolemise(&mut next_name)
 }
 
+/// Converts the expression into Prenex Form, in which all quantifiers come before the rest of the
+/// expression. This goes through the first four steps of [skolemise]: useless quantifiers are removed,
+/// negations are moved inwards and quantifiers are pushed inwards by [miniscope] before they are
+/// pulled out to the front.
+pub fn prenex(e: BExpr) -> BExpr {
+    PrenexForm::from(e).into_bexpr()
+}
+
+/// Skolemises the expression like [skolemise], but without converting it into Prenex Form. Instead,
+/// each existential quantifier is replaced with a Skolem function whose arguments are only the
+/// variables of the enclosing universal quantifiers that occur in its scope. E.g. in
+/// `all x: all y: (P(x) | some z: Q(y, z))`, `z` is Skolemised to `z(:y)` rather than `z(:x, :y)`.
+///
+/// Prenex Form places every existential quantifier after all universal quantifiers it may have been
+/// preceded by in the prefix, even when it was not in their scope in the original expression. This
+/// is partially avoided by [skolemise], but tracking scope directly yields Skolem functions with at
+/// most as many arguments, and often fewer.
+pub fn skolemise_antiprenex(e: BExpr) -> BExpr {
+    let e = miniscope(cleanup_useless_quantifiers(e).to_nnf()).rename_bound_apart();
+
+    let mut next_name = e.free();
+    antiprenex(e, &mut Vec::new(), &mut next_name)
+}
+
+/// Skolemises the expression while tracking the universal quantifiers in scope, see
+/// [skolemise_antiprenex]. The expression is expected to have all its negations moved inwards.
+fn antiprenex(e: BExpr, universals: &mut Vec<Name>, next_name: &mut Name) -> BExpr {
+    match e {
+        BExpr::And(lhs, rhs) => antiprenex(*lhs, universals, next_name) & antiprenex(*rhs, universals, next_name),
+        BExpr::Or(lhs, rhs) => antiprenex(*lhs, universals, next_name) | antiprenex(*rhs, universals, next_name),
+
+        BExpr::All(name, rhs) => {
+            universals.push(name);
+            let e = antiprenex(*rhs, universals, next_name);
+            universals.pop();
+            e
+        },
+
+        BExpr::Some(name, rhs) => {
+            // Only the universal variables that occur in the scope become arguments
+            let vars: Vec<Name> = universals.iter().copied().filter(|var| rhs.has_var(var)).collect();
+
+            let sk_fun = skolem_fun(next_name.incr(), &vars);
+            let rhs = (*rhs).unify(&Unifier::singleton(name, sk_fun));
+
+            antiprenex(rhs, universals, next_name)
+        },
+
+        // `Not` is covered here because DeMorgan should've brought all `Not`s inwards
+        e => e
+    }
+}
 
 /// A quantifier in the prenex form.
 enum Quantifier {
@@ -79,12 +136,6 @@ impl DisplayNamed for PrenexForm {
 
 
 impl PrenexForm {
-    /// Tests if this prenex form is in Skolem form, that is, whether it does not have
-    /// any existential quantifiers.
-    fn is_skolem_form(&self) -> bool {
-        self.prefix.iter().all(|q| matches!(q, Quantifier::Universal(_)))
-    }
-
     /// Quantifies a prenex form with universal quantification.
     fn all(mut self, name: Name) -> Self {
         self.prefix.push_front(Quantifier::Universal(name));
@@ -98,51 +149,21 @@ impl PrenexForm {
     }
 
     /// Computes the disjunction of two prenex forms, in prenex form.
-    /// This disjunction is obtained by concatenating the prefixes and
-    /// disjuncting the matrices.
-    fn disjunct(mut self, mut other: Self) -> Self {
-        // The prenex form of the disjuncts is simply obtained by
-        // concatenating the prefixes and disjuncting the matrices.
-        //
-        // However, if A is in Skolem form and B is not, then it
-        // would mean that the existential quantifier of B is in
-        // front of all the universal quantifiers of A, causing
-        // the resulting Skolem function to unnecessarily have all
-        // the variables of A.
-        //
-        // The solution is this swap, causing the universal quantifiers
-        // of A to come after the quantifiers of B and introducing no
-        // unecessarily complicated Skolem functions.
-        if self.is_skolem_form() && !other.is_skolem_form() {
-            std::mem::swap(&mut self, &mut other);
-        }
-
-        let mut prefix = VecDeque::new();
-        prefix.append(&mut self.prefix);
-        prefix.append(&mut other.prefix);
-
+    /// This disjunction is obtained by merging the prefixes, see
+    /// [merge_prefixes], and disjuncting the matrices.
+    fn disjunct(self, other: Self) -> Self {
         Self {
-            prefix,
+            prefix: merge_prefixes(self.prefix, other.prefix),
             matrix: self.matrix | other.matrix
         }
     }
 
     /// Computes the conjunction of two prenex forms, in prenex form.
-    /// This conjunction is obtained by concatenating the prefixes and
-    /// conjuncting the matrices.
-    fn conjunct(mut self, mut other: Self) -> Self {
-        // For the same reason as in `disjunct`, this swap will avoid
-        // unnecessarily complicated Skolem functions.
-        if self.is_skolem_form() && !other.is_skolem_form() {
-            std::mem::swap(&mut self, &mut other);
-        }
-
-        let mut prefix = VecDeque::new();
-        prefix.append(&mut self.prefix);
-        prefix.append(&mut other.prefix);
-
+    /// This conjunction is obtained by merging the prefixes, see
+    /// [merge_prefixes], and conjuncting the matrices.
+    fn conjunct(self, other: Self) -> Self {
         Self {
-            prefix,
+            prefix: merge_prefixes(self.prefix, other.prefix),
             matrix: self.matrix & other.matrix
         }
     }
@@ -150,7 +171,12 @@ impl PrenexForm {
 
     fn from(mut e: BExpr) -> PrenexForm {
         e = cleanup_useless_quantifiers(e);
-        e = demorgan_pos(e);
+        e = e.to_nnf();
+        e = miniscope(e);
+
+        // Miniscoping splits universal quantifiers into several quantifiers of the same variable,
+        // which must not end up in one prefix under the same name
+        e = e.rename_bound_apart();
 
         Self::from_raw(e)
     }
@@ -180,7 +206,18 @@ impl PrenexForm {
         }
     }
 
-    fn skolemise(self) -> BExpr {
+    /// Converts this prenex form back into a [BExpr], wrapping the matrix in the quantifiers of the
+    /// prefix.
+    fn into_bexpr(self) -> BExpr {
+        self.prefix.into_iter().rev().fold(self.matrix, |e, q| match q {
+            Universal(name) => BExpr::all(name, e),
+            Existential(name) => BExpr::some(name, e),
+        })
+    }
+
+    /// Skolemises this prenex form. The Skolem functions get fresh names, taken from the
+    /// given name counter. The counter must be higher than any name in this prenex form.
+    fn skolemise(self, next_name: &mut Name) -> BExpr {
         let mut vars = Vec::new();
 
         let Self {
@@ -190,13 +227,12 @@ impl PrenexForm {
 
         while let Some(q) = prefix.pop_front() {
             match q {
-                Universal(name) => {
-                    vars.push(name)
-                },
+                Universal(name) => vars.push(name),
 
                 Existential(name) => {
-                    // We can reuse the variable name as skolem function name
-                    let sk_fun = skolem_fun(name, &vars);
+                    // The variable name cannot be reused as skolem function name, as it may
+                    // already be in use as a function name elsewhere
+                    let sk_fun = skolem_fun(next_name.incr(), &vars);
 
                     // Use a unifier that replaces the existential variable
                     // with the skolem function
@@ -213,29 +249,100 @@ impl PrenexForm {
     }
 }
 
-fn demorgan_pos(e: BExpr) -> BExpr {
+/// Merges the prefixes of two prenex forms whose bound variables are apart. Since the variables bound
+/// by one prefix do not occur in the matrix of the other, any order of the quantifiers that keeps the
+/// order within each prefix gives an equivalent prenex form.
+///
+/// Every universal quantifier in front of an existential quantifier becomes an argument of its Skolem
+/// function, so existential quantifiers are taken as early as possible. When both prefixes start with
+/// a universal quantifier, the one taken is from the prefix with the fewest universal quantifiers
+/// before its next existential quantifier. E.g. merging `all x, some y` with `some z, all w` gives
+/// `some z, all x, some y, all w`, rather than placing `some z` after `all x`.
+fn merge_prefixes(mut a: VecDeque<Quantifier>, mut b: VecDeque<Quantifier>) -> VecDeque<Quantifier> {
+    let mut prefix = VecDeque::new();
+
+    loop {
+        if let Some(Existential(_)) = a.front() {
+            prefix.extend(a.pop_front());
+            continue;
+        }
+
+        if let Some(Existential(_)) = b.front() {
+            prefix.extend(b.pop_front());
+            continue;
+        }
+
+        let next_existential = |q: &VecDeque<Quantifier>| q.iter().position(|it| matches!(it, Existential(_)));
+
+        match (next_existential(&a), next_existential(&b)) {
+            (Some(i), Some(j)) if j < i => prefix.extend(b.pop_front()),
+            (Some(_), _) => prefix.extend(a.pop_front()),
+            (None, Some(_)) => prefix.extend(b.pop_front()),
+
+            (None, None) => {
+                prefix.append(&mut a);
+                prefix.append(&mut b);
+                return prefix;
+            }
+        }
+    }
+}
+
+/// Pushes quantifiers inwards, past connectives whose other operand does not use the
+/// bound variable. E.g. `all x: (P(x) | Q)` becomes `(all x: P(x)) | Q`. Additionally,
+/// universal quantifiers are distributed over conjunctions, e.g. `all x: (P(x) & Q(x))`
+/// becomes `(all x: P(x)) & (all x: Q(x))`.
+/// 
+/// The fewer universal quantifiers an existential quantifier is nested in, the fewer
+/// arguments its Skolem function will get. Miniscoping thus reduces the arity of
+/// Skolem functions.
+/// 
+/// Existential quantifiers are not distributed over disjunctions, since that would
+/// introduce two existential quantifiers binding the same variable, which cannot be
+/// Skolemised independently.
+/// 
+/// The expression is expected to have all its negations moved inwards.
+pub fn miniscope(e: BExpr) -> BExpr {
     match e {
-        BExpr::And(lhs, rhs) => demorgan_pos(*lhs) & demorgan_pos(*rhs),
-        BExpr::Or(lhs, rhs) => demorgan_pos(*lhs) | demorgan_pos(*rhs),
-        BExpr::Not(rhs) => demorgan_neg(*rhs),
+        BExpr::And(lhs, rhs) => miniscope(*lhs) & miniscope(*rhs),
+        BExpr::Or(lhs, rhs) => miniscope(*lhs) | miniscope(*rhs),
+        BExpr::Not(rhs) => !miniscope(*rhs),
 
-        BExpr::All(name, rhs) => BExpr::all(name, demorgan_pos(*rhs)),
-        BExpr::Some(name, rhs) => BExpr::some(name, demorgan_pos(*rhs)),
+        BExpr::All(name, rhs) => push_all(name, miniscope(*rhs)),
+        BExpr::Some(name, rhs) => push_some(name, miniscope(*rhs)),
 
         e => e
     }
 }
 
-fn demorgan_neg(e: BExpr) -> BExpr {
+fn push_all(name: Name, e: BExpr) -> BExpr {
+    if !e.has_var(&name) {
+        return e;
+    }
+
+    match e {
+        BExpr::And(lhs, rhs) => push_all(name, *lhs) & push_all(name, *rhs),
+
+        BExpr::Or(lhs, rhs) if !lhs.has_var(&name) => *lhs | push_all(name, *rhs),
+        BExpr::Or(lhs, rhs) if !rhs.has_var(&name) => push_all(name, *lhs) | *rhs,
+
+        e => BExpr::all(name, e)
+    }
+}
+
+fn push_some(name: Name, e: BExpr) -> BExpr {
+    if !e.has_var(&name) {
+        return e;
+    }
+
     match e {
-        BExpr::And(lhs, rhs) => demorgan_neg(*lhs) | demorgan_neg(*rhs),
-        BExpr::Or(lhs, rhs) => demorgan_neg(*lhs) & demorgan_neg(*rhs),
-        BExpr::Not(rhs) => demorgan_pos(*rhs),
+        BExpr::And(lhs, rhs) if !lhs.has_var(&name) => *lhs & push_some(name, *rhs),
+        BExpr::And(lhs, rhs) if !rhs.has_var(&name) => push_some(name, *lhs) & *rhs,
 
-        BExpr::All(name, rhs) => BExpr::some(name, demorgan_neg(*rhs)),
-        BExpr::Some(name, rhs) => BExpr::all(name, demorgan_neg(*rhs)),
+        BExpr::Or(lhs, rhs) if !lhs.has_var(&name) => *lhs | push_some(name, *rhs),
+        BExpr::Or(lhs, rhs) if !rhs.has_var(&name) => push_some(name, *lhs) | *rhs,
 
-        e => !e
+        e => BExpr::some(name, e)
     }
 }
 
@@ -278,9 +385,26 @@ fn skolem_fun(name: Name, vars: &Vec<Name>) -> AExpr {
 
 #[cfg(test)]
 mod test {
-    use crate::nf::skolemise::skolemise;
+    use crate::expr::{AExpr, BExpr, Name};
+    use crate::nf::skolemise::{prenex, skolemise, skolemise_antiprenex};
     use crate::test::TestContext;
 
+    fn max_arity(e: &BExpr) -> usize {
+        fn aexpr_arity(e: &AExpr) -> usize {
+            match e {
+                AExpr::Var(_) => 0,
+                AExpr::Fun(_, args) => args.iter().map(aexpr_arity).fold(args.len(), usize::max)
+            }
+        }
+
+        match e {
+            BExpr::Pred(_, args) => args.iter().map(aexpr_arity).fold(0, usize::max),
+            BExpr::And(lhs, rhs) | BExpr::Or(lhs, rhs) => usize::max(max_arity(lhs), max_arity(rhs)),
+            BExpr::Not(rhs) | BExpr::All(_, rhs) | BExpr::Some(_, rhs) => max_arity(rhs),
+            _ => 0
+        }
+    }
+
     #[test]
     fn test() {
         let mut ctx = TestContext::new();
@@ -290,4 +414,211 @@ mod test {
         let skolemised = skolemise(expr);
         ctx.display(&skolemised);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn miniscope_disjunction() {
+        let mut ctx = TestContext::new();
+
+        // Without miniscoping, `y` would be Skolemised to `y(:x)`
+        let expr = ctx.bexpr("all x: (P(x) | some y: Q(y))");
+
+        let skolemised = skolemise(expr);
+        ctx.display(&skolemised);
+
+        assert_eq!(0, max_arity(&skolemised));
+    }
+
+    #[test]
+    fn miniscope_conjunction() {
+        let mut ctx = TestContext::new();
+
+        // Without miniscoping, `z` would be Skolemised to `z(:x, :y)`
+        let expr = ctx.bexpr("all x: all y: (P(x, y) & (R(y) | some z: Q(x, z)))");
+
+        let skolemised = skolemise(expr);
+        ctx.display(&skolemised);
+
+        assert_eq!(1, max_arity(&skolemised));
+    }
+
+    /// Finds the arguments of the first occurrence of the given predicate.
+    fn pred_args(e: &BExpr, name: Name) -> Option<&Vec<AExpr>> {
+        match e {
+            BExpr::Pred(pred, args) if *pred == name => Some(args),
+            BExpr::And(lhs, rhs) | BExpr::Or(lhs, rhs) => pred_args(lhs, name).or_else(|| pred_args(rhs, name)),
+            BExpr::Not(rhs) | BExpr::All(_, rhs) | BExpr::Some(_, rhs) => pred_args(rhs, name),
+            _ => None
+        }
+    }
+
+    #[test]
+    fn miniscope_split_shared_name() {
+        let mut ctx = TestContext::new();
+
+        // Miniscoping splits `all x` into two quantifiers of `x`, only one of which is in the scope
+        // of `some y`. The Skolem constant of `y` must not depend on the other one.
+        let expr = ctx.bexpr("some y: all x: ((some w: P(x, w)) & R(x, y))");
+        let r = ctx.name("R");
+
+        let skolemised = skolemise(expr);
+        ctx.display(&skolemised);
+
+        let Some([_, AExpr::Fun(_, args)]) = pred_args(&skolemised, r).map(Vec::as_slice) else {
+            panic!("Unexpected Skolem form");
+        };
+
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn fresh_skolem_names() {
+        let p = Name::any();
+        let x = p.succ();
+
+        // `some x: P(:x) & !P(x())`, where the bound variable and the constant share a name
+        let expr = BExpr::some(x, BExpr::pred(p, vec![AExpr::var(x)])) & !BExpr::pred(p, vec![AExpr::con(x)]);
+
+        let skolemised = skolemise(expr);
+
+        let BExpr::And(lhs, rhs) = &skolemised else {
+            panic!("Expected conjunction");
+        };
+
+        let (BExpr::Pred(_, args), BExpr::Not(neg)) = (lhs.as_ref(), rhs.as_ref()) else {
+            panic!("Unexpected Skolem form");
+        };
+
+        // The Skolem constant must not be conflated with `x()`
+        assert_ne!(vec![AExpr::con(x)], *args);
+        assert_eq!(BExpr::pred(p, vec![AExpr::con(x)]), **neg);
+    }
+
+    #[test]
+    fn antiprenex_scope() {
+        let mut ctx = TestContext::new();
+
+        // `z` only depends on `y`, so it is Skolemised to `z(:y)` rather than `z(:x, :y)`
+        let expr = ctx.bexpr("all x: all y: (P(x, y) | (R(x) & some z: Q(y, z)))");
+
+        let skolemised = skolemise_antiprenex(expr.clone());
+        ctx.display(&skolemised);
+
+        assert_eq!(1, max_arity(&skolemised));
+        assert_eq!(2, max_arity(&skolemise(expr)));
+    }
+
+    #[test]
+    fn antiprenex_nested() {
+        let mut ctx = TestContext::new();
+
+        // `y` depends on `x`, and `z` on `x` through `y`
+        let expr = ctx.bexpr("all x: some y: (P(x, y) & some z: Q(y, z))");
+
+        let skolemised = skolemise_antiprenex(expr);
+        ctx.display(&skolemised);
+
+        assert!(skolemised.is_quantifier_free());
+        assert_eq!(1, max_arity(&skolemised));
+    }
+
+    #[test]
+    fn antiprenex_split_shared_name() {
+        let mut ctx = TestContext::new();
+
+        // Miniscoping splits `all x` into two quantifiers of `x`
+        let exprs = ctx.bexprs([
+            "some y: all x: ((some w: P(x, w)) & R(x, y))",
+            "all x: (P(x) & some y: (Q(x, y) | all x: R(x, y)))",
+        ]);
+
+        for expr in exprs {
+            let original = expr.functions();
+
+            let arities = |e: &BExpr| {
+                let mut arities: Vec<usize> = e.functions().into_iter()
+                    .filter(|it| !original.contains(it))
+                    .map(|(_, arity)| arity)
+                    .collect();
+                arities.sort();
+                arities
+            };
+
+            let antiprenex = skolemise_antiprenex(expr.clone());
+            ctx.display(&antiprenex);
+
+            // Both introduce the same Skolem functions here
+            assert!(antiprenex.is_quantifier_free());
+            assert_eq!(arities(&skolemise(expr)), arities(&antiprenex));
+        }
+    }
+
+    #[test]
+    fn antiprenex_not_worse() {
+        let mut ctx = TestContext::new();
+
+        let exprs = ctx.bexprs([
+            "all x: (P(x) -> Q(x)) & some x: P(x) & no x: Q(x)",
+            "all x: (P(x) | some y: Q(y))",
+            "all x: all y: (P(x, y) & (R(y) | some z: Q(x, z)))",
+            "!(all x: some y: R(x, y) -> some y: all x: R(x, y))",
+        ]);
+
+        for expr in exprs {
+            let antiprenex = skolemise_antiprenex(expr.clone());
+            assert!(antiprenex.is_quantifier_free());
+            assert!(max_arity(&antiprenex) <= max_arity(&skolemise(expr)));
+        }
+    }
+
+    #[test]
+    fn no_quantifier() {
+        let mut ctx = TestContext::new();
+
+        // `no x: P(x)` becomes `all x: !P(x)` in NNF, so `x` stays a variable
+        let expr = ctx.bexpr("no x: P(x)");
+
+        for skolemised in [skolemise(expr.clone()), skolemise_antiprenex(expr)] {
+            ctx.display(&skolemised);
+
+            let BExpr::Not(rhs) = &skolemised else {
+                panic!("Expected negation");
+            };
+
+            let BExpr::Pred(_, args) = rhs.as_ref() else {
+                panic!("Expected predicate");
+            };
+
+            assert!(matches!(args.as_slice(), [AExpr::Var(_)]));
+        }
+    }
+
+    #[test]
+    fn no_quantifier_useless() {
+        let mut ctx = TestContext::new();
+
+        let expr = ctx.bexpr("no x: P");
+
+        assert_eq!(ctx.bexpr("!P"), skolemise(expr));
+    }
+
+    #[test]
+    fn prenex_form() {
+        let mut ctx = TestContext::new();
+
+        let expr = ctx.bexpr("all x: P(x) & !(all y: Q(y))");
+
+        let prenex = prenex(expr);
+        ctx.display(&prenex);
+
+        // The existential quantifier goes first, so that it does not end up in the scope of `x`
+        let BExpr::Some(_, rhs) = &prenex else {
+            panic!("Expected existential quantifier");
+        };
+
+        let BExpr::All(_, rhs) = rhs.as_ref() else {
+            panic!("Expected universal quantifier");
+        };
+
+        assert!(rhs.is_quantifier_free());
+    }
+}
@@ -13,7 +13,7 @@ fn remove_true_false(expr: BExpr) -> BExpr {
 
             let r = remove_true_false(*rhs);
             if r == BExpr::False {
-                return l;
+                return r;
             }
 
             if l == BExpr::True {
@@ -35,7 +35,7 @@ fn remove_true_false(expr: BExpr) -> BExpr {
 
             let r = remove_true_false(*rhs);
             if r == BExpr::True {
-                return l;
+                return r;
             }
 
             if l == BExpr::False {
@@ -72,9 +72,65 @@ enum TseitinOperator {
     Conj(Atom, Atom),
     Disj(Atom, Atom),
     Inv(Atom),
+    Impl(Atom, Atom),
+    Equiv(Atom, Atom),
+    Xor(Atom, Atom),
     Ident,
 }
 
+/// A derived connective, recognised in its lowered form, see [derived].
+enum Derived<'e> {
+    Impl(&'e BExpr, &'e BExpr),
+    Equiv(&'e BExpr, &'e BExpr),
+    Xor(&'e BExpr, &'e BExpr),
+}
+
+/// Tests whether `e` is the negation of `of` in Negation Normal Form.
+fn negates(e: &BExpr, of: &BExpr) -> bool {
+    match e {
+        BExpr::Not(rhs) if **rhs == *of => true,
+        BExpr::Pred(_, _) => false,
+        e => *e == (!of.clone()).to_nnf()
+    }
+}
+
+/// Recognises implication, equivalence and exclusive disjunction in the form they take after being
+/// lowered by [BExpr::im], [BExpr::equiv] and `!BExpr::equiv`, and converted to Negation Normal Form.
+/// These connectives get direct clause templates, which are smaller than encoding their lowered form.
+fn derived(expr: &BExpr) -> Option<Derived<'_>> {
+    match expr {
+        // (P | !Q) & (!P | Q)
+        BExpr::And(lhs, rhs) => {
+            let (BExpr::Or(p, nq), BExpr::Or(np, q)) = (lhs.as_ref(), rhs.as_ref()) else {
+                return None;
+            };
+
+            if negates(nq, q) && negates(np, p) {
+                Some(Derived::Equiv(p, q))
+            } else {
+                None
+            }
+        },
+
+        BExpr::Or(lhs, rhs) => {
+            // (!P & Q) | (P & !Q)
+            if let (BExpr::And(np, q), BExpr::And(p, nq)) = (lhs.as_ref(), rhs.as_ref())
+                && negates(np, p) && negates(nq, q) {
+                return Some(Derived::Xor(p, q));
+            }
+
+            // !P | Q
+            if let BExpr::Not(p) = lhs.as_ref() {
+                return Some(Derived::Impl(p, rhs));
+            }
+
+            None
+        },
+
+        _ => None
+    }
+}
+
 #[derive(Clone)]
 struct TseitinAssignment {
     atom: Atom,
@@ -83,7 +139,10 @@ struct TseitinAssignment {
 
 struct Tseitin {
     namings: BTreeMap<BExpr, TseitinAssignment>,
-    next_name: Name
+    next_name: Name,
+
+    /// Whether to use direct clause templates for derived connectives
+    templates: bool
 }
 
 impl Tseitin {
@@ -111,7 +170,39 @@ impl Tseitin {
             }
         };
 
-        let operator = match &expr {
+        let derived = if self.templates { derived(&expr) } else { None };
+
+        let operator = match (&expr, derived) {
+            (_, Some(Derived::Impl(p, q))) => TseitinOperator::Impl(
+                self.assign(p.clone()),
+                self.assign(q.clone())
+            ),
+
+            (_, Some(Derived::Equiv(p, q))) => TseitinOperator::Equiv(
+                self.assign(p.clone()),
+                self.assign(q.clone())
+            ),
+
+            (_, Some(Derived::Xor(p, q))) => TseitinOperator::Xor(
+                self.assign(p.clone()),
+                self.assign(q.clone())
+            ),
+
+            (expr, None) => self.assign_operator(expr)
+        };
+
+        let assignment = TseitinAssignment {
+            atom,
+            operator
+        };
+
+        let entry = self.namings.entry(expr).or_insert(assignment);
+
+        return entry.atom.clone();
+    }
+
+    fn assign_operator(&mut self, expr: &BExpr) -> TseitinOperator {
+        match expr {
             BExpr::True | BExpr::False => panic!("True and False must have been eliminated Tseitin transformation"),
 
             BExpr::Pred(_, _) => TseitinOperator::Ident,
@@ -133,16 +224,7 @@ impl Tseitin {
             BExpr::All(_, _) | BExpr::Some(_, _) => {
                 panic!("Expression not Skolemised")
             },
-        };
-
-        let assignment = TseitinAssignment {
-            atom,
-            operator
-        };
-
-        let entry = self.namings.entry(expr).or_insert(assignment);
-
-        return entry.atom.clone();
+        }
     }
 
     fn to_cnf(self, base: Atom) -> Clauses {
@@ -192,8 +274,43 @@ impl TseitinAssignment {
                 // ===  (X -> !P) & (X <- !P)
                 // ===  (!X | !P) & (X | P)
 
-                cnf.insert(Clause::from_slices([x.clone()], [p.clone()]));
-                cnf.insert(Clause::from_slices([p], [x]));
+                cnf.insert(Clause::from_slices([], [x.clone(), p.clone()]));
+                cnf.insert(Clause::from_slices([x, p], []));
+            },
+
+            TseitinOperator::Impl(p, q) => {
+                //      (X <-> (P -> Q))
+                // ===  (X -> (!P | Q)) & (X <- (!P | Q))
+                // ===  (!X | !P | Q) & (X | (P & !Q))
+                // ===  (!X | !P | Q) & (X | P) & (X | !Q)
+
+                cnf.insert(Clause::from_slices([q.clone()], [x.clone(), p.clone()]));
+                cnf.insert(Clause::from_slices([x.clone(), p], []));
+                cnf.insert(Clause::from_slices([x], [q]));
+            },
+
+            TseitinOperator::Equiv(p, q) => {
+                //      (X <-> (P <-> Q))
+                // ===  (X -> (P <-> Q)) & (X <- (P <-> Q))
+                // ===  (!X | P | !Q) & (!X | !P | Q) & (X | (P ^ Q))
+                // ===  (!X | P | !Q) & (!X | !P | Q) & (X | P | Q) & (X | !P | !Q)
+
+                cnf.insert(Clause::from_slices([p.clone()], [x.clone(), q.clone()]));
+                cnf.insert(Clause::from_slices([q.clone()], [x.clone(), p.clone()]));
+                cnf.insert(Clause::from_slices([x.clone(), p.clone(), q.clone()], []));
+                cnf.insert(Clause::from_slices([x], [p, q]));
+            },
+
+            TseitinOperator::Xor(p, q) => {
+                //      (X <-> (P ^ Q))
+                // ===  (X -> (P ^ Q)) & (X <- (P ^ Q))
+                // ===  (!X | P | Q) & (!X | !P | !Q) & (X | (P <-> Q))
+                // ===  (!X | P | Q) & (!X | !P | !Q) & (X | P | !Q) & (X | !P | Q)
+
+                cnf.insert(Clause::from_slices([p.clone(), q.clone()], [x.clone()]));
+                cnf.insert(Clause::from_slices([], [x.clone(), p.clone(), q.clone()]));
+                cnf.insert(Clause::from_slices([x.clone(), p.clone()], [q.clone()]));
+                cnf.insert(Clause::from_slices([x, q], [p]));
             },
 
             TseitinOperator::Ident => {
@@ -204,9 +321,14 @@ impl TseitinAssignment {
 }
 
 fn base_cnf(expr: BExpr) -> Clauses {
+    base_cnf_with(expr, true)
+}
+
+fn base_cnf_with(expr: BExpr, templates: bool) -> Clauses {
     let mut tseitin = Tseitin {
         namings: BTreeMap::new(),
-        next_name: expr.free()
+        next_name: expr.free(),
+        templates
     };
 
     let base = tseitin.assign(expr);
@@ -214,7 +336,7 @@ fn base_cnf(expr: BExpr) -> Clauses {
 }
 
 pub fn cnf(mut expr: BExpr) -> Clauses {
-    expr = remove_true_false(expr);
+    expr = remove_true_false(skolemise::skolemise(expr));
 
     if let BExpr::True | BExpr::False = expr {
         return equiv_nf::cnf(expr);
@@ -224,7 +346,7 @@ pub fn cnf(mut expr: BExpr) -> Clauses {
 }
 
 pub fn dnf(mut expr: BExpr) -> Clauses {
-    expr = remove_true_false(expr);
+    expr = remove_true_false(skolemise::skolemise(expr));
 
     if let BExpr::True | BExpr::False = expr {
         return equiv_nf::cnf(expr);
@@ -234,3 +356,75 @@ pub fn dnf(mut expr: BExpr) -> Clauses {
     let inv_cnf = base_cnf(!expr);
     inv_cnf.into_iter().map(|it| it.reverse()).collect()
 }
+
+
+#[cfg(test)]
+mod test {
+    use crate::nf::skolemise;
+    use crate::nf::tseitin_nf::{base_cnf_with, remove_true_false};
+    use crate::res::find_model;
+    use crate::test::TestContext;
+
+    /// Encodes the given expression with and without clause templates for derived connectives, and
+    /// returns the amount of clauses of both, after checking they are equisatisfiable.
+    fn template_clause_counts(ctx: &mut TestContext, str: &str) -> (usize, usize) {
+        let expr = remove_true_false(skolemise::skolemise(ctx.bexpr(str)));
+
+        let with = base_cnf_with(expr.clone(), true);
+        let without = base_cnf_with(expr, false);
+
+        assert_eq!(find_model(&with).is_some(), find_model(&without).is_some());
+
+        (with.len(), without.len())
+    }
+
+    #[test]
+    fn test_templates() {
+        let mut ctx = TestContext::new();
+
+        for str in ["P -> Q", "P <-> Q", "!(P <-> Q)", "(P <-> Q) & !(Q <-> R) & (R -> P)", "(P <-> Q) & !(P <-> !Q)"] {
+            let (with, without) = template_clause_counts(&mut ctx, str);
+            assert!(with < without, "{str}: {with} clauses with templates, {without} without");
+        }
+    }
+
+    #[test]
+    fn test_template_counts() {
+        let mut ctx = TestContext::new();
+
+        // Base clause and 3 template clauses
+        assert_eq!((4, 6), template_clause_counts(&mut ctx, "P -> Q"));
+
+        // Base clause and 4 template clauses
+        assert_eq!(5, template_clause_counts(&mut ctx, "P <-> Q").0);
+        assert_eq!(5, template_clause_counts(&mut ctx, "!(P <-> Q)").0);
+    }
+
+    #[test]
+    fn test_remove_true_false() {
+        let mut ctx = TestContext::new();
+
+        let [p, t, f] = ctx.bexprs(["P", "true", "false"]).try_into().unwrap();
+
+        assert_eq!(f, remove_true_false(ctx.bexpr("P & false")));
+        assert_eq!(f, remove_true_false(ctx.bexpr("false & P")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("P & true")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("true & P")));
+
+        assert_eq!(t, remove_true_false(ctx.bexpr("P | true")));
+        assert_eq!(t, remove_true_false(ctx.bexpr("true | P")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("P | false")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("false | P")));
+    }
+
+    #[test]
+    fn test_remove_true_false_nested() {
+        let mut ctx = TestContext::new();
+
+        let [p, q] = ctx.bexprs(["P", "Q"]).try_into().unwrap();
+
+        assert_eq!(q, remove_true_false(ctx.bexpr("(P | true) & Q")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("P | (Q & false)")));
+        assert_eq!(p, remove_true_false(ctx.bexpr("P | !true")));
+    }
+}
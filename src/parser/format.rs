@@ -0,0 +1,106 @@
+use crate::expr::*;
+use crate::fmt::{NameEntry, NameTable};
+
+use super::span::SpanMap;
+
+
+/// The precedence level at which an expression is written. An expression must be parenthesized if
+/// it binds looser than the level of the position it is written at.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Level {
+    /// A position where a disjunction can go without parentheses.
+    Or,
+
+    /// A position where a conjunction can go without parentheses.
+    And,
+
+    /// A position where only a base expression can go without parentheses, like a quantifier body.
+    Base
+}
+
+/// Writes a [Stmt] in the input language, such that it parses back to the same statement. The
+/// names are written as given by the [NameTable]. Subexpressions that are [grouped](SpanMap::is_grouped)
+/// in the given [SpanMap] are parenthesized, even where precedence does not require it.
+pub(super) fn write_stmt(stmt: &Stmt, names: &NameTable, groups: &SpanMap) -> String {
+    let premises: Vec<_> = stmt.premises().iter().map(|it| write_bexpr(it, names, groups, Level::Or)).collect();
+    let conclusions: Vec<_> = stmt.conclusions().iter().map(|it| write_bexpr(it, names, groups, Level::Or)).collect();
+
+    let mut out = premises.join(", ");
+
+    if out.is_empty() {
+        out.push_str("|-");
+    } else {
+        out.push_str(" |-");
+    }
+
+    if !conclusions.is_empty() {
+        out.push(' ');
+        out.push_str(&conclusions.join(", "));
+    }
+
+    out
+}
+
+fn write_bexpr(expr: &BExpr, names: &NameTable, groups: &SpanMap, level: Level) -> String {
+    let grouped = groups.is_grouped(expr);
+
+    match expr {
+        BExpr::True => parenthesize("true".to_string(), grouped),
+        BExpr::False => parenthesize("false".to_string(), grouped),
+
+        BExpr::Pred(name, args) => parenthesize(write_call(name, args, names), grouped),
+
+        BExpr::And(lhs, rhs) => {
+            let out = format!("{} & {}", write_bexpr(lhs, names, groups, Level::And), write_bexpr(rhs, names, groups, Level::Base));
+            parenthesize(out, grouped || level > Level::And)
+        },
+
+        BExpr::Or(lhs, rhs) => {
+            let out = format!("{} | {}", write_bexpr(lhs, names, groups, Level::Or), write_bexpr(rhs, names, groups, Level::And));
+            parenthesize(out, grouped || level > Level::Or)
+        },
+
+        BExpr::Not(rhs) => parenthesize(format!("!{}", write_bexpr(rhs, names, groups, Level::Base)), grouped),
+
+        BExpr::All(name, rhs) => parenthesize(format!("all {}: {}", write_ident(name, names), write_bexpr(rhs, names, groups, Level::Base)), grouped),
+        BExpr::Some(name, rhs) => parenthesize(format!("some {}: {}", write_ident(name, names), write_bexpr(rhs, names, groups, Level::Base)), grouped),
+    }
+}
+
+fn write_aexpr(expr: &AExpr, names: &NameTable) -> String {
+    match expr {
+        AExpr::Var(name) => match names.entry(name) {
+            // Unbound variables are written as `:x`
+            NameEntry::Unbound(id) => format!(":{id}"),
+            _ => write_ident(name, names)
+        },
+
+        AExpr::Fun(name, args) => write_call(name, args, names),
+    }
+}
+
+fn write_call(name: &Name, args: &[AExpr], names: &NameTable) -> String {
+    let ident = write_ident(name, names);
+
+    if args.is_empty() {
+        return ident;
+    }
+
+    let args: Vec<_> = args.iter().map(|it| write_aexpr(it, names)).collect();
+    format!("{ident}({})", args.join(", "))
+}
+
+fn write_ident(name: &Name, names: &NameTable) -> String {
+    match names.entry(name).identifier() {
+        Some(id) => id.clone(),
+        None => names.write_entry(name)
+    }
+}
+
+fn parenthesize(str: String, parens: bool) -> String {
+    if parens {
+        format!("({str})")
+    } else {
+        str
+    }
+}
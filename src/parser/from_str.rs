@@ -0,0 +1,36 @@
+use std::str::FromStr;
+
+use crate::expr::{AExpr, BExpr, Stmt};
+
+use super::error::Error;
+use super::ParseContext;
+
+// These parse using a fresh ParseContext, whose NameTable is dropped afterwards. The parsed
+// expressions display with generated names, use a ParseContext to keep the original names.
+
+impl FromStr for AExpr {
+    type Err = Error;
+
+    /// Parses a term using a fresh [ParseContext]. See [ParseContext::aexpr].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParseContext::new().aexpr(s)
+    }
+}
+
+impl FromStr for BExpr {
+    type Err = Error;
+
+    /// Parses a boolean expression using a fresh [ParseContext]. See [ParseContext::bexpr].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParseContext::new().bexpr(s)
+    }
+}
+
+impl FromStr for Stmt {
+    type Err = Error;
+
+    /// Parses a statement using a fresh [ParseContext]. See [ParseContext::stmt].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParseContext::new().stmt(s)
+    }
+}
@@ -1,6 +1,18 @@
 use super::token::{TKind, Token};
 use super::coord::InputCoord;
 
+/// The comments recognized by a [Lexer], in addition to whitespace.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum CommentStyle {
+    /// Only `#` comments, running to the end of the line.
+    #[default]
+    Hash,
+
+    /// `#` comments, and C-style `//` line comments and `/* */` block comments. Block comments do not
+    /// nest and may span multiple lines.
+    C
+}
+
 /// The lexical analyzer
 pub struct Lexer<I> where I : Iterator<Item = char> {
     /// The current coordinate
@@ -16,18 +28,27 @@ pub struct Lexer<I> where I : Iterator<Item = char> {
     lb: Option<char>,
 
     /// The character stream
-    itr: I
+    itr: I,
+
+    /// The comments to skip
+    comments: CommentStyle
 }
 
 impl<I> Lexer<I> where I : Iterator<Item = char> {
-    /// Creates a new [Lexer]
-    pub fn new(mut itr: I) -> Self {
+    /// Creates a new [Lexer] that only recognizes `#` comments.
+    pub fn new(itr: I) -> Self {
+        Self::with_comment_style(itr, CommentStyle::Hash)
+    }
+
+    /// Creates a new [Lexer] that recognizes the given [CommentStyle].
+    pub fn with_comment_style(mut itr: I, comments: CommentStyle) -> Self {
         Self {
             pos: InputCoord::new(),
             buf: String::new(),
             la: itr.next(),
             lb: None,
-            itr
+            itr,
+            comments
         }
     }
 
@@ -74,15 +95,43 @@ impl<I> Lexer<I> where I : Iterator<Item = char> {
     fn skip(&mut self) {
         while let Some(c @ (' ' | '\n' | '\r' | '\t' | '#')) = self.la {
             if c == '#' {
-                loop {
+                self.line_comment();
+            }
+
+            self.shift();
+        }
+    }
+
+    /// Skips a line comment, up to but not including the line break. The lookahead must be the last
+    /// character of the comment start.
+    fn line_comment(&mut self) {
+        loop {
+            self.shift();
+            if let Some('\n' | '\r') | None = self.la {
+                break;
+            }
+        }
+    }
+
+    /// Skips a block comment, up to and including the closing `*/`. The opening `/*` must have been read
+    /// already, so that `/*/` does not count as a closed comment. Returns `false` if the stream ended
+    /// before the comment was closed.
+    fn block_comment(&mut self) -> bool {
+        loop {
+            match self.la {
+                None => return false,
+
+                Some('*') => {
                     self.shift();
-                    if let Some('\n' | '\r') | None = self.la {
-                        break;
+
+                    if let Some('/') = self.la {
+                        self.shift();
+                        return true;
                     }
                 }
-            }
 
-            self.shift();
+                Some(_) => self.shift()
+            }
         }
     }
 
@@ -256,6 +305,38 @@ impl<I> Lexer<I> where I : Iterator<Item = char> {
         return (self.buf.clone(), kind);
     }
 
+    /// Classify a token that starts with `/`. Returns [None] if it started a comment, which has then been
+    /// skipped. An unclosed block comment is an illegal token.
+    fn slash(&mut self) -> Option<(String, TKind)> {
+        // We had a /, what now?
+        self.push_shift();
+
+        if self.comments == CommentStyle::C {
+            match self.la {
+                // Line comment
+                Some('/') => {
+                    self.line_comment();
+                    return None;
+                }
+
+                // Block comment
+                Some('*') => {
+                    self.push_shift();
+
+                    if self.block_comment() {
+                        return None;
+                    }
+
+                    return Some((self.buf.clone(), TKind::Illegal));
+                }
+
+                _ => {}
+            }
+        }
+
+        Some((self.buf.clone(), TKind::Slash))
+    }
+
     /// Classify a token by the given kind
     fn sym(&mut self, kind: TKind) -> (String, TKind) {
         self.push_shift();
@@ -280,7 +361,12 @@ impl<I> Lexer<I> where I : Iterator<Item = char> {
             Some('+') => self.sym(TKind::Plus),
             Some('-') => self.minus(),
             Some('*') => self.sym(TKind::Star),
-            Some('/') => self.sym(TKind::Slash),
+            Some('/') => match self.slash() {
+                Some(token) => token,
+
+                // Skipped a comment, read the token after it
+                None => return self.token()
+            },
             Some('%') => self.sym(TKind::Perc),
 
             Some('!') => self.excl(),
@@ -2,6 +2,8 @@
 
 #![allow(dead_code, unused)]
 
+use std::collections::BTreeSet;
+
 use crate::expr::*;
 use crate::fmt::NameTable;
 use crate::parser::result::ParseResult;
@@ -11,6 +13,10 @@ pub use output::*;
 pub use error::*;
 pub use coord::*;
 pub use namer::*;
+pub use tptp::*;
+pub use lexer::CommentStyle;
+pub use span::SpanMap;
+pub use parser::DEFAULT_MAX_DEPTH;
 
 use parser::Parser;
 
@@ -26,16 +32,43 @@ mod namer;
 
 mod lexer;
 mod parser;
+mod format;
+
+mod tptp;
+mod span;
+mod from_str;
 
 
 
 pub struct ParseContext {
-    nc: NameContext
+    nc: NameContext,
+    comments: CommentStyle,
+    max_depth: usize
 }
 
 impl ParseContext {
     pub fn new() -> Self {
-        Self { nc: NameContext::new() }
+        Self { nc: NameContext::new(), comments: CommentStyle::Hash, max_depth: DEFAULT_MAX_DEPTH }
+    }
+
+    /// Sets whether using the same predicate or function name with different amounts of arguments in one
+    /// input is a parse error. Such a mismatch is almost always a typo, since the prover would treat both
+    /// uses as different predicates or functions. This is enabled by default. Arities are checked per
+    /// input, so separately parsed inputs may use a name with different amounts of arguments.
+    pub fn set_strict_arity(&mut self, strict: bool) {
+        self.nc.set_strict_arity(strict);
+    }
+
+    /// Sets the [CommentStyle] of inputs parsed after this call. By default, only `#` comments are
+    /// recognized.
+    pub fn set_comment_style(&mut self, comments: CommentStyle) {
+        self.comments = comments;
+    }
+
+    /// Sets the maximum depth to which parentheses, unary operators, argument lists and quantifiers may be nested
+    /// in inputs parsed after this call. Deeper input is rejected with an [Error]. Defaults to [DEFAULT_MAX_DEPTH].
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
     }
 
     pub fn name_table(&self) -> &NameTable {
@@ -46,7 +79,12 @@ impl ParseContext {
     where
     S : Input,
     F : FnOnce(&mut Parser<S::Iter>, &mut NameContext) -> ParseResult<T> {
-        Parser::new(input.char_stream()).parse(
+        self.nc.reset_arities();
+
+        let mut parser = Parser::with_comment_style(input.char_stream(), self.comments);
+        parser.set_max_depth(self.max_depth);
+
+        parser.parse(
             |p, nc| func(p, nc),
             name,
             &mut self.nc
@@ -88,6 +126,47 @@ impl ParseContext {
         self.stmt(input).map(|it| self.with_output(it))
     }
 
+    /// Parses a statement, and returns it along with a [SpanMap] of the ranges in the input where its
+    /// predicates and terms occur.
+    pub fn stmt_with_spans<S>(&mut self, input: S) -> Result<(Stmt, SpanMap), Error> where S : Input {
+        self.nc.record_spans();
+        let stmt = self.stmt(input);
+        let spans = self.nc.take_spans();
+
+        Ok((stmt?, spans))
+    }
+
+    /// Parses a statement, and returns it along with the names it uses, split into two sets. The first set
+    /// contains the static names, that is, the names of predicates, functions, constants and `:x` variables.
+    /// The second set contains the names of variables bound by quantifiers.
+    pub fn stmt_with_symbols<S>(&mut self, input: S) -> Result<(Stmt, BTreeSet<Name>, BTreeSet<Name>), Error> where S : Input {
+        let stmt = self.stmt(input)?;
+
+        let names: BTreeSet<Name> = stmt.names();
+        let (bound, symbols) = names.into_iter().partition(|name| self.name_table().entry(name).scope().is_some());
+
+        Ok((stmt, symbols, bound))
+    }
+
+
+    /// Parses a statement and writes it back in a canonical form. Identifiers are written as they were
+    /// given in the input, and parentheses are only written where they are needed. Derived connectives,
+    /// like `->` and `<->`, are written in terms of `&`, `|` and `!`, and quantifiers over multiple
+    /// names are written as nested quantifiers. The output parses back to the same statement.
+    pub fn reformat<S>(&mut self, input: S) -> Result<String, Error> where S : Input {
+        let stmt = self.stmt(input)?;
+        Ok(format::write_stmt(&stmt, self.name_table(), &SpanMap::new()))
+    }
+
+    /// Like [ParseContext::reformat], but keeps the explicit grouping of the input. Every subexpression that
+    /// is written in parentheses or with a `+` prefix in the input is parenthesized in the output, even where
+    /// precedence does not require it. Subexpressions are identified structurally, see [SpanMap], so if a
+    /// grouped subexpression occurs multiple times, all its occurrences are parenthesized.
+    pub fn reformat_grouped<S>(&mut self, input: S) -> Result<String, Error> where S : Input {
+        let (stmt, groups) = self.stmt_with_spans(input)?;
+        Ok(format::write_stmt(&stmt, self.name_table(), &groups))
+    }
+
 
     pub fn unifiable_valid<S>(&mut self, input: S) -> Result<(), Error> where S : Input {
         self.parse(input, "unifiable", |p, _| p.unifiable())?;
@@ -103,6 +182,17 @@ impl ParseContext {
     }
 
 
+    /// Parses an input of the form `P === Q` as two [BExpr]s. Unlike [ParseContext::unifiable], both sides
+    /// are boolean expressions. When a side has multiple expressions, their conjunction is returned.
+    pub fn equivalence<S>(&mut self, input: S) -> Result<(BExpr, BExpr), Error> where S : Input {
+        self.parse(input, "unifiable", |p, nc| p.unifiable()?.into_equivalence(nc))
+    }
+
+    pub fn equivalence_output<S>(&mut self, input: S) -> Result<Output<(BExpr, BExpr)>, Error> where S : Input {
+        self.equivalence(input).map(|it| self.with_output(it))
+    }
+
+
     pub fn aexpr_valid<S>(&mut self, input: S) -> Result<(), Error> where S : Input {
         self.parse(input, "exp", |p, _| p.exp())?;
         Ok(())
@@ -126,6 +216,16 @@ impl ParseContext {
         self.parse(input, "exp", |p, nc| p.exp()?.as_bexpr(nc))
     }
 
+    /// Parses a boolean expression, and returns it along with a [SpanMap] of the ranges in the input where
+    /// its predicates and terms occur.
+    pub fn bexpr_with_spans<S>(&mut self, input: S) -> Result<(BExpr, SpanMap), Error> where S : Input {
+        self.nc.record_spans();
+        let bexpr = self.bexpr(input);
+        let spans = self.nc.take_spans();
+
+        Ok((bexpr?, spans))
+    }
+
     pub fn bexpr_output<S>(&mut self, input: S) -> Result<Output<BExpr>, Error> where S : Input {
         self.bexpr(input).map(|it| self.with_output(it))
     }
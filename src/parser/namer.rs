@@ -1,8 +1,12 @@
 use std::collections::BTreeMap;
 
-use crate::expr::Name;
+use crate::expr::{AExpr, BExpr, Name};
 use crate::fmt::NameTable;
 
+use super::coord::InputRange;
+use super::result::ParseResult;
+use super::span::SpanMap;
+
 
 /// A context that binds identifiers to the correct names.
 pub struct NameContext {
@@ -12,7 +16,12 @@ pub struct NameContext {
     bound: Vec<BTreeMap<String, Name>>,
     unbound: BTreeMap<String, Name>,
 
-    rev_table: NameTable
+    rev_table: NameTable,
+
+    spans: Option<SpanMap>,
+
+    strict_arity: bool,
+    arities: BTreeMap<Name, (usize, InputRange)>
 }
 
 impl NameContext {
@@ -25,8 +34,43 @@ impl NameContext {
             bound: Vec::new(),
             unbound: BTreeMap::new(),
 
-            rev_table: NameTable::new()
+            rev_table: NameTable::new(),
+
+            spans: None,
+
+            strict_arity: true,
+            arities: BTreeMap::new()
+        }
+    }
+
+    /// Sets whether using a name with different amounts of arguments is an error, see [Self::check_arity].
+    pub(super) fn set_strict_arity(&mut self, strict: bool) {
+        self.strict_arity = strict;
+    }
+
+    /// Forgets the arities seen so far, so that the next input may use names with other arities.
+    pub(super) fn reset_arities(&mut self) {
+        self.arities.clear();
+    }
+
+    /// Checks that a predicate or function is used with the same amount of arguments as where it was first
+    /// used, if strict arity is enabled. Otherwise, or if this is the first use, this always succeeds.
+    pub(super) fn check_arity(&mut self, name: Name, arity: usize, range: InputRange) -> ParseResult<()> {
+        if !self.strict_arity {
+            return Ok(());
+        }
+
+        let (first, first_range) = *self.arities.entry(name).or_insert((arity, range));
+
+        if first != arity {
+            let id = self.rev_table.write_entry(&name);
+            let line = first_range.from.line;
+            let col = first_range.from.col;
+
+            return range.error(format!("{id} is used with {arity} arguments, but with {first} arguments at [{line}:{col}]"));
         }
+
+        Ok(())
     }
 
     pub fn rev_table(&self) -> &NameTable {
@@ -37,6 +81,37 @@ impl NameContext {
         self.rev_table
     }
 
+    /// Starts recording the ranges of predicates and terms into a new [SpanMap].
+    pub(super) fn record_spans(&mut self) {
+        self.spans = Some(SpanMap::new());
+    }
+
+    /// Stops recording ranges and returns the [SpanMap] recorded since [Self::record_spans].
+    pub(super) fn take_spans(&mut self) -> SpanMap {
+        self.spans.take().unwrap_or_default()
+    }
+
+    /// Records the range of a predicate, if ranges are being recorded.
+    pub(super) fn add_pred_span(&mut self, name: Name, args: &[AExpr], range: InputRange) {
+        if let Some(spans) = &mut self.spans {
+            spans.add_pred(name, args, range);
+        }
+    }
+
+    /// Records the range of a term, if ranges are being recorded.
+    pub(super) fn add_term_span(&mut self, term: &AExpr, range: InputRange) {
+        if let Some(spans) = &mut self.spans {
+            spans.add_term(term, range);
+        }
+    }
+
+    /// Records an explicitly grouped Boolean subexpression, if ranges are being recorded.
+    pub(super) fn add_group_span(&mut self, expr: &BExpr, range: InputRange) {
+        if let Some(spans) = &mut self.spans {
+            spans.add_group(expr, range);
+        }
+    }
+
     /// Generates a new unique name
     fn new_name(&mut self) -> Name {
         return self.next_unique_name.incr();
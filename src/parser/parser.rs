@@ -15,9 +15,24 @@ pub struct Parser<I> where I : Iterator<Item = char> {
     la: Option<TKind>,
 
     /// The full lookahead token
-    token: Option<Token>
+    token: Option<Token>,
+
+    /// The amount of parentheses, unary operators, argument lists and quantifiers the parser is currently nested in
+    depth: usize,
+
+    /// The maximum nesting depth, see [Parser::set_max_depth]
+    max_depth: usize,
+
+    /// The error raised when the input exceeded the maximum nesting depth. This is kept separately, since
+    /// some production rules discard errors of their alternatives.
+    too_deep: Option<ParseError>
 }
 
+/// The default maximum nesting depth of a [Parser], see [Parser::set_max_depth]. Input of this depth fits
+/// in the 8 MiB stack of a main thread, and in optimized builds also in the 2 MiB stack of a spawned
+/// thread. Unoptimized builds on spawned threads may need a lower maximum.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
 pub enum Precedence {
@@ -48,12 +63,20 @@ impl Precedence {
 impl<I> Parser<I> where I : Iterator<Item = char> {
     /// Creates a new [Parser]
     pub fn new(iter: I) -> Self {
-        let lexer = Lexer::new(iter);
+        Self::with_comment_style(iter, CommentStyle::Hash)
+    }
+
+    /// Creates a new [Parser] whose [Lexer] recognizes the given [CommentStyle].
+    pub fn with_comment_style(iter: I, comments: CommentStyle) -> Self {
+        let lexer = Lexer::with_comment_style(iter, comments);
 
         let mut parser = Self {
             lexer,
             la: None,
-            token: None
+            token: None,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            too_deep: None
         };
 
         // Shift first token into lookahead
@@ -62,6 +85,40 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
         parser
     }
 
+    /// Sets the maximum depth to which parentheses, unary operators, argument lists and quantifiers may be nested.
+    /// Input that nests deeper is rejected with an error, rather than overflowing the stack. Defaults to
+    /// [DEFAULT_MAX_DEPTH].
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Enters a nested production that started at `from`, failing if that exceeds the maximum depth. Every
+    /// successful call must be followed by a call to [Parser::leave]. Once the maximum depth was exceeded,
+    /// this keeps failing, so that productions that discard the error cannot nest any deeper.
+    fn enter(&mut self, from: InputCoord) -> ParseResult<()> {
+        if let Some(err) = &self.too_deep {
+            return Err(err.clone());
+        }
+
+        if self.depth >= self.max_depth {
+            let err = ParseError::Error {
+                from, to: self.pos(),
+                msg: format!("Expression is nested deeper than {} levels", self.max_depth)
+            };
+
+            self.too_deep = Some(err.clone());
+            return Err(err);
+        }
+
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Leaves a nested production, see [Parser::enter].
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
     /// The current position, which is the start coordinate of the next token.
     /// If no next token is present, it is the coordinate after the very last character in the input.
     fn pos(&self) -> InputCoord {
@@ -186,7 +243,10 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
         }
 
         if let Ok(op) = self.unary_op() {
-            let exp = Self::expect(self.unary_exp(), "unary_exp")?;
+            self.enter(from)?;
+            let exp = Self::expect(self.unary_exp(), "unary_exp");
+            self.leave();
+            let exp = exp?;
 
             return Ok(ExpNode { 
                 from, to: self.pos(),
@@ -195,7 +255,10 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
         }
 
         if let Ok(_) = self.lit(TKind::LPar) {
-            let exp = Self::expect(self.exp(), "exp")?;
+            self.enter(from)?;
+            let exp = Self::expect(self.exp(), "exp");
+            self.leave();
+            let exp = exp?;
             Self::expect(self.lit(TKind::RPar), "RPar")?;
 
             return Ok(ExpNode { 
@@ -249,17 +312,22 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
     ///   | ''
     /// ```
     pub fn args(&mut self) -> ParseResult<Vec<ExpNode>> {
-        if let Ok(exp) = self.exp() {
-            if let Ok(_) = self.lit(TKind::Comma) {
-                let mut rest = Self::expect(self.args(), "args")?;
-                rest.insert(0, exp);
-                return Ok(rest);
-            }
+        let Ok(first) = self.exp() else {
+            return Ok(vec![]);
+        };
+
+        // Read the remaining arguments in a loop, as long lists would otherwise overflow the stack. Like
+        // an empty list, a trailing comma is allowed.
+        let mut args = vec![first];
+        while self.lit(TKind::Comma).is_ok() {
+            let Ok(exp) = self.exp() else {
+                break;
+            };
 
-            return Ok(vec![exp]);
+            args.push(exp);
         }
 
-        return Ok(vec![]);
+        Ok(args)
     }
 
     /// Reads a function call or identifier
@@ -274,7 +342,10 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
         let name = self.ident()?;
 
         if let Ok(_) = self.lit(TKind::LPar) {
-            let args = Self::expect(self.args(), "args")?;
+            self.enter(from)?;
+            let args = Self::expect(self.args(), "args");
+            self.leave();
+            let args = args?;
             Self::expect(self.lit(TKind::RPar), "RPar")?;
 
             return Ok(ExpNode { 
@@ -483,7 +554,11 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
         let quant = self.quant()?;
         let names = Self::expect(self.names(), "names")?;
         Self::expect(self.lit(TKind::Colon), "Colon")?;
-        let exp = Self::expect(self.base_exp(), "base_exp")?;
+
+        self.enter(from)?;
+        let exp = Self::expect(self.base_exp(), "base_exp");
+        self.leave();
+        let exp = exp?;
 
         return Ok(ExpNode {
             from, to: self.pos(),
@@ -537,7 +612,13 @@ impl<I> Parser<I> where I : Iterator<Item = char> {
 
     /// Calls the given parse `fun`, if it fails reports that it expected `rule`. Then it expects EOF. Then it reports an [Error] upon failure.
     pub fn parse<F, R>(&mut self, fun: F, rule: &str, nc: &mut NameContext) -> Result<R, Error> where F : FnOnce(&mut Parser<I>, &mut NameContext) -> ParseResult<R> {
-        let res = Self::expect_final(fun(self, nc), rule)?;
+        let res = fun(self, nc);
+
+        if let Some(err) = self.too_deep.take() {
+            return Self::expect_final(Err(err), rule);
+        }
+
+        let res = Self::expect_final(res, rule)?;
         Self::expect_final(self.eof(), "EOF")?;
 
         Ok(res)
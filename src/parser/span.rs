@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::expr::{AExpr, BExpr, Name};
+
+use super::coord::InputRange;
+
+/// A side-table that maps the predicates and terms of a parsed expression to the ranges in the input
+/// where they occur. This allows passes that run after parsing to point at a location in the input.
+///
+/// Sub-expressions are identified structurally: all occurrences of the same predicate or term share
+/// an entry, which lists their ranges in the order they appear in the input. Only predicates and terms
+/// are recorded, connectives and quantifiers are not, since these are restructured during parsing.
+///
+/// Additionally, the map records which Boolean subexpressions were explicitly grouped in the input, with
+/// parentheses or a `+` prefix, so that a formatter can reproduce the grouping of the input. These are
+/// identified structurally as well, so when a grouped subexpression also occurs ungrouped, all its
+/// occurrences are considered grouped.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct SpanMap {
+    preds: BTreeMap<(Name, Vec<AExpr>), Vec<InputRange>>,
+    terms: BTreeMap<AExpr, Vec<InputRange>>,
+    groups: BTreeMap<BExpr, Vec<InputRange>>
+}
+
+impl SpanMap {
+    /// Creates a new, empty [SpanMap].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an occurrence of a predicate.
+    pub(super) fn add_pred(&mut self, name: Name, args: &[AExpr], range: InputRange) {
+        self.preds.entry((name, args.to_vec())).or_default().push(range);
+    }
+
+    /// Records an occurrence of a term.
+    pub(super) fn add_term(&mut self, term: &AExpr, range: InputRange) {
+        self.terms.entry(term.clone()).or_default().push(range);
+    }
+
+    /// Records an explicitly grouped Boolean subexpression.
+    pub(super) fn add_group(&mut self, expr: &BExpr, range: InputRange) {
+        self.groups.entry(expr.clone()).or_default().push(range);
+    }
+
+    /// Returns the ranges where the given predicate occurs, in input order.
+    pub fn pred_spans(&self, name: Name, args: &[AExpr]) -> &[InputRange] {
+        self.preds.get(&(name, args.to_vec())).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the ranges where the given term occurs, in input order.
+    pub fn term_spans(&self, term: &AExpr) -> &[InputRange] {
+        self.terms.get(term).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the ranges where the given Boolean subexpression is explicitly grouped, in input order. The
+    /// ranges include the parentheses or the `+` prefix.
+    pub fn group_spans(&self, expr: &BExpr) -> &[InputRange] {
+        self.groups.get(expr).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns whether the given Boolean subexpression is explicitly grouped somewhere in the input.
+    pub fn is_grouped(&self, expr: &BExpr) -> bool {
+        self.groups.contains_key(expr)
+    }
+
+    /// Returns the ranges of all predicates and function applications with the given name, in input order.
+    /// Variables are not included.
+    pub fn name_spans(&self, name: Name) -> Vec<InputRange> {
+        let preds = self.preds.iter()
+            .filter(|((it, _), _)| *it == name)
+            .flat_map(|(_, spans)| spans);
+
+        let terms = self.terms.iter()
+            .filter(|(it, _)| matches!(it, AExpr::Fun(it, _) if *it == name))
+            .flat_map(|(_, spans)| spans);
+
+        let mut spans: Vec<InputRange> = preds.chain(terms).copied().collect();
+        spans.sort_by_key(|it| it.from.pos);
+        spans
+    }
+
+    /// Returns whether no occurrences or groupings were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.preds.is_empty() && self.terms.is_empty() && self.groups.is_empty()
+    }
+}
@@ -0,0 +1,686 @@
+use std::iter::Peekable;
+
+use crate::expr::*;
+
+use super::coord::InputCoord;
+use super::error::Error;
+use super::input::Input;
+use super::namer::NameContext;
+use super::ParseContext;
+
+
+/// The role of an annotated formula in a TPTP problem.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+pub enum Role {
+    /// An axiom, assumed to be true.
+    Axiom,
+
+    /// A hypothesis, assumed to be true.
+    Hypothesis,
+
+    /// A conjecture, which is to be proven from the axioms and hypotheses.
+    Conjecture,
+
+    /// A conjecture that has already been negated, which is to be refuted together
+    /// with the axioms and hypotheses.
+    NegatedConjecture,
+}
+
+impl Role {
+    /// Parses a role from its TPTP name.
+    fn from_str(str: &str) -> Option<Role> {
+        match str {
+            "axiom" => Some(Role::Axiom),
+            "hypothesis" => Some(Role::Hypothesis),
+            "conjecture" => Some(Role::Conjecture),
+            "negated_conjecture" => Some(Role::NegatedConjecture),
+            _ => None
+        }
+    }
+}
+
+
+/// Parses a TPTP problem in the FOF fragment, using a fresh [ParseContext]. See [ParseContext::tptp].
+pub fn parse_tptp(input: &str) -> Result<Vec<(Role, BExpr)>, Error> {
+    ParseContext::new().tptp(input)
+}
+
+/// Converts the formulas of a TPTP problem into a [Stmt]. Axioms, hypotheses and negated conjectures
+/// become premises, conjectures become conclusions. A problem without conjectures is a refutation
+/// problem, its conclusion is `false`.
+pub fn tptp_stmt(formulas: Vec<(Role, BExpr)>) -> Stmt {
+    let mut premises = Vec::new();
+    let mut conclusions = Vec::new();
+
+    for (role, expr) in formulas {
+        match role {
+            Role::Conjecture => conclusions.push(expr),
+            _ => premises.push(expr)
+        }
+    }
+
+    if conclusions.is_empty() {
+        conclusions.push(BExpr::False);
+    }
+
+    Stmt::from_implication(premises, conclusions)
+}
+
+
+/// A TPTP token kind.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum TKind {
+    /// Words starting with a lowercase letter: `fof`, `axiom`, `p`
+    LowerWord,
+
+    /// Words starting with an uppercase letter, which are variables: `X`, `Y1`
+    UpperWord,
+
+    /// Words starting with a dollar sign: `$true`, `$false`
+    DollarWord,
+
+    /// Single quoted words: `'a name'`
+    Quoted,
+
+    /// Integers: `1`, `42`
+    Integer,
+
+    /// `(`
+    LPar,
+
+    /// `)`
+    RPar,
+
+    /// `[`
+    LBrack,
+
+    /// `]`
+    RBrack,
+
+    /// `,`
+    Comma,
+
+    /// `.`
+    Dot,
+
+    /// `:`
+    Colon,
+
+    /// `!`
+    Excl,
+
+    /// `?`
+    Quest,
+
+    /// `~`
+    Tilde,
+
+    /// `&`
+    Amp,
+
+    /// `|`
+    Bar,
+
+    /// `=`
+    Is,
+
+    /// `!=`
+    NEq,
+
+    /// `=>`
+    RArrow,
+
+    /// `<=`
+    LArrow,
+
+    /// `<=>`
+    LRArrow,
+
+    /// `<~>`
+    Xor,
+
+    /// `~|`
+    Nor,
+
+    /// `~&`
+    Nand,
+
+    /// Any unrecognized character
+    Illegal,
+}
+
+/// A TPTP token.
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TKind,
+    text: String,
+    from: InputCoord,
+    to: InputCoord
+}
+
+/// The lexical analyzer for TPTP.
+struct Lexer<I> where I : Iterator<Item = char> {
+    pos: InputCoord,
+    itr: Peekable<I>
+}
+
+impl<I> Lexer<I> where I : Iterator<Item = char> {
+    fn new(itr: I) -> Self {
+        Self {
+            pos: InputCoord::new(),
+            itr: itr.peekable()
+        }
+    }
+
+    fn la(&mut self) -> Option<char> {
+        self.itr.peek().copied()
+    }
+
+    /// Shift one character. Line breaks are counted at `\n`.
+    fn shift(&mut self) -> Option<char> {
+        let c = self.itr.next()?;
+
+        if c == '\n' {
+            self.pos.newline();
+        } else {
+            self.pos.advance();
+        }
+
+        Some(c)
+    }
+
+    /// Skips whitespaces and comments, i.e. `% line comments` and `/* block comments */`.
+    fn skip(&mut self) -> Result<(), Error> {
+        loop {
+            match self.la() {
+                Some(' ' | '\n' | '\r' | '\t') => {
+                    self.shift();
+                },
+
+                Some('%') => {
+                    while let Some(c) = self.la() && c != '\n' {
+                        self.shift();
+                    }
+                },
+
+                Some('/') => {
+                    let from = self.pos;
+                    self.shift();
+
+                    if self.shift() != Some('*') {
+                        return Err(Error { msg: "Expected block comment".to_string(), from, to: self.pos });
+                    }
+
+                    let mut star = false;
+                    loop {
+                        match self.shift() {
+                            Some('/') if star => break,
+                            Some(c) => star = c == '*',
+                            None => return Err(Error { msg: "Unterminated block comment".to_string(), from, to: self.pos })
+                        }
+                    }
+                },
+
+                _ => return Ok(())
+            }
+        }
+    }
+
+    fn word(&mut self, text: &mut String) {
+        while let Some(c @ ('a'..='z' | 'A'..='Z' | '0'..='9' | '_')) = self.la() {
+            text.push(c);
+            self.shift();
+        }
+    }
+
+    /// Reads a token. Returns [None] at the end of the stream.
+    fn token(&mut self) -> Result<Option<Token>, Error> {
+        self.skip()?;
+
+        let from = self.pos;
+        let mut text = String::new();
+
+        let Some(c) = self.shift() else {
+            return Ok(None);
+        };
+
+        text.push(c);
+
+        let kind = match c {
+            'a'..='z' => {
+                self.word(&mut text);
+                TKind::LowerWord
+            },
+
+            'A'..='Z' => {
+                self.word(&mut text);
+                TKind::UpperWord
+            },
+
+            '$' => {
+                self.word(&mut text);
+                TKind::DollarWord
+            },
+
+            '0'..='9' => {
+                while let Some(c @ '0'..='9') = self.la() {
+                    text.push(c);
+                    self.shift();
+                }
+                TKind::Integer
+            },
+
+            '\'' => {
+                // The quotes are not part of the name
+                text.clear();
+
+                loop {
+                    match self.shift() {
+                        Some('\'') => break,
+                        Some('\\') => match self.shift() {
+                            Some(c) => text.push(c),
+                            None => return Err(Error { msg: "Unterminated quoted word".to_string(), from, to: self.pos })
+                        },
+                        Some(c) => text.push(c),
+                        None => return Err(Error { msg: "Unterminated quoted word".to_string(), from, to: self.pos })
+                    }
+                }
+
+                TKind::Quoted
+            },
+
+            '(' => TKind::LPar,
+            ')' => TKind::RPar,
+            '[' => TKind::LBrack,
+            ']' => TKind::RBrack,
+            ',' => TKind::Comma,
+            '.' => TKind::Dot,
+            ':' => TKind::Colon,
+            '?' => TKind::Quest,
+            '&' => TKind::Amp,
+            '|' => TKind::Bar,
+
+            '!' => self.follow(&mut text, '=', TKind::NEq, TKind::Excl),
+
+            '=' => self.follow(&mut text, '>', TKind::RArrow, TKind::Is),
+
+            '~' => match self.la() {
+                Some('|') => self.follow(&mut text, '|', TKind::Nor, TKind::Tilde),
+                Some('&') => self.follow(&mut text, '&', TKind::Nand, TKind::Tilde),
+                _ => TKind::Tilde
+            },
+
+            '<' => match self.la() {
+                Some('=') => {
+                    self.shift();
+                    text.push('=');
+                    self.follow(&mut text, '>', TKind::LRArrow, TKind::LArrow)
+                },
+
+                Some('~') => {
+                    self.shift();
+                    text.push('~');
+                    self.follow(&mut text, '>', TKind::Xor, TKind::Illegal)
+                },
+
+                _ => TKind::Illegal
+            },
+
+            _ => TKind::Illegal
+        };
+
+        Ok(Some(Token {
+            kind,
+            text,
+            from,
+            to: self.pos
+        }))
+    }
+
+    /// Reads `next` if it is the lookahead, and returns `then`, otherwise returns `otherwise`.
+    fn follow(&mut self, text: &mut String, next: char, then: TKind, otherwise: TKind) -> TKind {
+        if self.la() == Some(next) {
+            self.shift();
+            text.push(next);
+            then
+        } else {
+            otherwise
+        }
+    }
+}
+
+
+/// A parser for TPTP problems in the FOF fragment.
+struct Parser<'nc, I> where I : Iterator<Item = char> {
+    lexer: Lexer<I>,
+    token: Option<Token>,
+    nc: &'nc mut NameContext
+}
+
+impl<'nc, I> Parser<'nc, I> where I : Iterator<Item = char> {
+    fn new(iter: I, nc: &'nc mut NameContext) -> Result<Self, Error> {
+        let mut parser = Self {
+            lexer: Lexer::new(iter),
+            token: None,
+            nc
+        };
+
+        parser.shift()?;
+
+        Ok(parser)
+    }
+
+    fn shift(&mut self) -> Result<Option<Token>, Error> {
+        let next = self.lexer.token()?;
+        Ok(std::mem::replace(&mut self.token, next))
+    }
+
+    fn la(&self) -> Option<TKind> {
+        self.token.as_ref().map(|it| it.kind)
+    }
+
+    /// Creates an error at the lookahead token.
+    fn error<T, S>(&self, msg: S) -> Result<T, Error> where S : Into<String> {
+        let (from, to) = match &self.token {
+            Some(tok) => (tok.from, tok.to),
+            None => (self.lexer.pos, self.lexer.pos)
+        };
+
+        Err(Error { msg: msg.into(), from, to })
+    }
+
+    /// Reads a token of the given [TKind] if it is the lookahead.
+    fn accept(&mut self, kind: TKind) -> Result<Option<Token>, Error> {
+        if self.la() == Some(kind) {
+            self.shift()
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads a token of the given [TKind], or fails if it is not the lookahead.
+    fn expect(&mut self, kind: TKind, rule: &str) -> Result<Token, Error> {
+        match self.accept(kind)? {
+            Some(tok) => Ok(tok),
+            None => self.error(format!("Expected {rule}"))
+        }
+    }
+
+    /// Reads a TPTP problem:
+    /// ```txt
+    /// problem = fof*
+    /// fof = 'fof' '(' name ',' LowerWord ',' formula (',' annotations)? ')' '.'
+    /// name = LowerWord | Quoted | Integer
+    /// ```
+    fn problem(&mut self) -> Result<Vec<(Role, BExpr)>, Error> {
+        let mut out = Vec::new();
+
+        while let Some(tok) = &self.token {
+            if tok.kind != TKind::LowerWord || tok.text != "fof" {
+                return self.error("Expected fof, other TPTP fragments and includes are not supported");
+            }
+
+            self.shift()?;
+            self.expect(TKind::LPar, "LPar")?;
+
+            if self.accept(TKind::LowerWord)?.is_none()
+            && self.accept(TKind::Quoted)?.is_none()
+            && self.accept(TKind::Integer)?.is_none() {
+                return self.error("Expected name");
+            }
+
+            self.expect(TKind::Comma, "Comma")?;
+
+            let role = match &self.token {
+                Some(Token { kind: TKind::LowerWord, text, .. }) => match Role::from_str(text) {
+                    Some(role) => role,
+                    None => return self.error(format!("Unsupported role: {text}"))
+                },
+                _ => return self.error("Expected role")
+            };
+
+            self.shift()?;
+            self.expect(TKind::Comma, "Comma")?;
+
+            let formula = self.formula()?;
+
+            if self.accept(TKind::Comma)?.is_some() {
+                self.annotations()?;
+            }
+
+            self.expect(TKind::RPar, "RPar")?;
+            self.expect(TKind::Dot, "Dot")?;
+
+            out.push((role, formula));
+        }
+
+        Ok(out)
+    }
+
+    /// Skips annotations, which are balanced sequences of tokens up to the closing parenthesis
+    /// of the annotated formula.
+    fn annotations(&mut self) -> Result<(), Error> {
+        let mut depth = 0usize;
+
+        loop {
+            match self.la() {
+                Some(TKind::LPar | TKind::LBrack) => depth += 1,
+                Some(TKind::RPar | TKind::RBrack) if depth == 0 => return Ok(()),
+                Some(TKind::RPar | TKind::RBrack) => depth -= 1,
+                None => return self.error("Expected RPar"),
+                _ => {}
+            }
+
+            self.shift()?;
+        }
+    }
+
+    /// Reads a formula:
+    /// ```txt
+    /// formula
+    ///   = unitary ('&' unitary)+
+    ///   | unitary ('|' unitary)+
+    ///   | unitary bin_op unitary
+    ///   | unitary
+    /// bin_op = '=>' | '<=' | '<=>' | '<~>' | '~|' | '~&'
+    /// ```
+    fn formula(&mut self) -> Result<BExpr, Error> {
+        let lhs = self.unitary()?;
+
+        match self.la() {
+            Some(kind @ (TKind::Amp | TKind::Bar)) => {
+                let mut expr = lhs;
+
+                while self.accept(kind)?.is_some() {
+                    let rhs = self.unitary()?;
+                    expr = if kind == TKind::Amp {
+                        expr & rhs
+                    } else {
+                        expr | rhs
+                    };
+                }
+
+                Ok(expr)
+            },
+
+            Some(kind @ (TKind::RArrow | TKind::LArrow | TKind::LRArrow | TKind::Xor | TKind::Nor | TKind::Nand)) => {
+                self.shift()?;
+                let rhs = self.unitary()?;
+
+                Ok(match kind {
+                    TKind::RArrow => BExpr::im(lhs, rhs),
+                    TKind::LArrow => BExpr::revim(lhs, rhs),
+                    TKind::LRArrow => BExpr::equiv(lhs, rhs),
+                    TKind::Xor => !BExpr::equiv(lhs, rhs),
+                    TKind::Nor => !(lhs | rhs),
+                    _ => !(lhs & rhs),
+                })
+            },
+
+            _ => Ok(lhs)
+        }
+    }
+
+    /// Reads a unitary formula:
+    /// ```txt
+    /// unitary
+    ///   = ('!' | '?') '[' UpperWord (',' UpperWord)* ']' ':' unitary
+    ///   | '~' unitary
+    ///   | '(' formula ')'
+    ///   | atom
+    /// ```
+    fn unitary(&mut self) -> Result<BExpr, Error> {
+        match self.la() {
+            Some(kind @ (TKind::Excl | TKind::Quest)) => {
+                self.shift()?;
+                self.expect(TKind::LBrack, "LBrack")?;
+
+                let mut names = Vec::new();
+                loop {
+                    let var = self.expect(TKind::UpperWord, "variable")?;
+                    names.push(self.nc.enter(var.text));
+
+                    if self.accept(TKind::Comma)?.is_none() {
+                        break;
+                    }
+                }
+
+                self.expect(TKind::RBrack, "RBrack")?;
+                self.expect(TKind::Colon, "Colon")?;
+
+                let body = self.unitary();
+
+                for _ in &names {
+                    self.nc.leave();
+                }
+
+                let mut expr = body?;
+                for name in names.into_iter().rev() {
+                    expr = if kind == TKind::Excl {
+                        BExpr::all(name, expr)
+                    } else {
+                        BExpr::some(name, expr)
+                    };
+                }
+
+                Ok(expr)
+            },
+
+            Some(TKind::Tilde) => {
+                self.shift()?;
+                Ok(!self.unitary()?)
+            },
+
+            Some(TKind::LPar) => {
+                self.shift()?;
+                let expr = self.formula()?;
+                self.expect(TKind::RPar, "RPar")?;
+                Ok(expr)
+            },
+
+            _ => self.atom()
+        }
+    }
+
+    /// Reads an atomic formula:
+    /// ```txt
+    /// atom
+    ///   = '$true'
+    ///   | '$false'
+    ///   | functor ('(' term (',' term)* ')')?
+    /// functor = LowerWord | Quoted
+    /// ```
+    fn atom(&mut self) -> Result<BExpr, Error> {
+        if let Some(Token { kind: TKind::DollarWord, text, .. }) = &self.token {
+            let expr = match text.as_str() {
+                "$true" => BExpr::True,
+                "$false" => BExpr::False,
+                _ => return self.error(format!("Unsupported defined predicate: {text}"))
+            };
+
+            self.shift()?;
+            return Ok(expr);
+        }
+
+        let Some(functor) = self.functor()? else {
+            return self.error("Expected formula");
+        };
+
+        let args = self.args()?;
+
+        if let Some(TKind::Is | TKind::NEq) = self.la() {
+            return self.error("Equality is not supported");
+        }
+
+        Ok(BExpr::pred(self.nc.resolve_static(functor), args))
+    }
+
+    /// Reads a term:
+    /// ```txt
+    /// term
+    ///   = UpperWord
+    ///   | functor ('(' term (',' term)* ')')?
+    /// ```
+    fn term(&mut self) -> Result<AExpr, Error> {
+        if let Some(Token { kind: TKind::UpperWord, text, .. }) = &self.token {
+            let Some(name) = self.nc.resolve_bound(text) else {
+                return self.error(format!("Unbound variable: {text}"));
+            };
+
+            self.shift()?;
+            return Ok(AExpr::var(name));
+        }
+
+        let Some(functor) = self.functor()? else {
+            return self.error("Expected term");
+        };
+
+        let args = self.args()?;
+
+        Ok(AExpr::fun(self.nc.resolve_static(functor), args))
+    }
+
+    fn functor(&mut self) -> Result<Option<String>, Error> {
+        if let Some(tok) = self.accept(TKind::LowerWord)? {
+            return Ok(Some(tok.text));
+        }
+
+        if let Some(tok) = self.accept(TKind::Quoted)? {
+            return Ok(Some(tok.text));
+        }
+
+        Ok(None)
+    }
+
+    fn args(&mut self) -> Result<Vec<AExpr>, Error> {
+        let mut args = Vec::new();
+
+        if self.accept(TKind::LPar)?.is_some() {
+            loop {
+                args.push(self.term()?);
+
+                if self.accept(TKind::Comma)?.is_none() {
+                    break;
+                }
+            }
+
+            self.expect(TKind::RPar, "RPar")?;
+        }
+
+        Ok(args)
+    }
+}
+
+
+impl ParseContext {
+    /// Parses a TPTP problem in the FOF fragment, that is, a sequence of annotated formulas of the form
+    /// `fof(name, role, formula).`. The supported roles are given by [Role]. Predicates and functions
+    /// share the names of this context, variables must be bound by a quantifier.
+    pub fn tptp<S>(&mut self, input: S) -> Result<Vec<(Role, BExpr)>, Error> where S : Input {
+        Parser::new(input.char_stream(), &mut self.nc)?.problem()
+    }
+
+    pub fn tptp_output<S>(&mut self, input: S) -> Result<super::Output<Vec<(Role, BExpr)>>, Error> where S : Input {
+        self.tptp(input).map(|it| self.with_output(it))
+    }
+}
@@ -87,13 +87,27 @@ impl ExpNode {
     pub fn as_aexpr(self, nc: &mut NameContext) -> ParseResult<AExpr> {
         let range = self.range();
 
+        // Parentheses are not recorded, the term inside already is
+        let par = matches!(self.tree, ExpTree::UnOp(UnOp::Par, _));
+
         let res = match self.tree {
             // ExpTree::Num(val) => AExpr::num(Self::parse_nr(&val, range)?),
 
-            ExpTree::Ident(name) => nc.resolve_bound(&name).map(|it| AExpr::var(it)).unwrap_or_else(|| AExpr::con(nc.resolve_static(name))),
+            ExpTree::Ident(name) => match nc.resolve_bound(&name) {
+                Some(name) => AExpr::var(name),
+                None => {
+                    let name = nc.resolve_static(name);
+                    nc.check_arity(name, 0, range)?;
+                    AExpr::con(name)
+                }
+            },
             ExpTree::Global(name) => AExpr::var(nc.resolve_static(name)),
 
-            ExpTree::Fun(name, args) => AExpr::fun(nc.resolve_static(name), Self::as_aexprs(args, nc)?),
+            ExpTree::Fun(name, args) => {
+                let name = nc.resolve_static(name);
+                nc.check_arity(name, args.len(), range)?;
+                AExpr::fun(name, Self::as_aexprs(args, nc)?)
+            },
 
             // ExpTree::UnOp(UnOp::Neg, rhs) => AExpr::neg(rhs.as_aexpr(nc)?),
             ExpTree::UnOp(UnOp::Par, rhs) => rhs.as_aexpr(nc)?,
@@ -107,6 +121,10 @@ impl ExpNode {
             _ => range.error("Not an arithmetic expression")?,
         };
 
+        if !par {
+            nc.add_term_span(&res, range);
+        }
+
         Ok(res)
     }
 
@@ -154,12 +172,27 @@ impl ExpNode {
             ExpTree::False => BExpr::False,
             ExpTree::True => BExpr::True,
 
-            ExpTree::Ident(name) => BExpr::sym(nc.resolve_static(name)),
-
-            ExpTree::Fun(name, args) => BExpr::pred(nc.resolve_static(name), Self::as_aexprs(args, nc)?),
+            ExpTree::Ident(name) => {
+                let name = nc.resolve_static(name);
+                nc.check_arity(name, 0, range)?;
+                nc.add_pred_span(name, &[], range);
+                BExpr::sym(name)
+            },
+
+            ExpTree::Fun(name, args) => {
+                let name = nc.resolve_static(name);
+                nc.check_arity(name, args.len(), range)?;
+                let args = Self::as_aexprs(args, nc)?;
+                nc.add_pred_span(name, &args, range);
+                BExpr::pred(name, args)
+            },
 
             ExpTree::UnOp(UnOp::Not, rhs) => BExpr::not(rhs.as_bexpr(nc)?),
-            ExpTree::UnOp(UnOp::Par, rhs) => rhs.as_bexpr(nc)?,
+            ExpTree::UnOp(UnOp::Par, rhs) => {
+                let res = rhs.as_bexpr(nc)?;
+                nc.add_group_span(&res, range);
+                res
+            },
 
             // ExpTree::BinOp(BinOp::Eq, lhs, rhs) => BExpr::eq(lhs.as_aexpr(nc)?, rhs.as_aexpr(nc)?),
             // ExpTree::BinOp(BinOp::NEq, lhs, rhs) => BExpr::neq(lhs.as_aexpr(nc)?, rhs.as_aexpr(nc)?),
@@ -168,11 +201,38 @@ impl ExpNode {
             // ExpTree::BinOp(BinOp::LtEq, lhs, rhs) => BExpr::lteq(lhs.as_aexpr(nc)?, rhs.as_aexpr(nc)?),
             // ExpTree::BinOp(BinOp::GtEq, lhs, rhs) => BExpr::gteq(lhs.as_aexpr(nc)?, rhs.as_aexpr(nc)?),
 
-            ExpTree::BinOp(BinOp::And, lhs, rhs) => BExpr::and(lhs.as_bexpr(nc)?, rhs.as_bexpr(nc)?),
-            ExpTree::BinOp(BinOp::Or, lhs, rhs) => BExpr::or(lhs.as_bexpr(nc)?, rhs.as_bexpr(nc)?),
-            ExpTree::BinOp(BinOp::Im, lhs, rhs) => BExpr::im(lhs.as_bexpr(nc)?, rhs.as_bexpr(nc)?),
-            ExpTree::BinOp(BinOp::RevIm, lhs, rhs) => BExpr::revim(lhs.as_bexpr(nc)?, rhs.as_bexpr(nc)?),
-            ExpTree::BinOp(BinOp::Equiv, lhs, rhs) => BExpr::equiv(lhs.as_bexpr(nc)?, rhs.as_bexpr(nc)?),
+            ExpTree::BinOp(op @ (BinOp::And | BinOp::Or | BinOp::Im | BinOp::RevIm | BinOp::Equiv), lhs, rhs) => {
+                // Chains like `P & Q & ...` nest to the left, so their left operands are unfolded in a loop
+                // rather than by recursion, as long chains would otherwise overflow the stack
+                let mut rhss = vec![(op, *rhs)];
+                let mut lhs = *lhs;
+                loop {
+                    match lhs.tree {
+                        ExpTree::BinOp(op @ (BinOp::And | BinOp::Or | BinOp::Im | BinOp::RevIm | BinOp::Equiv), l, r) => {
+                            rhss.push((op, *r));
+                            lhs = *l;
+                        },
+                        tree => {
+                            lhs.tree = tree;
+                            break;
+                        }
+                    }
+                }
+
+                let mut res = lhs.as_bexpr(nc)?;
+                for (op, rhs) in rhss.into_iter().rev() {
+                    let rhs = rhs.as_bexpr(nc)?;
+                    res = match op {
+                        BinOp::And => BExpr::and(res, rhs),
+                        BinOp::Or => BExpr::or(res, rhs),
+                        BinOp::Im => BExpr::im(res, rhs),
+                        BinOp::RevIm => BExpr::revim(res, rhs),
+                        _ => BExpr::equiv(res, rhs),
+                    };
+                }
+
+                res
+            },
 
             ExpTree::Quant(q, names, rhs) => Self::expand_quant(q, names, rhs, nc)?,
 
@@ -308,6 +368,15 @@ impl UnifiableNode {
         let right = ExpNode::as_aexprs(self.right, nc)?;
         Ok((left, right))
     }
+
+    /// Converts this node into two [BExpr]s, the conjunctions of the left and right hand side expressions.
+    pub fn into_equivalence(self, nc: &mut NameContext) -> ParseResult<(BExpr, BExpr)> {
+        let left = ExpNode::as_bexprs(self.left, nc)?;
+        let right = ExpNode::as_bexprs(self.right, nc)?;
+
+        let conj = |exprs: Vec<BExpr>| exprs.into_iter().reduce(BExpr::and).unwrap_or(BExpr::True);
+        Ok((conj(left), conj(right)))
+    }
 }
 
 
@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+use crate::nf::Clause;
+
+/// An event emitted by a [Resolver](super::Resolver) as it makes progress, see
+/// [Resolver::set_event_hook](super::Resolver::set_event_hook).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ResolutionEvent<'a> {
+    /// A candidate clause was taken from the queue, to be learned.
+    Picked(&'a Clause),
+
+    /// A clause was added to the knowledge base. The parents are the two clauses it was resolved from,
+    /// or [None] if the clause is a premise.
+    Learned {
+        clause: &'a Clause,
+        parents: Option<(&'a Clause, &'a Clause)>
+    },
+
+    /// A clause was dropped because it was subsumed by a clause in the knowledge base.
+    Subsumed(&'a Clause),
+
+    /// The empty clause was learned, so a proof was found.
+    Contradiction
+}
+
+/// A hook that receives [ResolutionEvent]s.
+pub(super) struct EventHook(pub Box<dyn FnMut(&ResolutionEvent)>);
+
+impl Debug for EventHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EventHook")
+    }
+}
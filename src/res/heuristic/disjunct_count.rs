@@ -3,12 +3,8 @@ use crate::nf::Clause;
 pub fn disjunct_count(c: &Clause) -> u64 {
     let mut heuristic = 0u64;
 
-    for _ in c.pos().iter_preds() {
-        heuristic += 1;
-    }
-
-    for _ in c.neg().iter_preds() {
-        heuristic += 1;
+    for _ in c.iter_literals() {
+        heuristic = heuristic.saturating_add(1);
     }
     
     heuristic
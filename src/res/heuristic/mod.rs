@@ -1,11 +1,12 @@
-use crate::nf::Clause;
+use crate::nf::{Clause, NormalForm};
+use crate::util::pqueue::{PQueue, Weighted};
 use crate::res::heuristic::disjunct_count::disjunct_count;
 use crate::res::heuristic::symbol_count::symbol_count;
 
 mod symbol_count;
 mod disjunct_count;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum Heuristic {
     /// The most naive heuristic. It does not prioritise any clause more than the other.
     Naive,
@@ -35,6 +36,9 @@ pub enum Heuristic {
 }
 
 impl Heuristic {
+    /// Weighs a clause at the given distance from the premises. A lower weight means a higher priority.
+    /// The weight saturates at [u64::MAX] rather than wrapping around, so that huge clauses are never
+    /// mistaken for light ones.
     pub fn heuristic(&self, clause: &Clause, distance: u64) -> u64 {
         match self {
             Heuristic::Naive => 0,
@@ -42,8 +46,29 @@ impl Heuristic {
             Heuristic::Distance => if clause.is_empty() { 0 } else { distance },
             Heuristic::SymbolCount => symbol_count(clause),
             Heuristic::DisjunctCount => disjunct_count(clause),
-            Heuristic::SymbolCountPlusDistance => symbol_count(clause) + if clause.is_empty() { 0 } else { distance },
-            Heuristic::DisjunctCountPlusDistance => disjunct_count(clause) + if clause.is_empty() { 0 } else { distance },
+            Heuristic::SymbolCountPlusDistance => symbol_count(clause).saturating_add(if clause.is_empty() { 0 } else { distance }),
+            Heuristic::DisjunctCountPlusDistance => disjunct_count(clause).saturating_add(if clause.is_empty() { 0 } else { distance }),
         }
     }
 }
+
+/// A [Clause] along with the [Heuristic] to weigh it with, so that it can be put in a [PQueue] with
+/// [PQueue::insert_elem]. The clause is weighed as a premise, with distance 0.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct WeightedClause(pub Clause, pub Heuristic);
+
+impl Weighted<u64> for WeightedClause {
+    fn weight(&self) -> u64 {
+        self.1.heuristic(&self.0, 0)
+    }
+}
+
+impl NormalForm {
+    /// Iterates the clauses of this normal form in the order of the given [Heuristic], that is, in
+    /// ascending order of weight. Each clause is weighed as a premise, with distance 0, and yielded
+    /// along with its weight. Clauses of equal weight are yielded in no particular order.
+    pub fn iter_by_heuristic<'a>(&'a self, h: &Heuristic) -> impl Iterator<Item = (&'a Clause, u64)> {
+        let mut queue = PQueue::assoc(self.clauses(), |clause| h.heuristic(clause, 0));
+        std::iter::from_fn(move || queue.poll())
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use crate::nf::{Atom, Clause, NormalForm};
+use crate::uni::Unifier;
+
+use super::{Deduction, Proof, Resolvee};
+
+impl NormalForm {
+    /// Solves this CNF using unit propagation, if it is a ground [Horn][NormalForm::is_horn] CNF.
+    /// Returns [None] if this CNF is not Horn or not ground, in which case full resolution is needed.
+    ///
+    /// Atoms are derived by forward chaining: a clause whose negative atoms have all been derived
+    /// derives its positive atom by successive unit resolution steps. If a clause without a positive
+    /// atom has all its negative atoms derived, the empty clause is derived and the CNF is refuted.
+    /// Otherwise, assigning `true` to all derived atoms and `false` to all other atoms satisfies the
+    /// CNF. This runs in time linear in the size of the CNF, up to logarithmic factors.
+    ///
+    /// The returned [Proof::Proven] contains the unit resolution proof. A [Proof::Disproven] has a
+    /// model if all predicates are nullary.
+    pub fn solve_horn(&self) -> Option<Proof> {
+        if !self.is_horn() || !self.is_ground() {
+            return None;
+        }
+
+        let clauses: Vec<&Clause> = self.clauses().iter().collect();
+
+        let mut proof = Vec::new();
+        for clause in &clauses {
+            proof.push(Deduction::Premise {
+                clause: (*clause).clone()
+            });
+        }
+
+        // For each clause, the amount of negative atoms not derived yet
+        let mut remaining = Vec::new();
+
+        // For each atom, the clauses it appears negatively in
+        let mut watches: BTreeMap<Atom, Vec<usize>> = BTreeMap::new();
+
+        let mut ready = VecDeque::new();
+
+        for (i, clause) in clauses.iter().enumerate() {
+            let neg = atoms(clause, true);
+
+            for atom in &neg {
+                watches.entry(atom.clone()).or_default().push(i);
+            }
+
+            if neg.is_empty() {
+                ready.push_back(i);
+            }
+
+            remaining.push(neg.len());
+        }
+
+        // The line of the unit clause of each derived atom
+        let mut derived: BTreeMap<Atom, usize> = BTreeMap::new();
+
+        while let Some(i) = ready.pop_front() {
+            let clause = clauses[i];
+            let head = atoms(clause, false).pop();
+
+            if let Some(head) = &head && derived.contains_key(head) {
+                continue;
+            }
+
+            // Resolve away the negative atoms one by one
+            let mut current = clause.clone();
+            let mut line = i;
+
+            for atom in atoms(clause, true) {
+                current.remove_neg(&atom);
+
+                proof.push(Deduction::Resolve {
+                    clause: current.clone(),
+                    a_line: line,
+                    b_line: derived[&atom],
+                    resolvee: Resolvee {
+                        a: atom.clone(),
+                        b: atom,
+                        a_neg: true,
+                        b_neg: false,
+                        mgu: Unifier::new()
+                    }
+                });
+
+                line = proof.len() - 1;
+            }
+
+            let Some(head) = head else {
+                proof.push(Deduction::QED {
+                    line_with_bottom: line
+                });
+
+                return Some(Proof::Proven(proof));
+            };
+
+            if let Some(watching) = watches.get(&head) {
+                for &j in watching {
+                    remaining[j] -= 1;
+
+                    if remaining[j] == 0 {
+                        ready.push_back(j);
+                    }
+                }
+            }
+
+            derived.insert(head, line);
+        }
+
+        let mut model = Some(BTreeMap::new());
+
+        for clause in &clauses {
+            for (name, args, _) in clause.iter_literals() {
+                if !args.is_empty() {
+                    model = None;
+                    break;
+                }
+
+                if let Some(model) = &mut model {
+                    model.insert(name, derived.contains_key(&Atom::Pred(name, vec![])));
+                }
+            }
+        }
+
+        Some(Proof::Disproven {
+            model
+        })
+    }
+}
+
+fn atoms(clause: &Clause, neg: bool) -> Vec<Atom> {
+    let index = if neg { clause.neg() } else { clause.pos() };
+    index.iter_preds().map(|(name, args)| Atom::Pred(name, args.clone())).collect()
+}
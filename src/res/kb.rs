@@ -124,6 +124,33 @@ impl KnowledgeBase {
         Some(new_candidates)
     }
 
+    /// Returns all clauses in this knowledge base.
+    pub fn clauses(&self) -> impl Iterator<Item = &Clause> {
+        self.clauses.iter().map(|it| it.as_ref())
+    }
+
+    /// Looks up the clauses in this knowledge base that can possibly resolve with the given clause,
+    /// that is, the clauses that use a predicate in the complementary form of how the given clause
+    /// uses it. The lookup goes through the predicate names of the given clause, so it does not scan
+    /// the whole knowledge base. The given clause itself is not included.
+    pub fn partners(&self, clause: &Clause) -> BTreeSet<Rc<Clause>> {
+        let mut partners = RcClauses::new();
+
+        for name in clause.pos().iter_pred_names() {
+            if let Some(set) = self.by_neg.get(name) {
+                partners.extend(set.iter().filter(|it| ***it != *clause).cloned());
+            }
+        }
+
+        for name in clause.neg().iter_pred_names() {
+            if let Some(set) = self.by_pos.get(name) {
+                partners.extend(set.iter().filter(|it| ***it != *clause).cloned());
+            }
+        }
+
+        partners
+    }
+
     /// Resolves a set of resolution candidates. These candidates are added to the set `out`.
     pub fn resolution_candidates(&self, out: &mut BTreeSet<(Rc<Clause>, Rc<Clause>)>) {
         for cand in self.candidates.iter() {
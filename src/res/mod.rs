@@ -3,9 +3,23 @@ mod resolvee;
 mod resolver;
 mod heuristic;
 mod proof;
+mod mgu_cache;
+mod model;
+mod horn;
+mod selection;
+mod event;
+mod trivial;
+mod result;
+mod options;
 
 pub use resolvee::*;
 pub use kb::*;
 pub use resolver::*;
 pub use heuristic::*;
-pub use proof::*;
\ No newline at end of file
+pub use proof::*;
+pub use mgu_cache::*;
+pub use model::*;
+pub use selection::*;
+pub use result::*;
+pub use options::*;
+pub use event::ResolutionEvent;
\ No newline at end of file
@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::expr::Name;
+use crate::nf::Clause;
+
+/// A propositional clause, that is, the set of nullary predicates used positively
+/// and the set of nullary predicates used negatively.
+type PropClause = (BTreeSet<Name>, BTreeSet<Name>);
+
+/// Finds a model of the given set of [Clause]s, that is, a truth assignment of predicates under which
+/// every clause is satisfied. Models can only be found for propositional clauses, i.e. clauses that
+/// only use nullary predicates. If any of the clauses uses a predicate with arguments, or if the clauses
+/// are unsatisfiable, this returns [None].
+/// 
+/// The model is found using the DPLL algorithm: units are propagated until no units are left, after which
+/// the algorithm branches on the truth value of an unassigned predicate. Predicates that do not influence
+/// satisfiability are assigned `false`.
+pub fn find_model<'a, I>(clauses: I) -> Option<BTreeMap<Name, bool>> where I : IntoIterator<Item = &'a Clause> {
+    let mut names = BTreeSet::new();
+    let mut props = Vec::new();
+
+    for clause in clauses {
+        let mut pos = BTreeSet::new();
+        let mut neg = BTreeSet::new();
+
+        for (name, args) in clause.pos().iter_preds() {
+            if !args.is_empty() {
+                return None;
+            }
+
+            pos.insert(name);
+        }
+
+        for (name, args) in clause.neg().iter_preds() {
+            if !args.is_empty() {
+                return None;
+            }
+
+            neg.insert(name);
+        }
+
+        names.extend(pos.iter().copied());
+        names.extend(neg.iter().copied());
+        props.push((pos, neg));
+    }
+
+    let mut model = BTreeMap::new();
+    if !dpll(&props, &mut model) {
+        return None;
+    }
+
+    for name in names {
+        model.entry(name).or_insert(false);
+    }
+
+    Some(model)
+}
+
+/// The unassigned literals of a clause, or [None] if the clause is already satisfied.
+fn unassigned(clause: &PropClause, model: &BTreeMap<Name, bool>) -> Option<Vec<(Name, bool)>> {
+    let (pos, neg) = clause;
+    let mut lits = Vec::new();
+
+    for (names, value) in [(pos, true), (neg, false)] {
+        for name in names {
+            match model.get(name) {
+                Some(v) if *v == value => return None,
+                Some(_) => {},
+                None => lits.push((*name, value))
+            }
+        }
+    }
+
+    Some(lits)
+}
+
+fn dpll(clauses: &[PropClause], model: &mut BTreeMap<Name, bool>) -> bool {
+    // Unit propagation
+    let mut branch = None;
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+        branch = None;
+
+        for clause in clauses {
+            let Some(lits) = unassigned(clause, model) else {
+                continue;
+            };
+
+            match lits.as_slice() {
+                // All literals are false, conflict
+                [] => return false,
+
+                [(name, value)] => {
+                    model.insert(*name, *value);
+                    changed = true;
+                },
+
+                [lit, ..] => {
+                    branch.get_or_insert(*lit);
+                }
+            }
+        }
+    }
+
+    // No unsatisfied clauses left
+    let Some((name, value)) = branch else {
+        return true;
+    };
+
+    for value in [value, !value] {
+        let mut attempt = model.clone();
+        attempt.insert(name, value);
+
+        if dpll(clauses, &mut attempt) {
+            *model = attempt;
+            return true;
+        }
+    }
+
+    false
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use crate::nf::NormalForm;
+
+use super::{Heuristic, ProofResult, Resolver};
+
+/// The settings of a resolution run by [resolve_nf]. Each setting corresponds to a setter of [Resolver],
+/// and the defaults are those of [Resolver::new], so by default resolution is not bounded.
+#[derive(Clone, Debug)]
+pub struct ResolverOptions {
+    /// The heuristic, see [Resolver::set_heuristic].
+    pub heuristic: Heuristic,
+
+    /// The maximum amount of resolution steps, if any.
+    pub max_steps: Option<usize>,
+
+    /// The maximum amount of time to resolve, if any.
+    pub timeout: Option<Duration>,
+
+    /// The maximum amount of clauses, see [Resolver::set_max_clauses].
+    pub max_clauses: Option<usize>,
+
+    /// The maximum depth of terms in resolvents, see [Resolver::set_max_term_depth].
+    pub max_term_depth: Option<usize>,
+
+    /// The window of the watchdog, see [Resolver::set_stall_window]. When set, resolution stops
+    /// once the resolver stalls.
+    pub stall_window: Option<usize>
+}
+
+impl Default for ResolverOptions {
+    fn default() -> Self {
+        Self {
+            heuristic: Heuristic::SymbolCount,
+            max_steps: None,
+            timeout: None,
+            max_clauses: None,
+            max_term_depth: None,
+            stall_window: None
+        }
+    }
+}
+
+impl ResolverOptions {
+    /// Creates a [Resolver] with these options.
+    pub fn resolver(&self) -> Resolver {
+        let mut resolver = Resolver::new();
+        resolver.set_heuristic(self.heuristic);
+        resolver.set_max_clauses(self.max_clauses);
+        resolver.set_max_term_depth(self.max_term_depth);
+        resolver.set_stall_window(self.stall_window);
+        resolver.should_stop_when_stalled(true);
+        resolver
+    }
+}
+
+/// Attempts to refute the clauses of the given CNF by resolution, without going through a [BExpr](crate::expr::BExpr).
+/// The result is [ProofResult::Proven] when the empty clause was derived, [ProofResult::Exhausted] when all
+/// resolvents were derived without finding it, and [ProofResult::Undecided] when resolution was cut short by
+/// the given options, including when resolvents were discarded for exceeding the maximum term depth or
+/// the clause limit. Since only the outcome is returned, no proof is derived.
+///
+/// Without a step limit, timeout or stall window, this may not terminate.
+pub fn resolve_nf(nf: &NormalForm, opts: ResolverOptions) -> ProofResult {
+    let mut resolver = opts.resolver();
+    resolver.should_skip_proof_derivation(true);
+    resolver.assume_cnf(nf.clone());
+
+    let deadline = opts.timeout.map(|it| Instant::now() + it);
+    let result = match (opts.max_steps, deadline) {
+        (None, None) if opts.stall_window.is_some() => resolver.step_n_times(usize::MAX),
+        (None, None) => Some(resolver.step_indefinitely()),
+        (None, Some(deadline)) => resolver.step_until(deadline),
+        (Some(n), None) => resolver.step_n_times(n),
+        (Some(n), Some(deadline)) => resolver.step_n_times_until(n, deadline),
+    };
+
+    ProofResult::new(result.as_ref(), resolver.stats(), false)
+}
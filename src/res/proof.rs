@@ -1,4 +1,7 @@
-use crate::fmt::DisplayNamed;
+use std::collections::BTreeMap;
+
+use crate::expr::{AExpr, Name};
+use crate::fmt::{DisplayNamed, NameEntry, NameTable};
 use crate::nf::Clause;
 use crate::res::Resolvee;
 
@@ -6,7 +9,111 @@ use crate::res::Resolvee;
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub enum Proof {
     Proven(Vec<Deduction>),
-    Disproven
+    Disproven {
+        /// A truth assignment satisfying all premises, if requested and if one could be
+        /// found. See [Resolver::should_build_model](crate::res::Resolver::should_build_model).
+        model: Option<BTreeMap<Name, bool>>
+    },
+
+    /// Resolution ran out of candidates without deriving the empty clause, but candidates were
+    /// discarded along the way, see [ResolverStats::discarded_by_depth](crate::res::ResolverStats::discarded_by_depth)
+    /// and [ResolverStats::discarded_by_clause_limit](crate::res::ResolverStats::discarded_by_clause_limit).
+    /// The discarded candidates may have led to a proof, so the statement is neither proven nor disproven.
+    Undecided
+}
+
+impl Proof {
+    /// Serializes this proof in TSTP derivation syntax, so that it can be checked by third-party TSTP
+    /// tools. Each deduction becomes a `cnf` line named `c<line>`, where premises get role `axiom` and
+    /// resolvents get role `plain` with an `inference(resolution, ...)` record referring to their parents.
+    /// The derivation is wrapped in SZS status lines. A [Proof::Disproven] only yields a status line.
+    pub fn to_tstp(&self, names: &NameTable) -> String {
+        let deductions = match self {
+            Proof::Proven(deductions) => deductions,
+            Proof::Disproven { .. } => return "% SZS status Satisfiable\n".to_string(),
+            Proof::Undecided => return "% SZS status GaveUp\n".to_string(),
+        };
+
+        let mut out = String::new();
+        out.push_str("% SZS status Unsatisfiable\n");
+        out.push_str("% SZS output start CNFRefutation\n");
+
+        for (line, deduction) in deductions.iter().enumerate() {
+            match deduction {
+                Deduction::Premise { clause } => {
+                    out.push_str(&format!("cnf(c{line}, axiom, {}).\n", tstp_clause(clause, names)));
+                },
+
+                Deduction::Resolve { clause, a_line, b_line, .. } => {
+                    out.push_str(&format!(
+                        "cnf(c{line}, plain, {}, inference(resolution, [status(thm)], [c{a_line}, c{b_line}])).\n",
+                        tstp_clause(clause, names)
+                    ));
+                },
+
+                Deduction::Magic { clause } => {
+                    out.push_str(&format!("cnf(c{line}, plain, {}, introduced(unknown)).\n", tstp_clause(clause, names)));
+                },
+
+                Deduction::QED { .. } => {}
+            }
+        }
+
+        out.push_str("% SZS output end CNFRefutation\n");
+        out
+    }
+}
+
+/// Formats a functor as a TPTP atomic word, quoting it if it is not a lowercase word.
+fn tstp_functor(name: &Name, names: &NameTable) -> String {
+    let id = match names.entry(name) {
+        NameEntry::Unbound(id) => id.clone(),
+        entry => entry.write(name)
+    };
+
+    let mut chars = id.chars();
+    let lower_word = matches!(chars.next(), Some('a'..='z'))
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if lower_word {
+        id
+    } else {
+        format!("'{}'", id.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+/// Formats a term in TPTP syntax. Variables are named `X<name>`, since TPTP requires variables to be
+/// uppercase words.
+fn tstp_term(expr: &AExpr, names: &NameTable) -> String {
+    match expr {
+        AExpr::Var(name) => format!("X{name}"),
+        AExpr::Fun(name, args) => tstp_application(name, args, names)
+    }
+}
+
+fn tstp_application(name: &Name, args: &[AExpr], names: &NameTable) -> String {
+    let functor = tstp_functor(name, names);
+
+    if args.is_empty() {
+        return functor;
+    }
+
+    let args: Vec<_> = args.iter().map(|it| tstp_term(it, names)).collect();
+    format!("{functor}({})", args.join(", "))
+}
+
+/// Formats a clause in TPTP syntax, the empty clause being `$false`.
+fn tstp_clause(clause: &Clause, names: &NameTable) -> String {
+    let literals: Vec<_> = clause.iter_literals().map(|(name, args, neg)| {
+        let application = tstp_application(&name, args, names);
+        if neg { format!("~{application}") } else { application }
+    }).collect();
+
+    if literals.is_empty() {
+        return "$false".to_string();
+    }
+
+    format!("({})", literals.join(" | "))
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
@@ -1,6 +1,7 @@
-use crate::expr::Name;
-use crate::nf::{Atom, Clause, PredicateIndex};
-use crate::uni::Unifier;
+use crate::expr::{AExpr, Name};
+use crate::nf::{Atom, Clause, NormalForm, PredicateIndex};
+use crate::res::MguCache;
+use crate::uni::{Unifiable, Unifier};
 
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
@@ -14,7 +15,7 @@ pub struct Resolvee {
 
 
 impl Resolvee {
-    fn find_by_name(a: &PredicateIndex, b: &PredicateIndex, a_neg: bool, name: Name) -> Option<Vec<Self>> {
+    fn find_by_name(a: &PredicateIndex, b: &PredicateIndex, a_neg: bool, name: Name, cache: &mut Option<&mut MguCache>) -> Option<Vec<Self>> {
         let a_preds = a.get_preds(&name)?;
         let b_preds = b.get_preds(&name)?;
         
@@ -22,10 +23,18 @@ impl Resolvee {
 
         for a_pred in a_preds {
             for b_pred in b_preds {
-                if let Some(mgu) = Unifier::mgu(a_pred, b_pred) {
+                let a_atom = Atom::Pred(name, a_pred.clone());
+                let b_atom = Atom::Pred(name, b_pred.clone());
+
+                let mgu = match cache {
+                    Some(cache) => cache.mgu(&a_atom, &b_atom),
+                    None => Unifier::mgu(a_pred, b_pred)
+                };
+
+                if let Some(mgu) = mgu {
                     out.push(Self {
-                        a: Atom::Pred(name, a_pred.clone()),
-                        b: Atom::Pred(name, b_pred.clone()),
+                        a: a_atom,
+                        b: b_atom,
                         a_neg, b_neg: !a_neg,
                         mgu
                     });
@@ -36,12 +45,12 @@ impl Resolvee {
         Some(out)
     }
 
-    fn find_index(a: &PredicateIndex, b: &PredicateIndex, a_neg: bool) -> Vec<Self> {
+    fn find_index(a: &PredicateIndex, b: &PredicateIndex, a_neg: bool, cache: &mut Option<&mut MguCache>) -> Vec<Self> {
         let mut resolvees = Vec::new();
 
         for name in a.iter_pred_names() {
             if b.contains_preds(name) {
-                if let Some(mut elem) = Self::find_by_name(&a, &b, a_neg, *name) {
+                if let Some(mut elem) = Self::find_by_name(a, b, a_neg, *name, cache) {
                     resolvees.append(&mut elem);
                 }
             }
@@ -53,9 +62,130 @@ impl Resolvee {
     pub fn find(a: &Clause, b: &Clause) -> Vec<Self> {
         let mut v = Vec::new();
 
-        v.append(&mut Self::find_index(a.pos(), b.neg(), false));
-        v.append(&mut Self::find_index(a.neg(), b.pos(), true));
+        v.append(&mut Self::find_index(a.pos(), b.neg(), false, &mut None));
+        v.append(&mut Self::find_index(a.neg(), b.pos(), true, &mut None));
+
+        v
+    }
+
+    /// Same as [Resolvee::find], but only finds resolvees on predicates with the given name. That is,
+    /// it only pairs the positive occurrences of the predicate in `a` with the negative occurrences in
+    /// `b`, and vice versa.
+    pub fn find_by_pred(a: &Clause, b: &Clause, name: Name) -> Vec<Self> {
+        let mut v = Vec::new();
+
+        v.append(&mut Self::find_by_name(a.pos(), b.neg(), false, name, &mut None).unwrap_or_default());
+        v.append(&mut Self::find_by_name(a.neg(), b.pos(), true, name, &mut None).unwrap_or_default());
+
+        v
+    }
+
+    /// Same as [Resolvee::find], but consults the given [MguCache] before computing any MGU.
+    pub fn find_cached(a: &Clause, b: &Clause, cache: &mut MguCache) -> Vec<Self> {
+        let mut v = Vec::new();
+        let mut cache = Some(cache);
+
+        v.append(&mut Self::find_index(a.pos(), b.neg(), false, &mut cache));
+        v.append(&mut Self::find_index(a.neg(), b.pos(), true, &mut cache));
 
         v
     }
+
+    /// Computes the resolvent of the two given clauses over this resolvee. That is, it removes the
+    /// resolved atoms from both clauses, concatenates the remainders and unifies the result with the
+    /// MGU of this resolvee. The clauses must be the clauses this resolvee was [found][Resolvee::find]
+    /// from, in the same order.
+    /// 
+    /// The parent clauses are borrowed, only the atoms that end up in the resolvent are copied.
+    pub fn resolvent(&self, a: &Clause, b: &Clause) -> Clause {
+        self.remainder(a, b).unify(&self.mgu)
+    }
+
+    /// Same as [Resolvee::resolvent], but returns [None] if the resolvent is a tautology, see
+    /// [Clause::apply_and_simplify].
+    pub fn simplified_resolvent(&self, a: &Clause, b: &Clause) -> Option<Clause> {
+        self.remainder(a, b).apply_and_simplify(&self.mgu)
+    }
+
+    /// Concatenates the two given clauses without the resolved atoms, before unification.
+    fn remainder(&self, a: &Clause, b: &Clause) -> Clause {
+        let mut result = Clause::new();
+
+        add_remainder(&mut result, a, &self.a, self.a_neg);
+        add_remainder(&mut result, b, &self.b, self.b_neg);
+
+        result
+    }
+}
+
+
+/// Adds the atoms of the given clause to the result, except the resolved atom.
+fn add_remainder(result: &mut Clause, clause: &Clause, resolved: &Atom, resolved_neg: bool) {
+    for (name, args) in clause.pos().iter_preds() {
+        if resolved_neg || !is_atom(resolved, name, args) {
+            result.add_pos(Atom::Pred(name, args.clone()));
+        }
+    }
+
+    for (name, args) in clause.neg().iter_preds() {
+        if !resolved_neg || !is_atom(resolved, name, args) {
+            result.add_neg(Atom::Pred(name, args.clone()));
+        }
+    }
+}
+
+fn is_atom(atom: &Atom, name: Name, args: &Vec<AExpr>) -> bool {
+    match atom {
+        Atom::Pred(n, a) => *n == name && a == args
+    }
+}
+
+
+/// Computes all binary resolvents of two clauses. For each atom in `a` that appears in complementary
+/// form in `b` with the same predicate name, it attempts to find an MGU, and if it exists, the
+/// resolvent is formed as by [Resolvee::resolvent]. Each resolvent is returned along with the MGU
+/// that was used to obtain it.
+/// 
+/// Note that resolvents which are tautologies are not filtered out.
+pub fn resolve(a: &Clause, b: &Clause) -> Vec<(Clause, Unifier)> {
+    Resolvee::find(a, b)
+        .into_iter()
+        .map(|resolvee| (resolvee.resolvent(a, b), resolvee.mgu))
+        .collect()
+}
+
+impl Clause {
+    /// Computes the binary resolvents of this clause and `other` on predicates with the given name, like
+    /// [resolve] but without considering the literals of other predicates. This is useful when an index
+    /// already determined which predicate connects the two clauses. Each resolvent is returned along with
+    /// the MGU that was used to obtain it.
+    /// 
+    /// Unlike [resolve], resolvents that are tautologies are filtered out, see [Clause::is_disjoint].
+    pub fn resolvents_on(&self, other: &Clause, name: Name) -> Vec<(Clause, Unifier)> {
+        Resolvee::find_by_pred(self, other, name)
+            .into_iter()
+            .map(|resolvee| (resolvee.resolvent(self, other), resolvee.mgu))
+            .filter(|(resolvent, _)| resolvent.is_disjoint())
+            .collect()
+    }
+}
+
+impl NormalForm {
+    /// Lazily enumerates all binary resolvents between distinct clauses of this CNF, along with the
+    /// MGU that was used to obtain them, as by [resolve]. Each unordered pair of clauses is visited
+    /// once, and resolvents that are tautologies are filtered out. This performs a single resolution
+    /// step on every pair without learning anything, which allows to build other search strategies
+    /// on top of it. Pairs are only resolved as the iterator advances, so callers can stop early.
+    pub fn all_resolvents(&self) -> impl Iterator<Item = (Clause, Unifier)> + '_ {
+        let clauses = self.clauses();
+
+        clauses.iter().enumerate().flat_map(move |(i, a)| {
+            clauses.iter().skip(i + 1).flat_map(move |b| {
+                Resolvee::find(a, b).into_iter().filter_map(move |resolvee| {
+                    let resolvent = resolvee.simplified_resolvent(a, b)?;
+                    Some((resolvent, resolvee.mgu))
+                })
+            })
+        })
+    }
 }
@@ -1,20 +1,42 @@
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::rc::Rc;
+use std::time::Instant;
 
+use crate::expr::Name;
 use crate::nf::Clause;
+use crate::nf::Clauses;
 use crate::nf::NormalForm;
+use crate::nf::PredicateIndex;
 use crate::res::Heuristic;
 use crate::res::Deduction;
+use crate::res::event::EventHook;
 use crate::res::KnowledgeBase;
+use crate::res::MguCache;
+use crate::res::find_model;
 use crate::res::Proof;
+use crate::res::ProofResult;
+use crate::res::ResolutionEvent;
 use crate::res::Resolvee;
-use crate::uni::Unifiable;
+use crate::res::SelectionFunction;
 use crate::util::pqueue::PQueue;
 use crate::util::pqueue::Weighted;
 
 
-#[derive(Debug)]
+/// The amount of resolution steps between two deadline checks in [Resolver::step_until].
+const DEADLINE_CHECK_INTERVAL: usize = 64;
+
+/// The minimum amount of candidate pairs to resolve in parallel, with the `parallel` feature. Fewer
+/// pairs are resolved sequentially, as the parallelisation overhead outweighs the gain.
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 32;
+
+/// The resolvents of a pair of clauses, each with the resolvee it was obtained from, as computed in
+/// parallel with the `parallel` feature. It is [None] when the pair was skipped.
+#[cfg(feature = "parallel")]
+type PairResolvents = Option<Vec<(Resolvee, Option<Clause>)>>;
+
+#[derive(Debug, Clone)]
 struct Candidate {
     // These three fields are only used in proofbuilding
     a: Rc<Clause>,
@@ -24,12 +46,17 @@ struct Candidate {
     // Result and heuristic are important for resolution
     result: Rc<Clause>,
     depth: u64,
-    heuristic: u64
+    heuristic: u64,
+
+    // The order in which candidates were inserted, to break ties between equal heuristics
+    sequence: u64
 }
 
-impl Weighted<u64> for Candidate {
-    fn weight(&self) -> u64 {
-        self.heuristic
+impl Weighted<(u64, u64)> for Candidate {
+    /// Candidates with the same heuristic are ordered by age, so that the order in which candidates
+    /// come out of the queue does not depend on the structure of the heap.
+    fn weight(&self) -> (u64, u64) {
+        (self.heuristic, self.sequence)
     }
 }
 
@@ -56,7 +83,57 @@ pub struct ResolverStats {
     pub deductions_made: usize,
 
     /// A list of all deductions made.
-    pub learning_order: Vec<Clause>
+    pub learning_order: Vec<Clause>,
+
+    /// The amount of resolvents discarded because they exceeded the maximum term depth, see
+    /// [Resolver::set_max_term_depth].
+    pub discarded_by_depth: usize,
+
+    /// The amount of candidates discarded because the resolver held more clauses than allowed, see
+    /// [Resolver::set_max_clauses]. When this is not 0, exhausting the search space no longer means
+    /// there is no proof.
+    pub discarded_by_clause_limit: usize,
+
+    /// The amount of resolvents produced, including the ones that were discarded.
+    pub resolvents_produced: usize,
+
+    /// The length of the longest resolvent produced that is not a tautology, see [Clause::len].
+    pub max_clause_len: usize,
+
+    /// The sum of the lengths of all resolvents produced that are not tautologies, see
+    /// [ResolverStats::avg_clause_len].
+    pub total_clause_len: usize,
+
+    /// The amount of resolvents discarded because they were tautologies.
+    pub tautologies_discarded: usize,
+
+    /// The amount of clauses dropped because they were subsumed by a clause in the knowledge base.
+    /// Currently, only clauses that are identical to a known clause up to the names of variables are
+    /// detected as subsumed, see [Clause::canonicalize].
+    pub subsumed_dropped: usize,
+
+    /// The largest amount of candidates that were queued at once.
+    pub peak_frontier: usize,
+
+    /// The amount of MGUs that were attempted to be computed.
+    pub mgu_attempts: usize,
+
+    /// The amount of MGUs that were found.
+    pub mgu_successes: usize
+}
+
+impl ResolverStats {
+    /// The average length of the resolvents produced that are not tautologies, or 0 if no such
+    /// resolvents were produced.
+    pub fn avg_clause_len(&self) -> f64 {
+        let count = self.resolvents_produced - self.tautologies_discarded;
+
+        if count == 0 {
+            0.0
+        } else {
+            self.total_clause_len as f64 / count as f64
+        }
+    }
 }
 
 /// The ecosystem of a resolution-based prover is all encapsulated in the [Resolver] struct.
@@ -71,12 +148,75 @@ pub struct Resolver {
     /// Whether to skip proof derivation
     skip_proof_derivation: bool,
 
+    /// Whether to build a model when no proof is found
+    build_model: bool,
+
+    /// The maximum depth of terms in resolvents, if any
+    max_term_depth: Option<usize>,
+
+    /// Amount of resolvents discarded due to the maximum term depth
+    discarded_by_depth: usize,
+
+    /// The maximum amount of learned and queued clauses, if any
+    max_clauses: Option<usize>,
+
+    /// Amount of candidates discarded due to the maximum amount of clauses
+    discarded_by_clause_limit: usize,
+
+    /// The amount of picks without progress after which the resolver is stalled, if any
+    stall_window: Option<usize>,
+
+    /// Whether the bounded step functions stop when the resolver is stalled
+    stop_when_stalled: bool,
+
+    /// The weight of the pick and the size of the queue when progress was last made
+    stall_reference: Option<(u64, usize)>,
+
+    /// Amount of picks since progress was last made
+    picks_without_progress: usize,
+
+    /// Whether the resolver has stalled
+    stalled: bool,
+
+    /// Amount of resolvents produced
+    resolvents_produced: usize,
+
+    /// Length of the longest resolvent produced
+    max_clause_len: usize,
+
+    /// Sum of the lengths of all resolvents produced
+    total_clause_len: usize,
+
+    /// Amount of tautologies discarded
+    tautologies_discarded: usize,
+
+    /// Amount of clauses dropped because they were already known
+    subsumed_dropped: usize,
+
+    /// The canonical forms of all learned clauses
+    canonical: BTreeSet<Clause>,
+
+    /// Largest size of the queue
+    peak_frontier: usize,
+
+    /// Amount of MGUs attempted
+    mgu_attempts: usize,
+
+    /// Amount of MGUs found
+    mgu_successes: usize,
+
     /// The queue of candidates
-    queue: PQueue<Candidate, u64>,
+    queue: PQueue<Candidate, (u64, u64)>,
+
+    /// The sequence number of the next candidate inserted into the queue
+    next_sequence: u64,
 
     /// Whether the empty clause has been learned
     empty_clause: Option<Rc<Clause>>,
 
+    /// A candidate whose resolvent is the empty clause, which is picked before any queued candidate
+    empty_candidate: Option<Candidate>,
+
     /// Premises
     premises: Vec<Rc<Clause>>,
 
@@ -94,6 +234,15 @@ pub struct Resolver {
 
     /// Whether the prover is done.
     done: bool,
+
+    /// The MGU cache, if enabled
+    mgu_cache: Option<MguCache>,
+
+    /// The selection function, if any
+    selection: Option<Rc<dyn SelectionFunction>>,
+
+    /// The event hook, if any
+    hook: Option<EventHook>,
 }
 
 impl Resolver {
@@ -102,17 +251,53 @@ impl Resolver {
             kb: KnowledgeBase::new(),
             heuristic: Heuristic::SymbolCount,
             skip_proof_derivation: false,
+            build_model: false,
+            max_term_depth: None,
+            discarded_by_depth: 0,
+            max_clauses: None,
+            discarded_by_clause_limit: 0,
+            stall_window: None,
+            stop_when_stalled: false,
+            stall_reference: None,
+            picks_without_progress: 0,
+            stalled: false,
+            resolvents_produced: 0,
+            max_clause_len: 0,
+            total_clause_len: 0,
+            tautologies_discarded: 0,
+            subsumed_dropped: 0,
+            canonical: BTreeSet::new(),
+            peak_frontier: 0,
+            mgu_attempts: 0,
+            mgu_successes: 0,
             queue: PQueue::new(),
+            next_sequence: 0,
             empty_clause: None,
+            empty_candidate: None,
             premises: Vec::new(),
             deduced: BTreeMap::new(),
             depths: BTreeMap::new(),
             deductions_made: 0,
             learning_order: Vec::new(),
-            done: false
+            done: false,
+            mgu_cache: None,
+            selection: None,
+            hook: None
         }
     }
 
+    /// Creates a new resolver that caches the MGUs it computes in an [MguCache].
+    pub fn with_mgu_cache() -> Self {
+        let mut new = Self::new();
+        new.mgu_cache = Some(MguCache::new());
+        new
+    }
+
+    /// Borrows the [MguCache] of this resolver, if it has one. See [Resolver::with_mgu_cache].
+    pub fn mgu_cache(&self) -> Option<&MguCache> {
+        self.mgu_cache.as_ref()
+    }
+
     /// Sets the heuristic used by the resolver.
     pub fn set_heuristic(&mut self, heuristic: Heuristic) {
         self.queue.reassoc_modify(|mut e| {
@@ -127,14 +312,131 @@ impl Resolver {
         self.skip_proof_derivation = skip;
     }
 
-    /// Assumes a premise.
+    /// Sets whether to build a model when resolution is exhausted without finding the empty clause.
+    /// Models can only be built for propositional clauses, see [find_model]. Since building a model
+    /// adds cost, this is disabled by default.
+    pub fn should_build_model(&mut self, build: bool) {
+        self.build_model = build;
+    }
+
+    /// Sets the maximum depth of terms in resolvents, see [Clause::depth]. Resolvents with deeper
+    /// terms are discarded. This keeps the search space finite, at the cost of completeness: when
+    /// resolvents were discarded, exhausting the search space no longer means there is no proof, and
+    /// the resolver yields [Proof::Undecided]. Premises are never discarded. By default, there is no maximum.
+    pub fn set_max_term_depth(&mut self, max: Option<usize>) {
+        self.max_term_depth = max;
+    }
+
+    /// Sets the maximum amount of clauses the resolver holds at once, counting both the learned clauses
+    /// and the queued candidates. When a learned clause brings in so many candidates that this amount is
+    /// exceeded, the heaviest candidates according to the [Heuristic] are discarded. This bounds the
+    /// memory used by the resolver, at the cost of completeness: when candidates were discarded, see
+    /// [ResolverStats::discarded_by_clause_limit], exhausting the search space no longer means there is
+    /// no proof, and the resolver yields [Proof::Undecided]. Premises are never discarded. By default,
+    /// there is no maximum.
+    pub fn set_max_clauses(&mut self, max: Option<usize>) {
+        self.max_clauses = max;
+    }
+
+    /// Sets the window of a watchdog that detects when resolution makes no progress, which is typical for
+    /// first-order inputs on which resolution does not terminate. Since candidates are picked lightest
+    /// first, picking a candidate that is lighter than an earlier pick means that lighter clauses were
+    /// found in the meantime. When instead no pick in a window of this many picks is lighter than the pick
+    /// before the window, while the queue grew, the resolver is considered stalled, see
+    /// [Resolver::is_stalled]. This is a heuristic: a stalled resolver may still find a proof. By
+    /// default, there is no watchdog.
+    pub fn set_stall_window(&mut self, window: Option<usize>) {
+        self.stall_window = window;
+    }
+
+    /// Sets whether [Resolver::step_n_times], [Resolver::step_until] and [Resolver::step_n_times_until]
+    /// stop and return [None] once the resolver is stalled, see [Resolver::set_stall_window]. Calling
+    /// [Resolver::step] or [Resolver::step_indefinitely] continues resolution regardless. This is disabled
+    /// by default.
+    pub fn should_stop_when_stalled(&mut self, stop: bool) {
+        self.stop_when_stalled = stop;
+    }
+
+    /// Returns whether the watchdog found that the resolver stalled, see [Resolver::set_stall_window].
+    /// Once stalled, the resolver stays stalled.
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    /// Sets the [SelectionFunction] of the resolver. Clauses are then only resolved on literals that are
+    /// selected in both clauses. By default, all literals are selected, as with [SelectAll](super::SelectAll).
+    pub fn set_selection_function<S>(&mut self, selection: S) where S : SelectionFunction + 'static {
+        self.selection = Some(Rc::new(selection));
+    }
+
+    /// Sets a hook that is called with a [ResolutionEvent] whenever the resolver picks, learns or drops
+    /// a clause, and when it finds the empty clause. This allows to follow the progress of the resolver,
+    /// e.g. for logging. Events are only emitted to the last hook that was set.
+    pub fn set_event_hook<F>(&mut self, hook: F) where F : FnMut(&ResolutionEvent) + 'static {
+        self.hook = Some(EventHook(Box::new(hook)));
+    }
+
+    /// Removes the hook set by [Resolver::set_event_hook].
+    pub fn remove_event_hook(&mut self) {
+        self.hook = None;
+    }
+
+    /// Creates a copy of this resolver in its current state, sharing the clauses it learned. The copy
+    /// continues independently from this resolver: clauses assumed or learned by either one are not seen
+    /// by the other. The copy has the same settings and selection function, but no event hook.
+    pub fn fork(&self) -> Resolver {
+        Resolver {
+            kb: self.kb.clone(),
+            heuristic: self.heuristic,
+            skip_proof_derivation: self.skip_proof_derivation,
+            build_model: self.build_model,
+            max_term_depth: self.max_term_depth,
+            discarded_by_depth: self.discarded_by_depth,
+            max_clauses: self.max_clauses,
+            discarded_by_clause_limit: self.discarded_by_clause_limit,
+            stall_window: self.stall_window,
+            stop_when_stalled: self.stop_when_stalled,
+            stall_reference: self.stall_reference,
+            picks_without_progress: self.picks_without_progress,
+            stalled: self.stalled,
+            resolvents_produced: self.resolvents_produced,
+            max_clause_len: self.max_clause_len,
+            total_clause_len: self.total_clause_len,
+            tautologies_discarded: self.tautologies_discarded,
+            subsumed_dropped: self.subsumed_dropped,
+            canonical: self.canonical.clone(),
+            peak_frontier: self.peak_frontier,
+            mgu_attempts: self.mgu_attempts,
+            mgu_successes: self.mgu_successes,
+            queue: self.queue.clone(),
+            next_sequence: self.next_sequence,
+            empty_clause: self.empty_clause.clone(),
+            empty_candidate: self.empty_candidate.clone(),
+            premises: self.premises.clone(),
+            deduced: self.deduced.clone(),
+            depths: self.depths.clone(),
+            deductions_made: self.deductions_made,
+            learning_order: self.learning_order.clone(),
+            done: self.done,
+            mgu_cache: self.mgu_cache.clone(),
+            selection: self.selection.clone(),
+            hook: None
+        }
+    }
+
+    /// Assumes a premise. Tautological clauses, which are not [disjoint][Clause::is_disjoint], are ignored.
     pub fn assume(&mut self, c: Clause) {
+        // Tautologies can never contribute to a refutation
+        if !c.is_disjoint() {
+            return;
+        }
+
         // In the resolver ecosystem we drastically move around and refer to clauses so
         // the first thing we do as a new clause enters the system is putting it into
         // a reference counted pointer.
         let rc = Rc::new(c);
 
-        if self.learn(rc.clone(), 1) {
+        if self.learn(rc.clone(), 1, None) {
             // This clause was assumed, it is thus a premise and we put it in the premise
             // set.
             self.premises.push(rc);
@@ -142,7 +444,8 @@ impl Resolver {
     }
 
 
-    /// Assumes all premises from the given CNF.
+    /// Assumes all premises from the given CNF. If the CNF [has an empty clause][NormalForm::has_empty_clause],
+    /// the next step immediately yields a proof, without resolving anything.
     pub fn assume_cnf(&mut self, cnf: NormalForm) {
         for clause in cnf.into_clauses() {
             self.assume(clause);
@@ -165,7 +468,7 @@ impl Resolver {
                 Proof::Proven(self.derive_proof(empty.clone()))
             }
         } else {
-            Proof::Disproven
+            self.exhausted_proof()
         };
 
         Some(ResolverResult {
@@ -178,7 +481,7 @@ impl Resolver {
     /// Performs at most `n` resolution steps. When a proof or counterproof is found,
     /// the proof is returned. Otherwise [None] is returned.
     pub fn step_n_times(&mut self, mut n: usize) -> Option<ResolverResult> {
-        while n > 0 {
+        while n > 0 && !self.halted() {
             if let Some(proof) = self.step() {
                 return Some(proof);
             }
@@ -189,6 +492,41 @@ impl Resolver {
         None
     }
 
+    /// Performs resolution steps until a proof or counterproof is found, or until the given deadline
+    /// has passed, in which case [None] is returned. To keep the overhead low, the deadline is only
+    /// checked once every few steps, so this may run slightly past the deadline.
+    pub fn step_until(&mut self, deadline: Instant) -> Option<ResolverResult> {
+        loop {
+            if let Some(proof) = self.step_n_times(DEADLINE_CHECK_INTERVAL) {
+                return Some(proof);
+            }
+
+            if self.halted() || Instant::now() >= deadline {
+                return None;
+            }
+        }
+    }
+
+    /// Performs at most `n` resolution steps, like [Resolver::step_n_times], but stops early when the
+    /// given deadline has passed, like [Resolver::step_until].
+    pub fn step_n_times_until(&mut self, mut n: usize, deadline: Instant) -> Option<ResolverResult> {
+        while n > 0 {
+            let batch = usize::min(n, DEADLINE_CHECK_INTERVAL);
+
+            if let Some(proof) = self.step_n_times(batch) {
+                return Some(proof);
+            }
+
+            n -= batch;
+
+            if self.halted() || Instant::now() >= deadline {
+                return None;
+            }
+        }
+
+        None
+    }
+
     /// Performs resolution steps indefinitely until a proof or counterproof is found.
     /// Note that due to semidecidability, no counterproof may ever be found and this function
     /// may iterate indefinitely.
@@ -200,6 +538,40 @@ impl Resolver {
         }
     }
 
+    /// Attempts to refute the given clauses together with the clauses known to this resolver, leaving this
+    /// resolver untouched. Typically, the resolver has assumed a set of premises and was stepped for a while
+    /// to saturate them, and the given clauses are the CNF of a negated goal. Every query then continues from
+    /// a [fork](Resolver::fork) of that saturated state, so the work spent on the premises is shared among
+    /// all goals. The stats of the result include the work done before forking.
+    ///
+    /// The Skolem functions of the goal must not share names with any symbol of the premises, nor may
+    /// the Skolem functions of the premises appear in the goal. Skolemisation names Skolem functions from
+    /// the [free](crate::expr::Names::free) name of the skolemised expression onwards, so when the goal and
+    /// the premises are skolemised separately, their Skolem functions may well coincide. Such clashes are
+    /// not detected and make the result unsound, so rename the Skolem functions apart, e.g. with
+    /// [Clause::rename_fun], or take all of them from the same expression.
+    ///
+    /// Like [Resolver::step_indefinitely], this may not terminate, unless a
+    /// [stall window](Resolver::set_stall_window) is set and the resolver
+    /// [stops when stalled](Resolver::should_stop_when_stalled).
+    pub fn prove(&self, goal: NormalForm) -> ProofResult {
+        let mut resolver = self.fork();
+        resolver.assume_cnf(goal);
+
+        let result = if resolver.stop_when_stalled && resolver.stall_window.is_some() {
+            resolver.step_n_times(usize::MAX)
+        } else {
+            Some(resolver.step_indefinitely())
+        };
+
+        ProofResult::new(result.as_ref(), resolver.stats(), false)
+    }
+
+    /// Returns whether the bounded step functions must stop, because the resolver stalled.
+    fn halted(&self) -> bool {
+        self.stop_when_stalled && self.stalled
+    }
+
     /// Generates a [Proof] object if possible.
     pub fn proof(&self) -> Option<Proof> {
         if !self.done {
@@ -209,43 +581,141 @@ impl Resolver {
         let proof = if let Some(empty) = &self.empty_clause {
             Proof::Proven(self.derive_proof(empty.clone()))
         } else {
-            Proof::Disproven
+            self.exhausted_proof()
         };
 
         Some(proof)
     }
 
+    /// The outcome of running out of candidates without deriving the empty clause. This disproves the
+    /// statement, unless resolvents were discarded for exceeding the maximum term depth or candidates were
+    /// discarded to stay within the clause limit, in which case the outcome is [Proof::Undecided] and no
+    /// model is built.
+    fn exhausted_proof(&self) -> Proof {
+        if self.discarded_by_depth > 0 || self.discarded_by_clause_limit > 0 {
+            Proof::Undecided
+        } else {
+            Proof::Disproven {
+                model: self.model()
+            }
+        }
+    }
+
+    /// Returns the clauses that the resolver currently knows, as a CNF. These are the premises and all
+    /// learned resolvents, except for the empty clause and clauses that were dropped as duplicates. When
+    /// the resolver is exhausted, this is the saturated clause set: every resolvent of its clauses is in
+    /// it already, up to variable names. When resolution was cut short, e.g. by a clause limit, it is only
+    /// the part of the search space that was explored.
+    pub fn saturated_cnf(&self) -> NormalForm {
+        self.kb.clauses().cloned().collect::<Clauses>().into()
+    }
+
+    /// Builds a model of the learned clauses, if enabled and possible.
+    fn model(&self) -> Option<BTreeMap<Name, bool>> {
+        if !self.build_model {
+            return None;
+        }
+
+        find_model(self.learning_order.iter().map(|it| it.as_ref()))
+    }
+
     /// Generates a [ResolverStats] object.
     pub fn stats(&self) -> ResolverStats {
         ResolverStats {
             deductions_made: self.deductions_made,
-            learning_order: self.learning_order.iter().map(|it| it.as_ref().clone()).collect()
+            learning_order: self.learning_order.iter().map(|it| it.as_ref().clone()).collect(),
+            discarded_by_depth: self.discarded_by_depth,
+            discarded_by_clause_limit: self.discarded_by_clause_limit,
+            resolvents_produced: self.resolvents_produced,
+            max_clause_len: self.max_clause_len,
+            total_clause_len: self.total_clause_len,
+            tautologies_discarded: self.tautologies_discarded,
+            subsumed_dropped: self.subsumed_dropped,
+            peak_frontier: self.peak_frontier,
+            mgu_attempts: self.mgu_attempts,
+            mgu_successes: self.mgu_successes
         }
     }
 
-    /// Adds a clause to the knowledge base and resolves new candidates from it.
-    fn learn(&mut self, clause: Rc<Clause>, distance: u64) -> bool {
+    /// Adds a clause to the knowledge base and resolves new candidates from it. Clauses that are
+    /// alpha-variants of a learned clause are dropped. The clause itself is learned, not its canonical
+    /// form, since clauses are not standardized apart and would otherwise share variables.
+    fn learn(&mut self, clause: Rc<Clause>, distance: u64, parents: Option<(&Clause, &Clause)>) -> bool {
+        if !clause.is_empty() && !self.canonical.insert(clause.as_ref().clone().canonicalize()) {
+            self.subsumed_dropped += 1;
+            self.emit(ResolutionEvent::Subsumed(&clause));
+            return false;
+        }
+
         self.learning_order.push(clause.clone());
 
         if clause.is_empty() {
             let is_new = self.empty_clause.is_none();
+            self.emit(ResolutionEvent::Learned { clause: &clause, parents });
             self.empty_clause = Some(clause);
+
+            if is_new {
+                self.emit(ResolutionEvent::Contradiction);
+            }
+
             return is_new;
         }
 
         if let Some(new_candidates) = self.kb.learn_rc(clause.clone()) {
             self.depths.insert(clause.clone(), distance);
+            self.emit(ResolutionEvent::Learned { clause: &clause, parents });
 
-            for (a, b) in new_candidates {
-                self.try_resolve(a, b);
-            }
+            self.resolve_candidates(new_candidates);
+            self.enforce_clause_limit();
 
             true
         } else {
+            self.subsumed_dropped += 1;
+            self.emit(ResolutionEvent::Subsumed(&clause));
             false
         }
     }
 
+    /// Discards the heaviest candidates in the queue if the resolver holds more clauses than allowed,
+    /// see [Resolver::set_max_clauses].
+    fn enforce_clause_limit(&mut self) {
+        if let Some(max) = self.max_clauses {
+            let room = max.saturating_sub(self.learning_order.len());
+            self.discarded_by_clause_limit += self.queue.truncate(room);
+        }
+    }
+
+    /// Updates the watchdog with the weight of a picked candidate, see [Resolver::set_stall_window].
+    fn watch_progress(&mut self, weight: u64) {
+        let Some(window) = self.stall_window else {
+            return;
+        };
+
+        let frontier = self.queue.len();
+
+        match self.stall_reference {
+            Some((ref_weight, ref_frontier)) if weight >= ref_weight && frontier > ref_frontier => {
+                self.picks_without_progress += 1;
+
+                if self.picks_without_progress >= window {
+                    self.stalled = true;
+                }
+            },
+
+            _ => {
+                self.stall_reference = Some((weight, frontier));
+                self.picks_without_progress = 0;
+            }
+        }
+    }
+
+    /// Passes an event to the event hook, if there is one.
+    fn emit(&mut self, event: ResolutionEvent) {
+        if let Some(EventHook(hook)) = &mut self.hook {
+            hook(&event);
+        }
+    }
+
     /// Learns the next clause in queue. Returns `false` if the queue is empty
     /// or if the empty clause was learned.
     fn deduce(&mut self) -> bool {
@@ -253,8 +723,11 @@ impl Resolver {
             return false;
         }
 
-        if let Some(candidate) = self.queue.poll_elem() {
-            if self.learn(candidate.result.clone(), candidate.depth) {
+        if let Some(candidate) = self.empty_candidate.take().or_else(|| self.queue.poll_elem()) {
+            self.emit(ResolutionEvent::Picked(&candidate.result));
+            self.watch_progress(candidate.heuristic);
+
+            if self.learn(candidate.result.clone(), candidate.depth, Some((&candidate.a, &candidate.b))) {
                 // We deduced this clause, so add to deduction map
                 self.deduced.insert(candidate.result.clone(), candidate);
             }
@@ -267,59 +740,157 @@ impl Resolver {
         }
     }
 
-    /// Attempts to resolve the two given clauses.
-    fn try_resolve(&mut self, a: Rc<Clause>, b: Rc<Clause>) {
-        for resolvee in Resolvee::find(a.as_ref(), b.as_ref()) {
-            self.resolve(a.clone(), b.clone(), resolvee);
+    /// Attempts to resolve all the given pairs of clauses.
+    #[cfg(not(feature = "parallel"))]
+    fn resolve_candidates(&mut self, candidates: BTreeSet<(Rc<Clause>, Rc<Clause>)>) {
+        for (a, b) in candidates {
+            self.try_resolve(a, b);
         }
     }
 
-    /// Resolves the given clauses using a found resolvee, and inserts it as
-    /// a candidate into the queue.
-    fn resolve(&mut self, a: Rc<Clause>, b: Rc<Clause>, resolvee: Resolvee) {
-        let Resolvee { a: a_atom, b: b_atom, a_neg, b_neg, mgu } = &resolvee;
+    /// Attempts to resolve all the given pairs of clauses. With the `parallel` feature, the resolvents
+    /// of many pairs are computed in parallel and then inserted into the queue sequentially. When an
+    /// empty resolvent is found, the pairs that have not been resolved yet are skipped, since a proof
+    /// is already guaranteed. Which pairs get skipped depends on thread timing, so the order of
+    /// clauses in the queue may differ between runs, but the final result does not.
+    ///
+    /// When the resolver has an [MguCache], pairs are always resolved sequentially.
+    #[cfg(feature = "parallel")]
+    fn resolve_candidates(&mut self, candidates: BTreeSet<(Rc<Clause>, Rc<Clause>)>) {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use rayon::prelude::*;
+
+        if self.mgu_cache.is_some() || candidates.len() < PARALLEL_THRESHOLD {
+            for (a, b) in candidates {
+                self.try_resolve(a, b);
+            }
+
+            return;
+        }
 
-        let mut new_a = a.as_ref().clone();
-        let mut new_b = b.as_ref().clone();
+        let pairs: Vec<_> = candidates.into_iter().collect();
 
-        // Remove resolved atoms
-        if *a_neg {
-            new_a.remove_neg(a_atom);
-        } else {
-            new_a.remove_pos(a_atom);
+        // Rc is not thread safe, but the clauses themselves are
+        let refs: Vec<(&Clause, &Clause)> = pairs.iter().map(|(a, b)| (a.as_ref(), b.as_ref())).collect();
+
+        let found_empty = AtomicBool::new(false);
+        let resolved: Vec<PairResolvents> = refs.par_iter().map(|(a, b)| {
+            if found_empty.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            let resolvents: Vec<_> = Resolvee::find(a, b).into_iter().map(|resolvee| {
+                let result = resolvee.simplified_resolvent(a, b);
+                (resolvee, result)
+            }).collect();
+
+            if resolvents.iter().any(|(_, result)| result.as_ref().is_some_and(Clause::is_empty)) {
+                found_empty.store(true, Ordering::Relaxed);
+            }
+
+            Some(resolvents)
+        }).collect();
+
+        for ((a, b), resolvents) in pairs.into_iter().zip(resolved) {
+            let Some(resolvents) = resolvents else {
+                continue;
+            };
+
+            self.mgu_attempts += mgu_attempts(a.as_ref(), b.as_ref());
+            self.mgu_successes += resolvents.len();
+
+            let (resolvees, results): (Vec<_>, Vec<_>) = resolvents.into_iter().unzip();
+            let selected = self.filter_selected(a.as_ref(), b.as_ref(), resolvees.clone());
+
+            for (resolvee, result) in resolvees.into_iter().zip(results) {
+                if selected.contains(&resolvee) {
+                    self.resolve(a.clone(), b.clone(), resolvee, result);
+                }
+            }
         }
+    }
 
-        if *b_neg {
-            new_b.remove_neg(b_atom);
-        } else {
-            new_b.remove_pos(b_atom);
+    /// Attempts to resolve the two given clauses.
+    fn try_resolve(&mut self, a: Rc<Clause>, b: Rc<Clause>) {
+        let resolvees = match &mut self.mgu_cache {
+            Some(cache) => Resolvee::find_cached(a.as_ref(), b.as_ref(), cache),
+            None => Resolvee::find(a.as_ref(), b.as_ref())
+        };
+
+        self.mgu_attempts += mgu_attempts(a.as_ref(), b.as_ref());
+        self.mgu_successes += resolvees.len();
+
+        let resolvees = self.filter_selected(a.as_ref(), b.as_ref(), resolvees);
+
+        for resolvee in resolvees {
+            let result = resolvee.simplified_resolvent(a.as_ref(), b.as_ref());
+            self.resolve(a.clone(), b.clone(), resolvee, result);
         }
+    }
+
+    /// Retains only the resolvees that resolve on literals selected by the [SelectionFunction].
+    fn filter_selected(&self, a: &Clause, b: &Clause, resolvees: Vec<Resolvee>) -> Vec<Resolvee> {
+        let Some(selection) = &self.selection else {
+            return resolvees;
+        };
+
+        let a_selected = selection.select(a);
+        let b_selected = selection.select(b);
+
+        resolvees.into_iter()
+            .filter(|it| a_selected.contains(&(it.a.clone(), it.a_neg)) && b_selected.contains(&(it.b.clone(), it.b_neg)))
+            .collect()
+    }
+
+    /// Inserts the resolvent of the given clauses, obtained using the given resolvee, as a candidate
+    /// into the queue. The resolvent is [None] if it was a tautology, see [Resolvee::simplified_resolvent].
+    fn resolve(&mut self, a: Rc<Clause>, b: Rc<Clause>, resolvee: Resolvee, result: Option<Clause>) {
+        self.resolvents_produced += 1;
+
+        // Tautologies are true per definition, so they must be ignored
+        let Some(result) = result else {
+            self.tautologies_discarded += 1;
+            return;
+        };
 
-        // Concat clauses and unify by the MGU
-        let result = new_a.concat(new_b).unify(mgu);
+        let len = result.len();
+        self.total_clause_len += len;
+        self.max_clause_len = usize::max(self.max_clause_len, len);
 
-        // If the result is not disjoint, then it contains a literal both
-        // in positive and negative forms. That means the clause is per
-        // definition a tautology and we must ignore it.
-        if !result.is_disjoint() {
+        if let Some(max) = self.max_term_depth && result.depth() > max {
+            self.discarded_by_depth += 1;
             return;
         }
 
         // Clause distance
-        let depth = u64::max(*self.depths.get(&a).unwrap_or(&1), *self.depths.get(&b).unwrap_or(&1)) + 1;
+        let depth = u64::max(*self.depths.get(&a).unwrap_or(&1), *self.depths.get(&b).unwrap_or(&1)).saturating_add(1);
 
         // Clause heuristic
         let heuristic = self.heuristic.heuristic(&result, depth);
 
-        // Insert into queue
-        self.queue.insert_elem(Candidate {
+        let candidate = Candidate {
             a,
             b,
             resolvee,
             result: Rc::new(result),
             heuristic,
-            depth
-        });
+            depth,
+            sequence: self.next_sequence
+        };
+
+        // The empty clause ends the resolution, so it skips the queue regardless of the heuristic. One
+        // empty resolvent is enough, any later ones are ignored.
+        if candidate.result.is_empty() {
+            self.empty_candidate.get_or_insert(candidate);
+            return;
+        }
+
+        // Insert into queue
+        self.queue.insert_elem(candidate);
+
+        self.next_sequence += 1;
+
+        self.peak_frontier = usize::max(self.peak_frontier, self.queue.len());
     }
 
 
@@ -342,6 +913,18 @@ impl Resolver {
     }
 }
 
+/// Counts the MGUs that are attempted when resolving the two given clauses, that is, the amount of
+/// pairs of complementary atoms with the same predicate name.
+fn mgu_attempts(a: &Clause, b: &Clause) -> usize {
+    let count = |x: &PredicateIndex, y: &PredicateIndex| -> usize {
+        x.iter_pred_names()
+            .filter_map(|name| Some(x.get_preds(name)?.len() * y.get_preds(name)?.len()))
+            .sum()
+    };
+
+    count(a.pos(), b.neg()) + count(a.neg(), b.pos())
+}
+
 struct ProofBuilder<'lt> {
     resolver: &'lt Resolver,
     proof: Vec<Deduction>,
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use super::{Proof, ResolverResult, ResolverStats};
+
+/// The outcome of an attempt to prove a statement, along with the [ResolverStats] of the attempt. This
+/// [Display]s as the keyword the command line prints for it: `proven`, `disproven`, `exhausted` or
+/// `undecided`.
+#[derive(Debug, Clone)]
+pub enum ProofResult {
+    /// The statement was proven.
+    Proven(ResolverStats),
+
+    /// The statement was disproven, i.e. its negation was proven.
+    Disproven(ResolverStats),
+
+    /// All possibilities were explored without finding a proof.
+    Exhausted(ResolverStats),
+
+    /// The resolver stopped before finding a proof or exploring all possibilities, e.g. because it ran
+    /// out of steps or time. This is also the outcome when the search space was exhausted but resolvents
+    /// were discarded due to the maximum term depth or the clause limit, since then exhausting proves nothing.
+    Undecided(ResolverStats),
+}
+
+impl ProofResult {
+    /// Determines the outcome from the result of stepping a resolver, [None] meaning that the resolver
+    /// did not finish, and the resolver's stats. When `disproving` is set, the resolver was refuting the
+    /// statement's negation, so that finding a proof disproves the statement.
+    pub fn new(result: Option<&ResolverResult>, stats: ResolverStats, disproving: bool) -> Self {
+        match result.map(|it| &it.proof) {
+            Some(Proof::Proven(_)) if disproving => Self::Disproven(stats),
+            Some(Proof::Proven(_)) => Self::Proven(stats),
+            Some(Proof::Disproven { .. }) if stats.discarded_by_depth == 0 && stats.discarded_by_clause_limit == 0 => Self::Exhausted(stats),
+            _ => Self::Undecided(stats),
+        }
+    }
+
+    /// The keyword this outcome [Display]s as.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Proven(_) => "proven",
+            Self::Disproven(_) => "disproven",
+            Self::Exhausted(_) => "exhausted",
+            Self::Undecided(_) => "undecided",
+        }
+    }
+
+    /// The stats of the attempt.
+    pub fn stats(&self) -> &ResolverStats {
+        match self {
+            Self::Proven(stats) | Self::Disproven(stats) | Self::Exhausted(stats) | Self::Undecided(stats) => stats,
+        }
+    }
+}
+
+impl Display for ProofResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.keyword())
+    }
+}
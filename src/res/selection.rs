@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use crate::expr::AExpr;
+use crate::nf::{Atom, Clause};
+use crate::uni::KboOrdering;
+
+/// A selection function determines which literals of a [Clause] may be resolved on. A
+/// [Resolver](super::Resolver) with a selection function only resolves two clauses on literals that are
+/// selected in both clauses. Selecting fewer literals prunes the search space, see
+/// [Resolver::set_selection_function](super::Resolver::set_selection_function).
+pub trait SelectionFunction : Debug {
+    /// Selects the literals of the given clause that may be resolved on. Each literal is returned as its
+    /// atom, along with whether the literal is negative.
+    fn select(&self, clause: &Clause) -> Vec<(Atom, bool)>;
+}
+
+/// A [SelectionFunction] that selects all literals. This is the same as not having a selection function.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectAll;
+
+impl SelectionFunction for SelectAll {
+    fn select(&self, clause: &Clause) -> Vec<(Atom, bool)> {
+        literals(clause).collect()
+    }
+}
+
+/// A [SelectionFunction] that selects the maximal literals of a clause, under a [KboOrdering] on the
+/// atoms of the literals. A literal is maximal if no other literal in the clause is strictly greater.
+/// Resolving only on maximal literals is known as ordered resolution.
+#[derive(Debug, Clone, Default)]
+pub struct SelectMaximal {
+    ordering: KboOrdering
+}
+
+impl SelectMaximal {
+    /// Creates a new [SelectMaximal] that compares atoms using the given [KboOrdering]. Predicates
+    /// are compared like functions.
+    pub fn new(ordering: KboOrdering) -> Self {
+        Self {
+            ordering
+        }
+    }
+}
+
+impl SelectionFunction for SelectMaximal {
+    fn select(&self, clause: &Clause) -> Vec<(Atom, bool)> {
+        let terms: Vec<_> = literals(clause).map(|(atom, neg)| (as_term(&atom), atom, neg)).collect();
+
+        terms.iter()
+            .filter(|(term, _, _)| terms.iter().all(|(other, _, _)| self.ordering.compare(other, term) != Some(Ordering::Greater)))
+            .map(|(_, atom, neg)| (atom.clone(), *neg))
+            .collect()
+    }
+}
+
+fn literals(clause: &Clause) -> impl Iterator<Item = (Atom, bool)> {
+    clause.iter_literals().map(|(name, args, neg)| (Atom::Pred(name, args.clone()), neg))
+}
+
+fn as_term(atom: &Atom) -> AExpr {
+    match atom {
+        Atom::Pred(name, args) => AExpr::fun(*name, args.clone())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::expr::{BExpr, Stmt};
+
+use super::Proof;
+
+impl Stmt {
+    /// Decides this statement for cheap, structural reasons, without converting it to CNF and
+    /// resolving it. Returns [None] if no such reason applies, in which case the statement has to
+    /// be proven by resolution.
+    ///
+    /// The statement is valid if any premise is `false`, or if every conclusion is `true` or appears
+    /// verbatim as a premise. Conjunctions are split, so `P & Q |- Q` is valid as well. In this case a
+    /// [Proof::Proven] without deductions is returned, as no resolution took place.
+    ///
+    /// The statement is invalid if all premises are `true` and some conclusion is `false`, in which
+    /// case a [Proof::Disproven] without model is returned.
+    pub fn trivial_result(&self) -> Option<Proof> {
+        let mut premises = Vec::new();
+        for premise in self.premises() {
+            conjuncts(premise, &mut premises);
+        }
+
+        let mut conclusions = Vec::new();
+        for conclusion in self.conclusions() {
+            conjuncts(conclusion, &mut conclusions);
+        }
+
+        if premises.contains(&&BExpr::False) {
+            return Some(Proof::Proven(Vec::new()));
+        }
+
+        if conclusions.iter().all(|it| **it == BExpr::True || premises.contains(it)) {
+            return Some(Proof::Proven(Vec::new()));
+        }
+
+        if premises.iter().all(|it| **it == BExpr::True) && conclusions.contains(&&BExpr::False) {
+            return Some(Proof::Disproven { model: None });
+        }
+
+        None
+    }
+}
+
+/// Collects the operands of nested conjunctions in the given expression.
+fn conjuncts<'a>(expr: &'a BExpr, out: &mut Vec<&'a BExpr>) {
+    match expr {
+        BExpr::And(lhs, rhs) => {
+            conjuncts(lhs, out);
+            conjuncts(rhs, out);
+        },
+
+        _ => out.push(expr)
+    }
+}
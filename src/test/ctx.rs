@@ -1,5 +1,5 @@
 use crate::expr::{AExpr, BExpr, Name, Stmt};
-use crate::fmt::DisplayNamed;
+use crate::fmt::{DisplayNamed, NameTable};
 use crate::nf::{Atom, Clause, NormalForm};
 use crate::parser::ParseContext;
 use crate::uni::Unifier;
@@ -22,6 +22,16 @@ impl TestContext {
         }
     }
 
+    /// Sets whether parsing rejects a name used with different amounts of arguments, see
+    /// [ParseContext::set_strict_arity]. Arity is strict by default.
+    pub fn set_strict_arity(&mut self, strict: bool) {
+        self.pc.set_strict_arity(strict);
+    }
+
+    pub fn name_table(&self) -> &NameTable {
+        self.pc.name_table()
+    }
+
     pub fn display<D>(&self, elem: D) where D : DisplayNamed {
         println!("{}", elem.with_table(self.pc.name_table()))
     }
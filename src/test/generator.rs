@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use crate::expr::{BExpr, Name};
+
+/// Generates pseudo-random [BExpr]s for property tests. The generator is seeded, so a failing case can be
+/// reproduced from the seed it was generated with, and generators with the same seed and symbols produce
+/// the same expressions.
+pub struct BExprGenerator {
+    state: u64,
+    symbols: Vec<Name>
+}
+
+impl BExprGenerator {
+    /// Creates a generator of expressions over the given nullary predicates, which must not be empty.
+    pub fn new(seed: u64, symbols: Vec<Name>) -> Self {
+        assert!(!symbols.is_empty(), "No symbols to generate expressions with");
+
+        // Xorshift does not work with a zero state
+        let state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        Self { state, symbols }
+    }
+
+    /// Generates a ground, quantifier-free expression, of which every subexpression nested deeper than
+    /// `depth` is a symbol or a constant. Symbols are more likely than constants.
+    pub fn ground(&mut self, depth: usize) -> BExpr {
+        let choice = if depth == 0 { self.below(10) } else { self.below(16) };
+
+        match choice {
+            0 => BExpr::True,
+            1 => BExpr::False,
+            2..10 => {
+                let i = self.below(self.symbols.len());
+                BExpr::sym(self.symbols[i])
+            },
+            10..12 => !self.ground(depth - 1),
+            12..14 => self.ground(depth - 1) & self.ground(depth - 1),
+            _ => self.ground(depth - 1) | self.ground(depth - 1),
+        }
+    }
+
+    /// Returns the next pseudo-random number, using xorshift64.
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Returns a pseudo-random number below `n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Returns all truth assignments of the given names, to check properties of propositional expressions
+/// exhaustively. There are `2^n` assignments for `n` names.
+pub fn all_assignments(names: &[Name]) -> Vec<BTreeMap<Name, bool>> {
+    (0..1usize << names.len())
+        .map(|bits| names.iter().enumerate().map(|(i, name)| (*name, bits & (1 << i) != 0)).collect())
+        .collect()
+}
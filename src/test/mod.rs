@@ -1,3 +1,5 @@
 mod ctx;
+mod generator;
 
-pub use ctx::*;
\ No newline at end of file
+pub use ctx::*;
+pub use generator::*;
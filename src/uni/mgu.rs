@@ -1,12 +1,32 @@
 use std::collections::BTreeSet;
 use std::mem::take;
 
-use crate::expr::{AExpr, Vars};
+use crate::expr::{AExpr, Name, Vars};
 use crate::expr::AExpr::*;
 use crate::uni::Unifiable;
 
 use super::Unifier;
 
+/// The reason why no [MGU][Unifier::mgu_explain] exists.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum UnifyError {
+    /// Two subterms with different function symbols were to be unified, e.g. `f(:x) = g(:y)`.
+    FunctionClash(Name, Name),
+
+    /// Two subterms with the same function symbol but a different amount of arguments were to be
+    /// unified, e.g. `f(:x) = f(:x, :y)`.
+    ArityMismatch(Name, usize, usize),
+
+    /// A variable was to be unified with a term that contains it, e.g. `:x = f(:x)`, which would yield
+    /// an infinite term. The term is given as it was when this was detected, that is, with the
+    /// substitutions found up to that point applied.
+    OccursCheck(Name, AExpr),
+
+    /// The inputs have a different shape, e.g. argument lists of different lengths, or predicates with
+    /// different names.
+    ShapeMismatch
+}
+
 /// An data structure for finding [MGUs][mgu]. It has a set of equalities `G` and
 /// a unifier `U`.
 /// 
@@ -38,7 +58,7 @@ impl MguFinder {
     }
 
     /// A step in the process of unification. This transforms both `G` and `U`.
-    fn step(&mut self) -> bool {
+    fn step(&mut self) -> Result<(), UnifyError> {
         // Move out old `G` and `U` by moving in empty sets/maps.
         let mut g = take(&mut self.g);
         let mut u = take(&mut self.u);
@@ -66,7 +86,7 @@ impl MguFinder {
                     if r.has_var(&x) {
                         // If `x` is a subexpression of `r`, then the unifier is infinitely recursive.
                         // This cannot happen so we fail.
-                        return false;
+                        return Err(UnifyError::OccursCheck(x, r));
                     }
 
 
@@ -90,14 +110,14 @@ impl MguFinder {
                     if x != y {
                         // This means we have `f(...) = g(...)` where `f !== g`. We cannot unify this,
                         // so we fail.
-                        return false;
+                        return Err(UnifyError::FunctionClash(x, y));
                     }
 
                     if xs.len() != ys.len() {
                         // This means we have two functions `f` equal to eachother, but their amount of
                         // arguments differs. We treat these to be different functions as well, so we
                         // fail.
-                        return false;
+                        return Err(UnifyError::ArityMismatch(x, xs.len(), ys.len()));
                     }
 
                     // Zip the function arguments, we will now unify these pairs.
@@ -126,21 +146,20 @@ impl MguFinder {
             self.u = u;
         }
 
-        return true;
+        Ok(())
     }
 
     /// Runs the MGU finding algorithm.
-    fn run(mut self) -> Option<Unifier> {
+    fn run(mut self) -> Result<Unifier, UnifyError> {
         // Repeatedly step until `G` is empty or a failure is detected.
-        while self.step() {
+        loop {
+            self.step()?;
+
             if self.g.is_empty() {
                 // If `G` is empty, we're done, so we stop. `U` is now our MGU.
-                return Some(self.u);
+                return Ok(self.u);
             }
         }
-
-        // If we reach here, `step()` returned `false` so we failed.
-        return None;
     }
 }
 
@@ -158,10 +177,15 @@ impl MguFinder {
 /// If such unifier does not exist, [None] is returned. Such unifier will for sure not exist when the input
 /// vectors have different lengths.
 pub fn mgu(left: Vec<AExpr>, right: Vec<AExpr>) -> Option<Unifier> {
+    mgu_explain(left, right).ok()
+}
+
+/// Same as [mgu], but returns the [UnifyError] that prevents unification if there is no unifier.
+pub fn mgu_explain(left: Vec<AExpr>, right: Vec<AExpr>) -> Result<Unifier, UnifyError> {
 
     // There is no unifier if input vectors differ in length.
     if left.len() != right.len() {
-        return None;
+        return Err(UnifyError::ShapeMismatch);
     }
 
     // Zip input vectors into a set of equalities.
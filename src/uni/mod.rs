@@ -1,4 +1,7 @@
 mod unifier;
 mod mgu;
+mod order;
 
-pub use unifier::*;
\ No newline at end of file
+pub use unifier::*;
+pub use order::*;
+pub use mgu::UnifyError;
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::expr::{AExpr, Name};
+
+/// The weight of a variable in a [KboOrdering].
+const VAR_WEIGHT: u32 = 1;
+
+/// A [Knuth-Bendix ordering][kbo] on [AExpr]s. It is a reduction ordering, meaning that it is
+/// well-founded, stable under substitution and compatible with the term structure, which makes it
+/// suitable to orient equalities into rewrite rules.
+///
+/// The ordering is parameterised by a weight for each symbol and a precedence on symbols. Variables
+/// have a weight of 1, and symbols that do not appear in [KboOrdering::weights] default to a
+/// weight of 1 as well. Symbols that do not appear in [KboOrdering::precedence] are incomparable to
+/// other symbols, which may make terms incomparable.
+///
+/// A term `s` is greater than a term `t` if each variable occurs at least as often in `s` as in `t`,
+/// and either:
+/// 1.  The weight of `s` is greater than the weight of `t`, or
+/// 2.  The weights are equal, `t` is a variable and `s` is a term `f(...f(t)...)`, or
+/// 3.  The weights are equal, `s = f(s1, ..., sn)`, `t = g(t1, ..., tm)` and `f` precedes `g`, or
+/// 4.  The weights are equal, `s = f(s1, ..., sn)`, `t = f(t1, ..., tn)` and the first `si` that
+///     differs from `ti` is greater than `ti`.
+///
+/// The weights and precedence are not checked for admissibility. That is, a unary symbol of weight
+/// 0 must be the greatest symbol in the precedence for the ordering to be well-founded.
+///
+/// [kbo]: https://en.wikipedia.org/wiki/Knuth%E2%80%93Bendix_ordering
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct KboOrdering {
+    /// The weights of symbols.
+    pub weights: BTreeMap<Name, u32>,
+
+    /// The precedence on symbols, from the smallest to the greatest symbol.
+    pub precedence: Vec<Name>
+}
+
+impl KboOrdering {
+    /// Creates a new [KboOrdering] from the given symbol weights and precedence. The precedence lists
+    /// symbols from smallest to greatest.
+    pub fn new(weights: BTreeMap<Name, u32>, precedence: Vec<Name>) -> Self {
+        Self {
+            weights,
+            precedence
+        }
+    }
+
+    /// Compares two terms in this ordering. Returns [None] if the terms are incomparable.
+    pub fn compare(&self, a: &AExpr, b: &AExpr) -> Option<Ordering> {
+        if a == b {
+            Some(Ordering::Equal)
+        } else if self.greater(a, b) {
+            Some(Ordering::Greater)
+        } else if self.greater(b, a) {
+            Some(Ordering::Less)
+        } else {
+            None
+        }
+    }
+
+    /// Tests whether `s` is strictly greater than `t`.
+    fn greater(&self, s: &AExpr, t: &AExpr) -> bool {
+        if !var_condition(s, t) {
+            return false;
+        }
+
+        let ws = self.weight(s);
+        let wt = self.weight(t);
+
+        if ws != wt {
+            return ws > wt;
+        }
+
+        match (s, t) {
+            (AExpr::Var(_), _) => false,
+
+            // The variable condition holds and weights are equal, so `s` must be of the form
+            // `f(...f(t)...)`, with `f` a unary symbol of weight 0.
+            (AExpr::Fun(_, _), AExpr::Var(_)) => true,
+
+            (AExpr::Fun(f, s_args), AExpr::Fun(g, t_args)) => {
+                if f != g {
+                    return self.precedence_of(f, g) == Some(Ordering::Greater);
+                }
+
+                match s_args.iter().zip(t_args).find(|(si, ti)| si != ti) {
+                    Some((si, ti)) => self.greater(si, ti),
+                    None => s_args.len() > t_args.len()
+                }
+            }
+        }
+    }
+
+    /// Computes the weight of a term, which is the sum of the weights of all symbol and variable
+    /// occurrences.
+    fn weight(&self, e: &AExpr) -> u32 {
+        match e {
+            AExpr::Var(_) => VAR_WEIGHT,
+            AExpr::Fun(name, args) => {
+                let own = self.weights.get(name).copied().unwrap_or(1);
+                args.iter().fold(own, |acc, arg| acc + self.weight(arg))
+            }
+        }
+    }
+
+    /// Compares two symbols in the precedence.
+    fn precedence_of(&self, f: &Name, g: &Name) -> Option<Ordering> {
+        let fi = self.precedence.iter().position(|it| it == f)?;
+        let gi = self.precedence.iter().position(|it| it == g)?;
+
+        Some(fi.cmp(&gi))
+    }
+}
+
+/// Tests whether every variable occurs at least as often in `s` as in `t`.
+fn var_condition(s: &AExpr, t: &AExpr) -> bool {
+    let mut s_vars = BTreeMap::new();
+    let mut t_vars = BTreeMap::new();
+
+    count_vars(s, &mut s_vars);
+    count_vars(t, &mut t_vars);
+
+    t_vars.iter().all(|(var, n)| s_vars.get(var).copied().unwrap_or(0) >= *n)
+}
+
+fn count_vars(e: &AExpr, counts: &mut BTreeMap<Name, usize>) {
+    match e {
+        AExpr::Var(name) => *counts.entry(*name).or_insert(0) += 1,
+        AExpr::Fun(_, args) => {
+            for arg in args {
+                count_vars(arg, counts);
+            }
+        }
+    }
+}
@@ -5,6 +5,8 @@ use std::hash::Hash;
 use crate::expr::{AExpr, Name, Names, Vars};
 use crate::fmt::{write_comma_separated, DisplayNamed};
 
+use super::UnifyError;
+
 /// A unifier is a set of substitutions `x := a` where `x` is some bound variable name and `a` an [AExpr],
 /// with two additional restrictions:
 /// 1.  A variable may only appear on the left hand side of a substitution if and only if it does not appear
@@ -17,6 +19,8 @@ use crate::fmt::{write_comma_separated, DisplayNamed};
 /// by some other [AExpr]. A unifier is therefore a composite transformation of several substitutions, the
 /// transformation being referred to as "unification". Thanks to the first restriction mentioned above, the
 /// order of substitution does not matter.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "BTreeMap<Name, AExpr>", into = "BTreeMap<Name, AExpr>"))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
 pub struct Unifier {
     table: BTreeMap<Name, AExpr>
@@ -107,6 +111,28 @@ impl Unifier {
         self.clone().without(name)
     }
 
+    /// Restricts this unifier to the given set of names. That is, it returns a new unifier which only
+    /// has the substitutions `x := a` of this unifier for which `x` is in `names`. This is useful for
+    /// answer extraction, where one is only interested in the substitutions of the variables of the
+    /// original goal.
+    ///
+    /// The right hand sides of the retained substitutions are unified by this unifier before the other
+    /// substitutions are dropped, so that no substitution is lost transitively.
+    pub fn restrict_to(&self, names: &BTreeSet<Name>) -> Unifier {
+        let table = self.table.iter()
+            .filter(|(name, _)| names.contains(name))
+            .map(|(name, expr)| (*name, expr.clone().unify(self)))
+            .collect();
+
+        Unifier { table }
+    }
+
+    /// Applies this unifier to the given [AExpr]. This is a convenience function for
+    /// [Unifiable::unify], that does not require the [Unifiable] trait to be imported.
+    pub fn compose_apply(&self, expr: AExpr) -> AExpr {
+        expr.unify(self)
+    }
+
     /// Unifies the given [Name] to an [AExpr] as defined by this unifier. The given name is said to be the name
     /// of a bound variable, which could be unified. If the unifier has a substitution for this name, said substitution
     /// is cloned and returned. Otherwise, it generates a [variable expression][AExpr::Var] with the given name.
@@ -121,6 +147,12 @@ impl Unifier {
     }
 
 
+    /// Tests whether this unifier has a substitution for the given variable name.
+    pub fn substitutes(&self, name: &Name) -> bool {
+        self.table.contains_key(name)
+    }
+
+
     /// Returns an iterator over the borrowed substitution pairs in this [Unifier].
     /// To move the elements, use [Self::into_iter].
     pub fn iter(&self) -> impl Iterator<Item = (&Name, &AExpr)> {
@@ -147,6 +179,26 @@ impl Unifier {
         super::mgu::mgu(l, r)
     }
 
+    /// Same as [Unifier::mgu], but if no MGU exists, the [UnifyError] that prevents unification is
+    /// returned.
+    pub fn mgu_explain<U>(left: &U, right: &U) -> Result<Self, UnifyError> where U : Unifiable {
+        if !U::can_resolve_mgu(left, right) {
+            return Err(UnifyError::ShapeMismatch);
+        }
+
+        let l = left.mgu_arguments().ok_or(UnifyError::ShapeMismatch)?;
+        let r = right.mgu_arguments().ok_or(UnifyError::ShapeMismatch)?;
+
+        super::mgu::mgu_explain(l, r)
+    }
+
+    /// Tests whether this unifier unifies the given argument lists, that is, whether both lists have the
+    /// same length and are element-wise equal after applying this unifier to them. Any MGU found by
+    /// [Unifier::mgu] unifies its inputs, so this is useful as a check on the MGU algorithm.
+    pub fn unifies(&self, left: &[AExpr], right: &[AExpr]) -> bool {
+        left.len() == right.len() && left.iter().zip(right).all(|(l, r)| self.compose_apply(l.clone()) == self.compose_apply(r.clone()))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.table.is_empty()
     }
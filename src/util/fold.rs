@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use crate::expr::Name;
+
 pub struct Max<N> where N : Ord {
     pub result: Option<N>
 }
@@ -17,4 +21,31 @@ impl<N> FromIterator<N> for Min<N> where N : Ord {
     fn from_iter<T: IntoIterator<Item = N>>(iter: T) -> Self {
         Self { result: iter.into_iter().min() }
     }
-}
\ No newline at end of file
+}
+
+
+/// Counts how often each [Name] occurs. Collecting the names of an expression into a
+/// [NameHistogram] gives the frequency of each name in one pass, e.g.
+/// `expr.names::<NameHistogram>()`.
+pub struct NameHistogram {
+    pub result: BTreeMap<Name, usize>
+}
+
+impl NameHistogram {
+    /// Returns how often the given name occurs.
+    pub fn count(&self, name: &Name) -> usize {
+        self.result.get(name).copied().unwrap_or(0)
+    }
+}
+
+impl FromIterator<Name> for NameHistogram {
+    fn from_iter<T: IntoIterator<Item = Name>>(iter: T) -> Self {
+        let mut result = BTreeMap::new();
+
+        for name in iter {
+            *result.entry(name).or_insert(0) += 1;
+        }
+
+        Self { result }
+    }
+}
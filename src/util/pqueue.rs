@@ -177,6 +177,45 @@ W : Ord + Copy {
         self.heap.clear();
     }
 
+    /// Retains only the elements for which the given predicate returns `true`, removing all other
+    /// elements. The predicate receives each element along with its weight, in no particular order.
+    /// 
+    /// The total operation takes `O(N)` if there are `N` elements in the queue, which is cheaper than
+    /// removing elements one by one.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+    F : FnMut(&E, W) -> bool {
+        let len = self.heap.len();
+        self.heap.retain(|(e, w)| f(e, *w));
+
+        if self.heap.len() != len {
+            self.restore();
+        }
+    }
+
+    /// Removes the elements with the highest weights from this queue, such that at most `len` elements
+    /// remain. Returns the amount of elements removed. Among elements of equal weight, it is unspecified
+    /// which are removed.
+    /// 
+    /// This operation runs in `O(N)` in a queue of size `N`.
+    pub fn truncate(&mut self, len: usize) -> usize {
+        if self.heap.len() <= len {
+            return 0;
+        }
+
+        let removed = self.heap.len() - len;
+
+        if len > 0 {
+            // Move the `len` lightest elements to the front
+            self.heap.select_nth_unstable_by(len - 1, |a, b| a.1.cmp(&b.1));
+        }
+
+        self.heap.truncate(len);
+        self.restore();
+
+        removed
+    }
+
     fn upheap(&mut self, mut i: usize) {
         let mut p = parent(i);
 
@@ -0,0 +1,250 @@
+use rsplib::expr::{AExpr, BExpr, BExprFolder, FlatBExpr, Name};
+use rsplib::test::TestContext;
+
+#[test]
+fn rename_bound_apart() {
+    let p = Name::any();
+    let q = p.succ();
+    let x = q.succ();
+
+    // (all x: P(x)) & (all x: Q(x))
+    let expr = BExpr::all(x, BExpr::pred(p, vec![AExpr::var(x)]))
+             & BExpr::all(x, BExpr::pred(q, vec![AExpr::var(x)]));
+
+    let BExpr::And(lhs, rhs) = expr.rename_bound_apart() else {
+        panic!("Expected conjunction");
+    };
+
+    let (BExpr::All(a, lhs), BExpr::All(b, rhs)) = (*lhs, *rhs) else {
+        panic!("Expected quantifiers");
+    };
+
+    assert_ne!(a, b);
+    assert_ne!(x, a);
+    assert_ne!(x, b);
+    assert_eq!(BExpr::pred(p, vec![AExpr::var(a)]), *lhs);
+    assert_eq!(BExpr::pred(q, vec![AExpr::var(b)]), *rhs);
+}
+
+#[test]
+fn rename_bound_apart_shadowing() {
+    let p = Name::any();
+    let q = p.succ();
+    let x = q.succ();
+    let y = x.succ();
+
+    // all x: (P(x) & some x: Q(x, y))
+    let expr = BExpr::all(x, BExpr::pred(p, vec![AExpr::var(x)]) & BExpr::some(x, BExpr::pred(q, vec![AExpr::var(x), AExpr::var(y)])));
+
+    let BExpr::All(a, body) = expr.rename_bound_apart() else {
+        panic!("Expected universal quantifier");
+    };
+
+    let BExpr::And(lhs, rhs) = *body else {
+        panic!("Expected conjunction");
+    };
+
+    let BExpr::Some(b, rhs) = *rhs else {
+        panic!("Expected existential quantifier");
+    };
+
+    assert_ne!(a, b);
+    assert_eq!(BExpr::pred(p, vec![AExpr::var(a)]), *lhs);
+
+    // The unbound `y` must not be renamed
+    assert_eq!(BExpr::pred(q, vec![AExpr::var(b), AExpr::var(y)]), *rhs);
+}
+
+#[test]
+fn to_nnf() {
+    let mut ctx = TestContext::new();
+
+    let [exp, expected] = ctx.bexprs([
+        "!(P & !(Q | !R)) | !!S",
+        "(!P | (Q | !R)) | S"
+    ]).try_into().unwrap();
+
+    assert_eq!(expected, exp.to_nnf());
+}
+
+#[test]
+fn to_nnf_quantifiers() {
+    let p = Name::any();
+    let x = p.succ();
+
+    // !(all x: !some x: P(x))
+    let exp = !BExpr::all(x, !BExpr::some(x, BExpr::pred(p, vec![AExpr::var(x)])));
+
+    // some x: some x: P(x)
+    let expected = BExpr::some(x, BExpr::some(x, BExpr::pred(p, vec![AExpr::var(x)])));
+
+    assert_eq!(expected, exp.to_nnf());
+}
+
+#[test]
+fn to_nnf_idempotent() {
+    let mut ctx = TestContext::new();
+
+    let exps = ctx.bexprs([
+        "!(P & !(Q | !R)) | !!S",
+        "!(all x: P(x) -> some y: !Q(x, y))",
+        "!(P <-> !Q)",
+        "!!!true & !(false | P)",
+    ]);
+
+    for exp in exps {
+        let nnf = exp.to_nnf();
+        assert_eq!(nnf.clone(), nnf.to_nnf());
+    }
+}
+
+/// Counts the predicates in an expression.
+struct PredCount;
+
+impl BExprFolder<usize> for PredCount {
+    fn fold_const(&mut self, _: bool) -> usize { 0 }
+    fn fold_pred(&mut self, _: Name, _: &[AExpr]) -> usize { 1 }
+    fn combine_and(&mut self, lhs: usize, rhs: usize) -> usize { lhs + rhs }
+    fn combine_or(&mut self, lhs: usize, rhs: usize) -> usize { lhs + rhs }
+    fn combine_not(&mut self, rhs: usize) -> usize { rhs }
+    fn combine_all(&mut self, _: Name, rhs: usize) -> usize { rhs }
+    fn combine_some(&mut self, _: Name, rhs: usize) -> usize { rhs }
+}
+
+/// Rebuilds an expression, removing double negations by inspecting the operand of negations.
+struct DoubleNegation;
+
+impl BExprFolder<BExpr> for DoubleNegation {
+    fn fold_const(&mut self, value: bool) -> BExpr { BExpr::bool(value) }
+    fn fold_pred(&mut self, name: Name, args: &[AExpr]) -> BExpr { BExpr::pred(name, args.to_vec()) }
+    fn combine_and(&mut self, lhs: BExpr, rhs: BExpr) -> BExpr { lhs & rhs }
+    fn combine_or(&mut self, lhs: BExpr, rhs: BExpr) -> BExpr { lhs | rhs }
+    fn combine_not(&mut self, rhs: BExpr) -> BExpr { !rhs }
+    fn combine_all(&mut self, name: Name, rhs: BExpr) -> BExpr { BExpr::all(name, rhs) }
+    fn combine_some(&mut self, name: Name, rhs: BExpr) -> BExpr { BExpr::some(name, rhs) }
+
+    fn fold_not(&mut self, rhs: &BExpr) -> BExpr {
+        match rhs {
+            BExpr::Not(e) => e.fold(self),
+            e => !e.fold(self)
+        }
+    }
+}
+
+#[test]
+fn fold() {
+    let mut ctx = TestContext::new();
+
+    let [exp, expected] = ctx.bexprs([
+        "!!(P & !Q) | !!!R(a) & true",
+        "(P & !Q) | !R(a) & true"
+    ]).try_into().unwrap();
+
+    assert_eq!(3, exp.fold(&mut PredCount));
+    assert_eq!(2, ctx.bexpr("all x: P(x) -> some y: Q(x, y)").fold(&mut PredCount));
+    assert_eq!(expected, exp.fold(&mut DoubleNegation));
+}
+
+#[test]
+fn desugar() {
+    let mut ctx = TestContext::new();
+
+    let exps = ctx.bexprs([
+        "P -> Q",
+        "P <- Q",
+        "(P <-> Q) & all x: (R(x) -> some y: S(x, y))",
+    ]);
+
+    for exp in exps {
+        assert_eq!(exp.clone(), exp.desugar());
+    }
+}
+
+#[test]
+fn simplify() {
+    let mut ctx = TestContext::new();
+
+    let cases = [
+        ("P & true", "P"),
+        ("true & P", "P"),
+        ("P | false", "P"),
+        ("P & false", "false"),
+        ("true | P", "true"),
+        ("P & P", "P"),
+        ("P | P", "P"),
+        ("!!P", "P"),
+        ("!true", "false"),
+        ("!false", "true"),
+        ("!(P & false) & Q", "Q"),
+        ("(P | false) & (P & true)", "P"),
+        ("all x: P(x) | true", "true"),
+        ("P & Q", "P & Q"),
+    ];
+
+    for (input, expected) in cases {
+        let exp = ctx.bexpr(input);
+        let expected = ctx.bexpr(expected);
+        assert_eq!(expected, exp.simplify(), "simplifying {input}");
+    }
+}
+
+#[test]
+fn simplify_preserves_meaning() {
+    let mut ctx = TestContext::new();
+
+    let exps = ctx.bexprs([
+        "(P & true) | (Q & false) | !!R",
+        "(P | P) & !(Q | true) | !false",
+        "!(P & !P) & (Q -> Q)",
+    ]);
+
+    let names = ctx.names(["P", "Q", "R"]);
+
+    for bits in 0..8u32 {
+        let assignment = names.iter().enumerate().map(|(i, n)| (*n, bits & (1 << i) != 0)).collect();
+
+        for exp in exps.iter() {
+            assert_eq!(exp.eval(&assignment), exp.clone().simplify().eval(&assignment));
+        }
+    }
+}
+
+#[test]
+fn flatten() {
+    let mut ctx = TestContext::new();
+
+    let [p, q, r, s, t, u] = ["P", "Q", "R", "S", "T", "U"].map(|it| ctx.bexpr(it).flatten());
+
+    let expected = FlatBExpr::Or(vec![
+        FlatBExpr::And(vec![p, q.clone(), r]),
+        FlatBExpr::And(vec![s, FlatBExpr::Not(Box::new(FlatBExpr::Or(vec![t, u])))]),
+        q
+    ]);
+
+    assert_eq!(expected, ctx.bexpr("P & (Q & R) | S & !(T | U) | Q").flatten());
+
+    // Chains are rebuilt to the left, like they are parsed
+    let expr = ctx.bexpr("P & Q & R | S");
+    assert_eq!(expr.clone(), expr.flatten().unflatten());
+
+    assert_eq!(BExpr::True, FlatBExpr::And(vec![]).unflatten());
+    assert_eq!(BExpr::False, FlatBExpr::Or(vec![]).unflatten());
+}
+
+#[test]
+fn flatten_long_conjunction() {
+    let mut name = Name::any();
+
+    let mut expr = BExpr::sym(name.incr());
+    for _ in 0..10000 {
+        expr &= BExpr::sym(name.incr());
+    }
+
+    let FlatBExpr::And(operands) = expr.flatten() else {
+        panic!("Expected a conjunction");
+    };
+    assert_eq!(10001, operands.len());
+
+    let expr = FlatBExpr::And(operands).unflatten();
+    assert!(matches!(expr.flatten(), FlatBExpr::And(operands) if operands.len() == 10001));
+}
@@ -1,4 +1,10 @@
-use rsplib::nf::NormalForm;
+use std::collections::{BTreeMap, BTreeSet};
+
+use rsplib::expr::{BExpr, Name};
+use rsplib::nf::{self as nf, Atom, Clause, CnfTooLarge, NormalForm, PredicateIndex, Signature};
+use rsplib::uni::Unifiable;
+use rsplib::res::{find_model, Heuristic, WeightedClause};
+use rsplib::util::pqueue::PQueue;
 use rsplib::test::TestContext;
 
 
@@ -59,4 +65,531 @@ fn test_equivalent_demorgan2() {
     let actual = NormalForm::equiv_cnf(exp);
 
     assert_eq!(expected, actual);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_keep_tautologies() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("(P | Q) & (P -> P | R)");
+
+    let mut actual = NormalForm::equiv_cnf_keep_tautologies(exp.clone());
+    assert_eq!(ctx.cnf("(P | Q) & (!P | P | R)"), actual);
+    assert_eq!(vec![&ctx.clause("!P | P | R")], actual.non_disjoint_clauses().collect::<Vec<_>>());
+
+    actual.retain_only_disjoint();
+    assert_eq!(NormalForm::equiv_cnf(exp), actual);
+}
+
+#[test]
+fn test_unit_contradiction() {
+    let mut ctx = TestContext::new();
+
+    assert!(ctx.cnf("P(a) & (Q | R) & !P(a)").detect_unit_contradiction());
+    assert!(ctx.cnf("P(:x) & !P(f(:x))").detect_unit_contradiction());
+    assert!(ctx.cnf("!P(:x, b) & P(a, :y)").detect_unit_contradiction());
+
+    assert!(!ctx.cnf("P(a) & !P(b)").detect_unit_contradiction());
+    assert!(!ctx.cnf("P(a) & (!P(a) | Q)").detect_unit_contradiction());
+    assert!(!ctx.cnf("P(:x) & !Q(:x)").detect_unit_contradiction());
+    assert!(!ctx.cnf("P(:x, :x) & !P(a, b)").detect_unit_contradiction());
+}
+
+#[test]
+fn unit_contradiction_pair() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P(a) & (Q | R) & !P(a)");
+    let (p, n) = (ctx.clause("P(a)"), ctx.clause("!P(a)"));
+    assert_eq!(Some((&p, &n)), cnf.unit_contradiction());
+
+    let cnf = ctx.cnf("!P(:x, b) & Q & P(a, :y)");
+    let (p, n) = (ctx.clause("P(a, :y)"), ctx.clause("!P(:x, b)"));
+    assert_eq!(Some((&p, &n)), cnf.unit_contradiction());
+
+    assert_eq!(None, ctx.cnf("P(a) & !P(b)").unit_contradiction());
+}
+
+#[test]
+fn test_bounded_cnf() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("(P & Q) | (R & S) | T");
+
+    assert_eq!(Ok(NormalForm::equiv_cnf(exp.clone())), NormalForm::equiv_cnf_bounded(exp.clone(), 3));
+    assert_eq!(Err(CnfTooLarge { max_clause_len: 2, clause_len: 3 }), NormalForm::equiv_cnf_bounded(exp, 2));
+
+    // Clauses of the negated DNF count as well
+    let exp = ctx.bexpr("!(P & Q & R)");
+    assert!(NormalForm::equiv_cnf_bounded(exp, 2).is_err());
+}
+
+#[test]
+fn test_deeply_nested() {
+    let mut name = Name::any();
+
+    // !(!(!(... | P1) | P2) | P3), nested thousands of times, which is a conjunction of literals
+    let mut exp = BExpr::sym(name.incr());
+    for _ in 0..5000 {
+        exp = !(!exp | BExpr::sym(name.incr()));
+    }
+
+    let cnf = NormalForm::equiv_cnf(exp);
+    assert_eq!(5001, cnf.len());
+    assert!(cnf.clauses().iter().all(|it| it.len() == 1));
+}
+
+#[test]
+fn test_pure_literals() {
+    let mut ctx = TestContext::new();
+
+    let mut cnf = ctx.cnf("(P | Q(a)) & (!Q(:x) | R) & (!R | Q(b)) & S");
+    let expected = ctx.cnf("(!Q(:x) | R) & (!R | Q(b))");
+
+    assert_eq!(2, cnf.eliminate_pure_literals());
+    assert_eq!(expected, cnf);
+}
+
+#[test]
+fn test_unit_propagate() {
+    let mut ctx = TestContext::new();
+
+    let mut cnf = ctx.cnf("P & (!P | Q) & (!Q | R | S) & (!P | T(:x))");
+    let expected = ctx.cnf("(R | S) & T(:x)");
+
+    assert_eq!(Ok(()), cnf.unit_propagate());
+    assert_eq!(expected, cnf);
+}
+
+#[test]
+fn test_unit_propagate_contradiction() {
+    let mut ctx = TestContext::new();
+
+    let mut cnf = ctx.cnf("P(a) & (!P(a) | Q) & !Q");
+
+    assert_eq!(Err(()), cnf.unit_propagate());
+    assert!(cnf.has_empty_clause());
+}
+
+#[test]
+fn test_remove_subsumed() {
+    let mut ctx = TestContext::new();
+
+    let mut cnf = ctx.cnf("(P | Q) & (P | Q | R) & (!R | S(:x)) & (Q | !R | S(:x)) & (P | R)");
+    let expected = ctx.cnf("(P | Q) & (!R | S(:x)) & (P | R)");
+
+    assert_eq!(2, cnf.remove_subsumed());
+    assert_eq!(expected, cnf);
+}
+
+#[test]
+fn test_dnf_to_cnf() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("(P & Q) | (!R & S) | T");
+
+    let expected = NormalForm::equiv_cnf(exp.clone());
+    let actual = NormalForm::equiv_dnf(exp).to_cnf_from_dnf();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_cnf_to_dnf() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("(P | Q) & (!R | S) & !P");
+
+    let expected = NormalForm::equiv_dnf(exp.clone());
+    let actual = NormalForm::equiv_cnf(exp).to_dnf_from_cnf();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_tseitin_quantified() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("all x: P(x)");
+
+    // A single predicate needs no Tseitin naming, so this is just the Skolem form
+    let expected = NormalForm::equiv_cnf(exp.clone());
+    let actual = NormalForm::tseitin_cnf(exp);
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_tseitin_quantified_nested() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("all x: some y: (P(x, y) | !Q(y))");
+    let actual = NormalForm::tseitin_cnf(exp);
+
+    assert!(!actual.is_empty());
+    assert!(!actual.has_empty_clause());
+}
+
+#[test]
+fn test_tseitin_negation() {
+    let mut ctx = TestContext::new();
+
+    // The names of `P & Q` and its negation must have opposite truth values,
+    // otherwise this is satisfiable
+    let unsat = NormalForm::tseitin_cnf(ctx.bexpr("!(P & Q) & P & Q"));
+    assert_eq!(None, find_model(unsat.clauses()));
+
+    let sat = NormalForm::tseitin_cnf(ctx.bexpr("!(P & Q) & P"));
+    let model = find_model(sat.clauses()).unwrap();
+    assert_eq!(Some(&true), model.get(&ctx.name("P")));
+    assert_eq!(Some(&false), model.get(&ctx.name("Q")));
+}
+
+#[test]
+fn test_dimacs() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P | !Q) & (Q | R(a)) & !P");
+    let dimacs = cnf.to_dimacs(&rsplib::fmt::NameTable::new()).unwrap();
+
+    let lines: Vec<_> = dimacs.lines().filter(|it| !it.starts_with("c ")).collect();
+    assert_eq!("p cnf 3 3", lines[0]);
+    assert_eq!(4, lines.len());
+    assert!(lines[1..].iter().all(|it| it.ends_with(" 0")));
+
+    // Every atom is listed in a comment
+    assert_eq!(3, dimacs.lines().filter(|it| it.starts_with("c ")).count());
+}
+
+#[test]
+fn test_dimacs_not_ground() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P(:x) | Q");
+    assert_eq!(None, cnf.to_dimacs(&rsplib::fmt::NameTable::new()));
+}
+
+#[test]
+fn signature() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(E(f(a, :x), b) | !P(g(:x))) & Q");
+    let names = ctx.names(["E", "f", "a", "b", "g", "P", "Q"]);
+
+    let sig = Signature::of(&cnf, names[0]);
+
+    assert_eq!(BTreeMap::from([(names[1], 2), (names[2], 0), (names[3], 0), (names[4], 1)]), sig.functions);
+    assert_eq!(BTreeMap::from([(names[5], 1), (names[6], 0)]), sig.predicates);
+}
+
+#[test]
+fn bexpr_signature() {
+    let mut ctx = TestContext::new();
+
+    // `P` is used with different amounts of arguments
+    ctx.set_strict_arity(false);
+
+    let expr = ctx.bexpr("all x: (E(f(a, x), b) -> !P(g(x)) & Q | P(a, b))");
+    let names = ctx.names(["E", "f", "a", "b", "g", "P", "Q"]);
+
+    assert_eq!(BTreeSet::from([(names[0], 2), (names[5], 1), (names[5], 2), (names[6], 0)]), expr.predicates());
+    assert_eq!(BTreeSet::from([(names[1], 2), (names[2], 0), (names[3], 0), (names[4], 1)]), expr.functions());
+
+    // Bound variables are not functions, and the equality predicate is excluded
+    let sig = expr.signature(names[0]);
+    assert_eq!(BTreeMap::from([(names[1], 2), (names[2], 0), (names[3], 0), (names[4], 1)]), sig.functions);
+    assert_eq!(BTreeMap::from([(names[5], 2), (names[6], 0)]), sig.predicates);
+
+    // It agrees with the signature of the CNF
+    let expr = ctx.bexpr("(E(f(a, :x), b) | !P(g(:x))) & Q");
+    assert_eq!(Signature::of(&NormalForm::equiv_cnf(expr.clone()), names[0]), expr.signature(names[0]));
+}
+
+#[test]
+fn equality_axioms_count() {
+    let mut ctx = TestContext::new();
+
+    let mut cnf = ctx.cnf("E(f(a), b) & P(a, b) & Q");
+    let e = ctx.name("E");
+
+    let sig = Signature::of(&cnf, e);
+    cnf.add_equality_axioms(&sig);
+
+    // 3 premises, reflexivity, symmetry, transitivity, congruence of f and P
+    assert_eq!(8, cnf.len());
+}
+
+#[test]
+fn nf_stats() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P(:x) | !Q(:x)) & Q(a) & (!P(a) | R | !R)");
+    let names = ctx.names(["P", "Q", "R"]);
+
+    assert_eq!(3, cnf.clause_count());
+    assert_eq!(6, cnf.literal_count());
+    assert_eq!(names.into_iter().collect::<BTreeSet<_>>(), cnf.predicate_names());
+
+    assert_eq!("3 clauses, 6 literals, 3 predicates", cnf.stats().to_string());
+}
+
+#[test]
+fn iter_by_heuristic() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P(f(g(:x))) | Q(:x)) & R & (P(a) | !Q(b))");
+
+    let order: Vec<_> = cnf.iter_by_heuristic(&Heuristic::SymbolCount).collect();
+
+    let expected = ctx.clauses(["R", "P(a) | !Q(b)", "P(f(g(:x))) | Q(:x)"]);
+    assert_eq!(expected.iter().collect::<Vec<_>>(), order.iter().map(|it| it.0).collect::<Vec<_>>());
+    assert_eq!(vec![1, 4, 6], order.iter().map(|it| it.1).collect::<Vec<_>>());
+}
+
+#[test]
+fn weighted_clause() {
+    let mut ctx = TestContext::new();
+
+    let mut queue = PQueue::new();
+    for clause in ctx.clauses(["P(f(g(:x))) | Q(:x)", "R", "P(a) | !Q(b) | S"]) {
+        queue.insert_elem(WeightedClause(clause, Heuristic::SymbolCount));
+    }
+
+    assert_eq!(Some((&WeightedClause(ctx.clause("R"), Heuristic::SymbolCount), &1)), queue.peek());
+
+    // Switching to another heuristic reweighs all clauses
+    queue.reassoc_modify(|WeightedClause(clause, _)| WeightedClause(clause, Heuristic::DisjunctCount));
+
+    let order: Vec<_> = queue.into_iter().map(|(WeightedClause(clause, _), weight)| (clause, weight)).collect();
+    let expected = ctx.clauses(["R", "P(f(g(:x))) | Q(:x)", "P(a) | !Q(b) | S"]).into_iter().zip([1, 2, 3]).collect::<Vec<_>>();
+    assert_eq!(expected, order);
+}
+
+#[test]
+fn heuristic_saturates() {
+    let mut ctx = TestContext::new();
+
+    let small = ctx.clause("P(a)");
+    let large = ctx.clause("P(f(g(:x))) | Q(:x)");
+
+    // A naive sum would overflow here and wrap around to a tiny weight
+    for h in [Heuristic::SymbolCountPlusDistance, Heuristic::DisjunctCountPlusDistance] {
+        let weight = h.heuristic(&large, u64::MAX);
+
+        assert_eq!(u64::MAX, weight);
+        assert!(weight > h.heuristic(&small, 0));
+    }
+}
+
+#[test]
+fn canonicalize_alpha_variants() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.clause("P(:x, f(:y)) | !Q(:y)");
+    let b = ctx.clause("!Q(:u) | P(:v, f(:u))");
+    let c = ctx.clause("P(:x, f(:x)) | !Q(:x)");
+
+    assert_ne!(a, b);
+    assert_eq!(a.clone().canonicalize(), b.clone().canonicalize());
+    assert_ne!(a.canonicalize(), c.clone().canonicalize());
+
+    // Canonicalizing is idempotent
+    assert_eq!(c.clone().canonicalize(), c.canonicalize().canonicalize());
+}
+
+#[test]
+fn structural_hash() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.clause("P(:x, f(:y)) | !Q(:y)");
+    let b = ctx.clause("!Q(:u) | P(:v, f(:u))");
+    let c = ctx.clause("P(:x, f(:x)) | !Q(:x)");
+
+    assert_eq!(a.structural_hash(), b.structural_hash());
+    assert_ne!(a.structural_hash(), c.structural_hash());
+    assert_eq!(c.structural_hash(), c.clone().canonicalize().structural_hash());
+}
+
+#[test]
+fn apply_and_simplify() {
+    let mut ctx = TestContext::new();
+
+    let clause = ctx.clause("P(:x) | !P(a) | Q(:x, :y)");
+
+    let mgu = ctx.mgu([("y", "b")]);
+    assert_eq!(Some(ctx.clause("P(:x) | !P(a) | Q(:x, b)")), clause.clone().apply_and_simplify(&mgu));
+
+    let mgu = ctx.mgu([("x", "a")]);
+    assert_eq!(None, clause.apply_and_simplify(&mgu));
+
+    // Atoms that become equal are merged
+    let clause = ctx.clause("P(:x) | P(a)");
+    assert_eq!(Some(ctx.clause("P(a)")), clause.apply_and_simplify(&mgu));
+}
+
+#[test]
+fn unify_clauses() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P(:x) | Q(:y)) & R(:y) & S(a)");
+    let mgu = ctx.mgu([("x", "b")]);
+
+    let expected = ctx.cnf("(P(b) | Q(:y)) & R(:y) & S(a)");
+
+    assert_eq!(expected.clauses(), &nf::unify_clauses(cnf.clone().into_clauses(), &mgu));
+    assert_eq!(expected, cnf.unify(&mgu));
+}
+
+#[test]
+fn dnf_satisfiable() {
+    let mut ctx = TestContext::new();
+
+    let sat = NormalForm::equiv_dnf(ctx.bexpr("(P | Q) & !P"));
+    assert!(sat.is_dnf_satisfiable());
+
+    let unsat = NormalForm::equiv_dnf(ctx.bexpr("(P | Q) & !P & !Q"));
+    assert!(!unsat.is_dnf_satisfiable());
+
+    // Clauses are interpreted as conjunctions here
+    let dnf = |clauses: Vec<_>| NormalForm::from(clauses.into_iter().collect::<BTreeSet<_>>());
+
+    assert!(dnf(ctx.clauses(["P | !Q", "Q | !Q"])).is_dnf_satisfiable());
+    assert!(!dnf(ctx.clauses(["P | !P", "Q | !Q"])).is_dnf_satisfiable());
+
+    // `P(:x) & !P(a)` is contradictory, even though it is disjoint
+    assert!(!dnf(ctx.clauses(["P(:x) | !P(a)"])).is_dnf_satisfiable());
+    assert!(dnf(ctx.clauses(["P(:x) | !Q(a)"])).is_dnf_satisfiable());
+}
+
+#[test]
+fn iter_literals() {
+    let mut ctx = TestContext::new();
+
+    let clause = ctx.clause("!Q(a) | P(:x) | R");
+    let [p, q, r] = ctx.names(["P", "Q", "R"]).try_into().unwrap();
+
+    let literals: Vec<_> = clause.iter_literals().map(|(name, args, neg)| (name, args.len(), neg)).collect();
+
+    assert_eq!(vec![(p, 1, false), (r, 0, false), (q, 1, true)], literals);
+    assert_eq!(clause.len(), literals.len());
+}
+
+#[test]
+fn clause_subset() {
+    let mut ctx = TestContext::new();
+
+    let small = ctx.clause("P(a) | !Q(:x)");
+    let large = ctx.clause("P(a) | P(b) | !Q(:x) | R");
+    let flipped = ctx.clause("!P(a) | P(b) | !Q(:x) | R");
+    let other_args = ctx.clause("P(b) | !Q(:x) | R");
+
+    assert!(small.pos().is_subset(large.pos()));
+    assert!(small.neg().is_subset(large.neg()));
+    assert!(!large.pos().is_subset(small.pos()));
+
+    assert!(small.is_subset_of(&large));
+    assert!(small.is_subset_of(&small));
+    assert!(!large.is_subset_of(&small));
+
+    // Same atom with the other polarity
+    assert!(!small.is_subset_of(&flipped));
+
+    // Same predicate with other arguments
+    assert!(!small.is_subset_of(&other_args));
+
+    assert!(Clause::new().is_subset_of(&small));
+}
+
+#[test]
+fn fmt_prolog() {
+    let mut ctx = TestContext::new();
+
+    let rule = ctx.clause("P(:x) | !Q(:x) | !R");
+    let fact = ctx.clause("P(a)");
+    let goal = ctx.clause("!Q(a) | !R");
+    let non_horn = ctx.clause("P(a) | R");
+
+    assert_eq!("P(:x) :- Q(:x), R().", rule.fmt_prolog(ctx.name_table()));
+    assert_eq!("P(a()).", fact.fmt_prolog(ctx.name_table()));
+    assert_eq!(":- Q(a()), R().", goal.fmt_prolog(ctx.name_table()));
+    assert_eq!(":- true.", Clause::new().fmt_prolog(ctx.name_table()));
+    assert_eq!("(P(a()), R())", non_horn.fmt_prolog(ctx.name_table()));
+}
+
+#[test]
+fn map_atoms() {
+    let mut ctx = TestContext::new();
+
+    let [p, q] = ctx.names(["P", "Q"]).try_into().unwrap();
+
+    // Rename P to Q
+    let renamed = ctx.clause("P(a) | !P(:x) | R").map_atoms(|Atom::Pred(name, args), neg| {
+        (Atom::Pred(if name == p { q } else { name }, args), neg)
+    });
+    assert_eq!(ctx.clause("Q(a) | !Q(:x) | R"), renamed);
+
+    // Flip polarities
+    assert_eq!(ctx.clause("!P(a) | Q"), ctx.clause("P(a) | !Q").map_atoms(|atom, neg| (atom, !neg)));
+
+    // Literals that coincide are merged
+    assert_eq!(ctx.clause("P(a)"), ctx.clause("P(a) | !P(a)").map_atoms(|atom, _| (atom, false)));
+}
+
+#[test]
+fn rename_fun() {
+    let mut ctx = TestContext::new();
+
+    // `f` is used both as a function and as a predicate
+    ctx.set_strict_arity(false);
+
+    let [f, g] = ctx.names(["f", "g"]).try_into().unwrap();
+
+    assert_eq!(ctx.aexpr("g(:x, g(a), h(g(b)))"), ctx.aexpr("f(:x, f(a), h(f(b)))").rename_fun(f, g));
+    assert_eq!(ctx.atom("P(g(:x), a)"), ctx.atom("P(f(:x), a)").rename_fun(f, g));
+
+    // Predicates are not functions, so `f` as a predicate is kept
+    assert_eq!(ctx.clause("f(g(a)) | !Q(g(:x))"), ctx.clause("f(f(a)) | !Q(f(:x))").rename_fun(f, g));
+
+    // Literals that coincide are merged
+    assert_eq!(ctx.clause("P(g(a))"), ctx.clause("P(f(a)) | P(g(a))").rename_fun(f, g));
+}
+
+#[test]
+fn into_iter() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P | Q) & R & (!P | S(a))");
+
+    let mut borrowed = Vec::new();
+    for clause in &cnf {
+        borrowed.push(clause.clone());
+    }
+
+    assert_eq!(cnf.clauses().iter().cloned().collect::<Vec<_>>(), borrowed);
+    assert_eq!(borrowed, cnf.into_iter().collect::<Vec<_>>());
+
+    let atoms = ctx.atoms(["P(a)", "P(b)", "Q", "R(a, :x)"]);
+    let index = PredicateIndex::from(atoms.clone());
+
+    let borrowed: Vec<_> = (&index).into_iter().map(|(name, args)| Atom::Pred(name, args.clone())).collect();
+    assert_eq!(atoms, borrowed);
+    assert_eq!(atoms, index.into_iter().collect::<Vec<_>>());
+
+    assert_eq!(0, PredicateIndex::new().into_iter().count());
+}
+
+#[test]
+fn atom_accessors() {
+    let mut ctx = TestContext::new();
+
+    let atom = ctx.atom("P(a, f(:x))");
+    let [p] = ctx.names(["P"]).try_into().unwrap();
+
+    assert_eq!(p, atom.name());
+    assert_eq!(2, atom.arity());
+    assert_eq!(ctx.aexprs(["a", "f(:x)"]), atom.args());
+    assert_eq!(atom, Atom::pred(atom.name(), atom.args().to_vec()));
+
+    assert_eq!(0, ctx.atom("Q").arity());
+    assert!(ctx.atom("Q").args().is_empty());
+}
@@ -0,0 +1,51 @@
+use std::process::Command;
+
+/// Runs the `consistent` command with the given arguments, returning its output and whether it succeeded.
+fn consistent<const N: usize>(args: [&str; N]) -> (String, bool) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rsproof"))
+        .arg("consistent")
+        .args(args)
+        .output()
+        .unwrap();
+
+    (String::from_utf8(output.stdout).unwrap().trim().to_string(), output.status.success())
+}
+
+#[test]
+fn consistent_premises() {
+    assert_eq!(("consistent".into(), true), consistent(["P, Q |-"]));
+    assert_eq!(("consistent".into(), true), consistent(["!P, Q |-", "-t"]));
+
+    // Conclusions are ignored
+    assert_eq!(("consistent".into(), true), consistent(["P |- !P"]));
+}
+
+#[test]
+fn inconsistent_premises() {
+    assert_eq!(("inconsistent".into(), false), consistent(["P, !P |-"]));
+    assert_eq!(("inconsistent".into(), false), consistent(["P | Q, !P, !Q |-", "--tseitin"]));
+    assert_eq!(("inconsistent".into(), false), consistent(["P, !P |-", "-H", "naive"]));
+    assert_eq!(("inconsistent".into(), false), consistent(["all x: P(x), some x: !P(x) |-", "--auto-cnf"]));
+}
+
+#[test]
+fn first_order_premises() {
+    // A ground Horn CNF is decided by unit propagation
+    assert_eq!(("consistent".into(), true), consistent(["P(a), P(a) -> Q(a) |-"]));
+
+    // Without a model, exhausting the search space is not reported as consistent
+    assert_eq!(("exhausted".into(), false), consistent(["all x: (P(x) -> Q(x)), P(a) |-"]));
+
+    assert_eq!(("inconsistent".into(), false), consistent(["all x: (P(x) -> Q(x)), P(a), !Q(a) |-"]));
+    assert_eq!(("inconsistent".into(), false), consistent(["(some y: all x: R(x, y)), !(some y: all x: R(x, y)) |-"]));
+}
+
+#[test]
+fn undecided_premises() {
+    // Resolution never exhausts these premises, so only the limits end it
+    let premises = "P(a), all x: (P(x) -> P(f(x))) |-";
+
+    assert_eq!(("undecided".into(), false), consistent([premises, "-s", "5"]));
+    assert_eq!(("undecided".into(), false), consistent([premises, "-T", "0.1"]));
+    assert_eq!(("undecided".into(), false), consistent([premises, "-s", "1000000", "--timeout", "0.1"]));
+}
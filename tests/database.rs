@@ -42,4 +42,25 @@ fn test_1() {
     ctx.display(&actual);
 
     assert_eq!(expected, actual);
-}
\ No newline at end of file
+}
+
+#[test]
+fn partners() {
+    let mut ctx = TestContext::new();
+
+    let mut db = KnowledgeBase::new();
+
+    db.learn(ctx.clause("P(:x) | Q(a)"));
+    db.learn(ctx.clause("!P(b)"));
+    db.learn(ctx.clause("!Q(b) | R"));
+    db.learn(ctx.clause("P(c) | !R"));
+    db.learn(ctx.clause("S"));
+
+    let expected: BTreeSet<_> = ctx.clauses(["P(:x) | Q(a)", "P(c) | !R"]).into_iter().map(Rc::new).collect();
+    assert_eq!(expected, db.partners(&ctx.clause("!P(a)")));
+
+    let expected: BTreeSet<_> = ctx.clauses(["!P(b)", "!Q(b) | R"]).into_iter().map(Rc::new).collect();
+    assert_eq!(expected, db.partners(&ctx.clause("P(:x) | Q(a)")));
+
+    assert!(db.partners(&ctx.clause("S")).is_empty());
+}
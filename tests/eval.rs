@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use rsplib::res::find_model;
+use rsplib::test::TestContext;
+
+#[test]
+fn eval_bexpr() {
+    let mut ctx = TestContext::new();
+
+    let [p, q] = ctx.names(["P", "Q"]).try_into().unwrap();
+    let assignment = BTreeMap::from([(p, true), (q, false)]);
+
+    assert_eq!(Some(true), ctx.bexpr("P | Q").eval(&assignment));
+    assert_eq!(Some(false), ctx.bexpr("P & Q").eval(&assignment));
+    assert_eq!(Some(false), ctx.bexpr("P -> Q").eval(&assignment));
+    assert_eq!(Some(true), ctx.bexpr("!(P <-> Q)").eval(&assignment));
+    assert_eq!(Some(true), ctx.bexpr("all x: P").eval(&assignment));
+}
+
+#[test]
+fn eval_bexpr_unknown() {
+    let mut ctx = TestContext::new();
+
+    let [p] = ctx.names(["P"]).try_into().unwrap();
+    let assignment = BTreeMap::from([(p, true)]);
+
+    // R is not assigned
+    assert_eq!(None, ctx.bexpr("P & R").eval(&assignment));
+
+    // Predicates with arguments can't be evaluated
+    assert_eq!(None, ctx.bexpr("P & Q(:a)").eval(&assignment));
+    assert_eq!(None, ctx.bexpr("all x: Q(x)").eval(&assignment));
+}
+
+#[test]
+fn eval_stmt() {
+    let mut ctx = TestContext::new();
+
+    let [p, q] = ctx.names(["P", "Q"]).try_into().unwrap();
+
+    let stmt = ctx.stmt("P, P -> Q |- Q");
+    assert_eq!(Some(true), stmt.eval(&BTreeMap::from([(p, true), (q, true)])));
+    assert_eq!(Some(true), stmt.eval(&BTreeMap::from([(p, true), (q, false)])));
+
+    // Counter-model
+    let stmt = ctx.stmt("P | Q |- P");
+    assert_eq!(Some(false), stmt.eval(&BTreeMap::from([(p, false), (q, true)])));
+    assert_eq!(Some(true), stmt.eval(&BTreeMap::from([(p, true), (q, false)])));
+}
+
+#[test]
+fn find_model_dpll() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P | Q) & (!P | Q) & (!Q | R | S) & !S");
+    let model = find_model(cnf.clauses()).unwrap();
+
+    let [q, r, s] = ctx.names(["Q", "R", "S"]).try_into().unwrap();
+    assert_eq!(Some(&true), model.get(&q));
+    assert_eq!(Some(&true), model.get(&r));
+    assert_eq!(Some(&false), model.get(&s));
+
+    let unsat = ctx.cnf("(P | Q) & (!P | Q) & (P | !Q) & (!P | !Q)");
+    assert_eq!(None, find_model(unsat.clauses()));
+}
@@ -0,0 +1,91 @@
+use rsplib::nf::{Clause, NormalForm};
+use rsplib::res::{Deduction, Proof};
+use rsplib::test::TestContext;
+
+fn clause_at(proof: &[Deduction], line: usize) -> &Clause {
+    match &proof[line] {
+        Deduction::Premise { clause } => clause,
+        Deduction::Resolve { clause, .. } => clause,
+        Deduction::Magic { clause } => clause,
+        Deduction::QED { .. } => panic!("QED has no clause"),
+    }
+}
+
+fn assert_valid(proof: &[Deduction]) {
+    for ded in proof {
+        match ded {
+            Deduction::Resolve { clause, a_line, b_line, resolvee } => {
+                let a = clause_at(proof, *a_line);
+                let b = clause_at(proof, *b_line);
+                assert_eq!(*clause, resolvee.resolvent(a, b));
+            },
+            Deduction::QED { line_with_bottom } => {
+                assert!(clause_at(proof, *line_with_bottom).is_empty());
+            },
+            Deduction::Magic { .. } => panic!("Magic deduction in proof"),
+            Deduction::Premise { .. } => {}
+        }
+    }
+
+    assert!(matches!(proof.last(), Some(Deduction::QED { .. })));
+}
+
+#[test]
+fn is_horn() {
+    let mut ctx = TestContext::new();
+
+    assert!(ctx.cnf("P & (!P | Q) & (!Q | !R)").is_horn());
+    assert!(ctx.cnf("!P(:x) | !Q(:x) | R(:x)").is_horn());
+    assert!(!ctx.cnf("P & (P | Q)").is_horn());
+}
+
+#[test]
+fn solve_horn_proven() {
+    let mut ctx = TestContext::new();
+
+    let cnf = NormalForm::equiv_cnf(ctx.stmt("P, P -> Q, Q & P -> R |- R").refutable_expr());
+
+    let Some(Proof::Proven(proof)) = cnf.solve_horn() else {
+        panic!("Expected proof");
+    };
+
+    assert_valid(&proof);
+}
+
+#[test]
+fn solve_horn_proven_ground() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P(a) & (!P(a) | Q(f(a))) & !Q(f(a))");
+
+    let Some(Proof::Proven(proof)) = cnf.solve_horn() else {
+        panic!("Expected proof");
+    };
+
+    assert_valid(&proof);
+}
+
+#[test]
+fn solve_horn_disproven() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P & (!P | Q) & (!R | S) & (!Q | !S)");
+    let names = ctx.names(["P", "Q", "R", "S"]);
+
+    let Some(Proof::Disproven { model: Some(model) }) = cnf.solve_horn() else {
+        panic!("Expected model");
+    };
+
+    assert_eq!(Some(&true), model.get(&names[0]));
+    assert_eq!(Some(&true), model.get(&names[1]));
+    assert_eq!(Some(&false), model.get(&names[2]));
+    assert_eq!(Some(&false), model.get(&names[3]));
+}
+
+#[test]
+fn solve_horn_unsupported() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(None, ctx.cnf("P | Q").solve_horn());
+    assert_eq!(None, ctx.cnf("P(:x) & !P(a)").solve_horn());
+}
@@ -0,0 +1,40 @@
+use rsplib::test::TestContext;
+
+#[test]
+fn aexpr_depth() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(1, ctx.aexpr(":x").depth());
+    assert_eq!(1, ctx.aexpr("a").depth());
+    assert_eq!(2, ctx.aexpr("f(:x)").depth());
+    assert_eq!(3, ctx.aexpr("f(:x, g(a))").depth());
+    assert_eq!(4, ctx.aexpr("f(f(f(:x)))").depth());
+}
+
+#[test]
+fn aexpr_symbol_count() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(1, ctx.aexpr(":x").symbol_count());
+    assert_eq!(1, ctx.aexpr("a").symbol_count());
+    assert_eq!(4, ctx.aexpr("f(:x, g(:x))").symbol_count());
+    assert_eq!(5, ctx.aexpr("f(g(a, b), :y)").symbol_count());
+}
+
+#[test]
+fn atom_depth() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(0, ctx.atom("P").depth());
+    assert_eq!(1, ctx.atom("P(:x, a)").depth());
+    assert_eq!(3, ctx.atom("P(a, f(g(:x)))").depth());
+}
+
+#[test]
+fn atom_symbol_count() {
+    let mut ctx = TestContext::new();
+
+    assert_eq!(1, ctx.atom("P").symbol_count());
+    assert_eq!(3, ctx.atom("P(:x, a)").symbol_count());
+    assert_eq!(5, ctx.atom("P(a, f(g(:x)))").symbol_count());
+}
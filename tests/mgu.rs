@@ -1,5 +1,5 @@
 use rsplib::test::TestContext;
-use rsplib::uni::Unifier;
+use rsplib::uni::{Unifier, UnifyError};
 
 #[test]
 fn mgu_1() {
@@ -9,6 +9,7 @@ fn mgu_1() {
     let b = ctx.aexprs(["f(a)", ":x"]);
 
     let actual = Unifier::mgu(&a, &b);
+    assert!(actual.as_ref().is_some_and(|it| it.unifies(&a, &b)));
     
     let expected = Some(ctx.mgu([
         ("x", "a"),
@@ -26,6 +27,7 @@ fn mgu_2() {
     let b = ctx.aexprs(["f(:y)", ":y",    "a"]);
 
     let actual = Unifier::mgu(&a, &b);
+    assert!(actual.as_ref().is_some_and(|it| it.unifies(&a, &b)));
     
     let expected = Some(ctx.mgu([
         ("x", "f(f(a))"),
@@ -44,6 +46,7 @@ fn mgu_3() {
     let b = ctx.aexprs([":y"]);
 
     let actual = Unifier::mgu(&a, &b);
+    assert!(actual.as_ref().is_some_and(|it| it.unifies(&a, &b)));
     
     let expected = Some(ctx.mgu([
         ("x", ":y")
@@ -52,6 +55,30 @@ fn mgu_3() {
     assert_eq!(expected, actual);
 }
 
+#[test]
+fn mgu_swapped() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.aexprs(["f(:x, :y)"]);
+    let b = ctx.aexprs(["f(:y, :x)"]);
+
+    let actual = Unifier::mgu(&a, &b).unwrap();
+    assert!(actual.unifies(&a, &b));
+}
+
+#[test]
+fn unifies() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.aexprs(["f(:x)", ":y"]);
+    let b = ctx.aexprs(["f(a)", ":x"]);
+
+    assert!(ctx.mgu([("x", "a"), ("y", "a")]).unifies(&a, &b));
+    assert!(!ctx.mgu([("x", "a")]).unifies(&a, &b));
+    assert!(!ctx.mgu([("x", "b"), ("y", "b")]).unifies(&a, &b));
+    assert!(!Unifier::new().unifies(&a, &b[..1]));
+}
+
 #[test]
 fn no_mgu_1() {
     let mut ctx = TestContext::new();
@@ -92,4 +119,55 @@ fn no_mgu_3() {
     let expected = None;
 
     assert_eq!(expected, actual);
-}
\ No newline at end of file
+}
+
+#[test]
+fn restrict_1() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.aexprs([":x",    "f(:z)", ":z"]);
+    let b = ctx.aexprs(["f(:y)", ":y",    "a"]);
+
+    let names = ctx.names(["x", "z"]).into_iter().collect();
+    let actual = Unifier::mgu(&a, &b).map(|it| it.restrict_to(&names));
+    
+    let expected = Some(ctx.mgu([
+        ("x", "f(f(a))"),
+        ("z", "a"),
+    ]));
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn mgu_explain() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.aexprs(["f(:x)", "g(:y)"]);
+    let b = ctx.aexprs(["f(a)", "h(:x)"]);
+
+    let expected = Err(UnifyError::FunctionClash(ctx.name("g"), ctx.name("h")));
+    assert_eq!(expected, Unifier::mgu_explain(&a, &b));
+
+    let a = ctx.aexprs(["f(:x)"]);
+    let b = ctx.aexprs(["f(a, b)"]);
+
+    let expected = Err(UnifyError::ArityMismatch(ctx.name("f"), 1, 2));
+    assert_eq!(expected, Unifier::mgu_explain(&a, &b));
+
+    let a = ctx.aexprs([":x"]);
+    let b = ctx.aexprs(["f(:x)"]);
+
+    let expected = Err(UnifyError::OccursCheck(ctx.name("x"), ctx.aexpr("f(:x)")));
+    assert_eq!(expected, Unifier::mgu_explain(&a, &b));
+
+    let a = ctx.aexprs([":x", ":y"]);
+    let b = ctx.aexprs(["a"]);
+
+    assert_eq!(Err(UnifyError::ShapeMismatch), Unifier::mgu_explain(&a, &b));
+
+    let a = ctx.aexprs(["f(:x)", ":y"]);
+    let b = ctx.aexprs(["f(a)", ":x"]);
+
+    assert_eq!(Unifier::mgu(&a, &b), Unifier::mgu_explain(&a, &b).ok());
+}
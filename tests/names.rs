@@ -1,7 +1,10 @@
 use std::collections::BTreeSet;
 
-use rsplib::expr::{Name, Names};
+use rsplib::expr::{AExpr, BExpr, Name, Names};
+use rsplib::fmt::{DisplayNamed, NameTable};
+use rsplib::parser::ParseContext;
 use rsplib::test::TestContext;
+use rsplib::util::fold::NameHistogram;
 
 fn assert_has_names(exp: impl Names, names: Vec<Name>) {
     let actual = exp.names::<BTreeSet<_>>();
@@ -22,4 +25,72 @@ fn names_2() {
     let mut ctx = TestContext::new();
 
     assert_has_names(ctx.aexpr("f(x, g(a, :x, :x))"), ctx.names(["f", "x", "g", "a"]));
-}
\ No newline at end of file
+}
+
+#[test]
+fn stmt_with_symbols() {
+    let mut ctx = ParseContext::new();
+
+    let (stmt, symbols, bound) = ctx.stmt_with_symbols("all x: P(x, :y), some x: Q(f(x), a) |- P(a, :y)").unwrap();
+
+    let expected_symbols: BTreeSet<_> = ["P", "Q", "f", "a", "y"].map(|it| ctx.name(it).unwrap()).into_iter().collect();
+    assert_eq!(expected_symbols, symbols);
+
+    // The two `x`s are bound by different quantifiers
+    assert_eq!(2, bound.len());
+    assert!(bound.is_disjoint(&symbols));
+
+    let all: BTreeSet<_> = stmt.names();
+    assert_eq!(all, symbols.union(&bound).copied().collect());
+}
+
+#[test]
+fn name_histogram() {
+    let mut ctx = TestContext::new();
+
+    let exp = ctx.bexpr("P(f(:x), :x) & Q(:x, a) | P(a, a)");
+    let names = ctx.names(["P", "Q", "f", "x", "a", "b"]);
+
+    let histogram: NameHistogram = exp.names();
+
+    assert_eq!(2, histogram.count(&names[0]));
+    assert_eq!(1, histogram.count(&names[1]));
+    assert_eq!(1, histogram.count(&names[2]));
+    assert_eq!(3, histogram.count(&names[3]));
+    assert_eq!(3, histogram.count(&names[4]));
+    assert_eq!(0, histogram.count(&names[5]));
+}
+
+#[test]
+fn name_table_name_for() {
+    let mut names = NameTable::new();
+
+    let p = names.name_for("P");
+    let f = names.name_for("f");
+    let a = names.name_for("a");
+
+    assert_ne!(p, f);
+    assert_ne!(f, a);
+    assert_eq!(p, names.name_for("P"));
+
+    assert_eq!(Some("P"), names.name_of(&p));
+    assert_eq!(Some("f"), names.name_of(&f));
+    assert_eq!(None, names.name_of(&a.succ()));
+
+    let exp = BExpr::pred(p, vec![AExpr::fun(f, vec![AExpr::con(a)])]);
+    assert_eq!("P(f(a()))", exp.with_table(&names).to_string());
+}
+
+#[test]
+fn name_table_name_for_parsed() {
+    let mut ctx = ParseContext::new();
+    let exp = ctx.bexpr("all x: P(x, a)").unwrap();
+
+    let mut names = ctx.name_table().clone();
+
+    // Existing unbound names are reused, bound names are not
+    let p = names.name_for("P");
+    let x = names.name_for("x");
+    assert!(exp.has_name::<BTreeSet<Name>>(&p));
+    assert!(!exp.has_name::<BTreeSet<Name>>(&x));
+}
@@ -0,0 +1,106 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use rsplib::test::TestContext;
+use rsplib::uni::KboOrdering;
+
+#[test]
+fn kbo_equal() {
+    let mut ctx = TestContext::new();
+
+    let kbo = KboOrdering::default();
+    let a = ctx.aexpr("f(:x, a)");
+
+    assert_eq!(Some(Ordering::Equal), kbo.compare(&a, &a));
+}
+
+#[test]
+fn kbo_variable_condition() {
+    let mut ctx = TestContext::new();
+
+    let f = ctx.name("f");
+    let g = ctx.name("g");
+    let kbo = KboOrdering::new(BTreeMap::new(), vec![g, f]);
+
+    // f(:x) is heavier than :y, but :y does not occur in f(:x)
+    let a = ctx.aexpr("f(:x)");
+    let b = ctx.aexpr(":y");
+    assert_eq!(None, kbo.compare(&a, &b));
+
+    // g(:x, :y) and f(:y, :y) are equally heavy, but :y occurs in f(:y, :y) more often and :x does not
+    // occur in it at all, so neither is greater
+    let a = ctx.aexpr("g(:x, :y)");
+    let b = ctx.aexpr("f(:y, :y)");
+    assert_eq!(None, kbo.compare(&a, &b));
+
+    // A term is greater than its proper subterms
+    let a = ctx.aexpr("f(:x)");
+    let b = ctx.aexpr(":x");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&a, &b));
+    assert_eq!(Some(Ordering::Less), kbo.compare(&b, &a));
+}
+
+#[test]
+fn kbo_weight() {
+    let mut ctx = TestContext::new();
+
+    let f = ctx.name("f");
+    let g = ctx.name("g");
+    let a = ctx.name("a");
+
+    // g precedes f, but weights decide first
+    let kbo = KboOrdering::new(BTreeMap::from([(f, 3), (g, 1)]), vec![a, f, g]);
+
+    let s = ctx.aexpr("f(:x)");
+    let t = ctx.aexpr("g(g(:x))");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&s, &t));
+
+    let s = ctx.aexpr("g(a, :x)");
+    let t = ctx.aexpr("f(:x)");
+    assert_eq!(Some(Ordering::Less), kbo.compare(&s, &t));
+}
+
+#[test]
+fn kbo_precedence() {
+    let mut ctx = TestContext::new();
+
+    // `f` and `g` are used with different amounts of arguments
+    ctx.set_strict_arity(false);
+
+    let f = ctx.name("f");
+    let g = ctx.name("g");
+    let kbo = KboOrdering::new(BTreeMap::new(), vec![f, g]);
+
+    // Equal weights, g > f in the precedence
+    let s = ctx.aexpr("g(:x)");
+    let t = ctx.aexpr("f(:x)");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&s, &t));
+    assert_eq!(Some(Ordering::Less), kbo.compare(&t, &s));
+
+    // Equal weights and head symbols, decided by the first differing argument
+    let s = ctx.aexpr("f(g(:x), f(:y))");
+    let t = ctx.aexpr("f(f(:x), g(:y))");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&s, &t));
+
+    // Symbols missing in the precedence are incomparable
+    let s = ctx.aexpr("h(:x)");
+    let t = ctx.aexpr("f(:x)");
+    assert_eq!(None, kbo.compare(&s, &t));
+}
+
+#[test]
+fn kbo_zero_weight_unary() {
+    let mut ctx = TestContext::new();
+
+    let i = ctx.name("i");
+    let f = ctx.name("f");
+    let kbo = KboOrdering::new(BTreeMap::from([(i, 0)]), vec![f, i]);
+
+    let s = ctx.aexpr("i(i(:x))");
+    let t = ctx.aexpr(":x");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&s, &t));
+
+    let s = ctx.aexpr("i(f(:x))");
+    let t = ctx.aexpr("f(i(:x))");
+    assert_eq!(Some(Ordering::Greater), kbo.compare(&s, &t));
+}
@@ -92,4 +92,38 @@ fn clear() {
 
     let vec = queue.into_iter().map(|it| it.0).collect::<Vec<_>>();
     assert_eq!(vec, vec![]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn truncate() {
+    let mut queue = PQueue::assoc([3, 7, 1, 5, 4, 6, 0, 2], |it| *it);
+
+    assert_eq!(5, queue.truncate(3));
+    assert_eq!(0, queue.truncate(3));
+
+    let vec = queue.into_iter().map(|it| it.0).collect::<Vec<_>>();
+    assert_eq!(vec, vec![0, 1, 2]);
+}
+
+#[test]
+fn truncate_to_empty() {
+    let mut queue = PQueue::assoc([3, 1, 5, 4], |it| *it);
+
+    assert_eq!(4, queue.truncate(0));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn retain() {
+    let mut queue = PQueue::assoc([3, 7, 1, 5, 4, 6, 0, 2], |it| *it * 10);
+
+    queue.retain(|it, _| it % 2 == 1);
+
+    let vec = queue.iter().map(|it| it.0).collect::<Vec<_>>();
+    assert_eq!(vec, vec![1, 3, 5, 7]);
+
+    queue.retain(|_, w| w < 50);
+
+    let vec = queue.into_iter().map(|it| it.0).collect::<Vec<_>>();
+    assert_eq!(vec, vec![1, 3]);
+}
@@ -96,6 +96,8 @@ prove!(long, "
 
 disprove!(cont2, "P |- !P");
 
+prove!(miniscope_split, "some y: all x: ((some w: P(x, w)) & R(x, y)) |- some y: all x: R(x, y)");
+
 const PROOF_STEPS: usize = 10000;
 
 fn assert_proven(ctx: &mut TestContext, mut resolver: Resolver) {
@@ -119,13 +121,21 @@ fn assert_proven(ctx: &mut TestContext, mut resolver: Resolver) {
         },
 
         Some(ResolverResult {
-            proof: Proof::Disproven,
+            proof: Proof::Disproven { .. },
             deductions_made: _,
             learning_order: _
         }) => {
             panic!("Disproven");
         },
 
+        Some(ResolverResult {
+            proof: Proof::Undecided,
+            deductions_made: _,
+            learning_order: _
+        }) => {
+            panic!("Undecided after discarding candidates");
+        },
+
         None => {
             panic!("Undecided after {PROOF_STEPS} resolution steps");
         }
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use rsplib::expr::{BExpr, Name};
+use rsplib::nf::NormalForm;
+use rsplib::res::find_model;
+use rsplib::test::{all_assignments, BExprGenerator, TestContext};
+
+const CASES: u64 = 500;
+const DEPTH: usize = 5;
+
+/// Evaluates a propositional CNF under a truth assignment.
+fn eval_cnf(cnf: &NormalForm, assignment: &BTreeMap<Name, bool>) -> bool {
+    cnf.clauses().iter().all(|clause| {
+        clause.iter_literals().any(|(name, _, neg)| assignment[&name] != neg)
+    })
+}
+
+/// Runs a property on generated ground expressions over the symbols `P`, `Q`, `R` and `S`.
+fn check<F>(mut property: F) where F : FnMut(BExpr, &[Name], u64) {
+    let mut ctx = TestContext::new();
+    let symbols = ctx.names(["P", "Q", "R", "S"]);
+
+    for seed in 0..CASES {
+        let expr = BExprGenerator::new(seed, symbols.clone()).ground(DEPTH);
+        property(expr, &symbols, seed);
+    }
+}
+
+#[test]
+fn equiv_cnf_is_equivalent() {
+    check(|expr, symbols, seed| {
+        let cnf = NormalForm::equiv_cnf(expr.clone());
+
+        for assignment in all_assignments(symbols) {
+            assert_eq!(expr.eval(&assignment), Some(eval_cnf(&cnf, &assignment)), "seed {seed}");
+        }
+    });
+}
+
+#[test]
+fn tseitin_cnf_is_equisatisfiable() {
+    check(|expr, symbols, seed| {
+        let satisfiable = all_assignments(symbols).iter().any(|it| expr.eval(it) == Some(true));
+
+        match find_model(NormalForm::tseitin_cnf(expr.clone()).clauses()) {
+            // The model of the Tseitin CNF is a model of the expression, where symbols that the model
+            // does not mention are free to choose
+            Some(mut model) => {
+                for symbol in symbols {
+                    model.entry(*symbol).or_insert(false);
+                }
+
+                assert_eq!(Some(true), expr.eval(&model), "seed {seed}");
+            },
+
+            None => assert!(!satisfiable, "seed {seed}")
+        }
+    });
+}
+
+#[test]
+fn transformations_are_equivalent() {
+    check(|expr, symbols, seed| {
+        let nnf = expr.clone().to_nnf();
+        let simplified = expr.clone().simplify();
+        let flattened = expr.clone().flatten().unflatten();
+
+        for assignment in all_assignments(symbols) {
+            let value = expr.eval(&assignment);
+
+            assert_eq!(value, nnf.eval(&assignment), "seed {seed}");
+            assert_eq!(value, simplified.eval(&assignment), "seed {seed}");
+            assert_eq!(value, flattened.eval(&assignment), "seed {seed}");
+        }
+    });
+}
+
+#[test]
+fn generator_is_deterministic() {
+    let mut ctx = TestContext::new();
+    let symbols = ctx.names(["P", "Q"]);
+
+    let a = BExprGenerator::new(42, symbols.clone()).ground(DEPTH);
+    let b = BExprGenerator::new(42, symbols.clone()).ground(DEPTH);
+    assert_eq!(a, b);
+
+    assert_eq!(16, all_assignments(&ctx.names(["P", "Q", "R", "S"])).len());
+}
@@ -1,5 +1,9 @@
-use rsplib::nf::NormalForm;
-use rsplib::res::{Proof, Resolver, ResolverResult};
+use rsplib::nf::{Clause, Clauses, NormalForm, Signature};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use rsplib::res::{self, resolve, Heuristic, Proof, ProofResult, ResolutionEvent, Resolver, ResolverOptions, ResolverResult};
 use rsplib::test::TestContext;
 
 const PROOF_STEPS: usize = 10000;
@@ -25,13 +29,21 @@ fn assert_proven(ctx: &mut TestContext, mut resolver: Resolver) {
         },
 
         Some(ResolverResult {
-            proof: Proof::Disproven,
+            proof: Proof::Disproven { .. },
             deductions_made: _,
             learning_order: _
         }) => {
             panic!("Disproven");
         },
 
+        Some(ResolverResult {
+            proof: Proof::Undecided,
+            deductions_made: _,
+            learning_order: _
+        }) => {
+            panic!("Undecided after discarding candidates");
+        },
+
         None => {
             panic!("Undecided after {PROOF_STEPS} resolution steps");
         }
@@ -80,4 +92,541 @@ fn non_cnf() {
     );
 
     assert_proven(&mut ctx, resolver);
-}
\ No newline at end of file
+}
+
+#[test]
+fn mgu_cache() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::with_mgu_cache();
+
+    resolver.assume(ctx.clause("!P(:x) | Q(:x)"));
+    resolver.assume(ctx.clause("!Q(:z)"));
+    resolver.assume(ctx.clause("P(S)"));
+
+    assert_proven(&mut ctx, resolver);
+
+    // Only the pairs `P(a), P(a)`, `Q, Q` and `R, R` are unified, so every lookup after the first of
+    // each pair is a hit
+    let mut resolver = Resolver::with_mgu_cache();
+
+    resolver.assume(ctx.clause("P(a)"));
+    resolver.assume(ctx.clause("!P(a) | Q"));
+    resolver.assume(ctx.clause("!P(a) | R"));
+    resolver.assume(ctx.clause("!Q | !R"));
+
+    let result = resolver.step_n_times(PROOF_STEPS);
+    assert!(matches!(result, Some(ResolverResult { proof: Proof::Proven(_), .. })));
+
+    let cache = resolver.mgu_cache().unwrap();
+    assert_eq!(3, cache.misses());
+    assert_eq!(5, cache.hits());
+    assert_eq!(resolver.stats().mgu_attempts, cache.hits() + cache.misses());
+}
+
+#[test]
+fn binary_resolvents() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.clause("P(:x) | Q(:x)");
+    let b = ctx.clause("!P(a) | R(b)");
+
+    let actual = resolve(&a, &b);
+    let expected = vec![(ctx.clause("Q(a) | R(b)"), ctx.mgu([("x", "a")]))];
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn binary_resolvents_on_predicate() {
+    let mut ctx = TestContext::new();
+
+    let a = ctx.clause("P(:x) | !Q(:x)");
+    let b = ctx.clause("!P(a) | Q(b)");
+    let p = ctx.name("P");
+    let q = ctx.name("Q");
+
+    let actual = a.resolvents_on(&b, p);
+    let expected = vec![(ctx.clause("!Q(a) | Q(b)"), ctx.mgu([("x", "a")]))];
+    assert_eq!(expected, actual);
+
+    let actual = a.resolvents_on(&b, q);
+    let expected = vec![(ctx.clause("P(b) | !P(a)"), ctx.mgu([("x", "b")]))];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn binary_resolvents_on_predicate_tautology() {
+    let mut ctx = TestContext::new();
+
+    // Resolving on Q gives P(:y) | !P(:y), a tautology
+    let a = ctx.clause("P(:x) | Q(:x)");
+    let b = ctx.clause("!Q(:y) | !P(:y)");
+    let q = ctx.name("Q");
+
+    assert!(a.resolvents_on(&b, q).is_empty());
+    assert!(a.resolvents_on(&b, ctx.name("R")).is_empty());
+
+    // Resolvents that only have a tautological instance are kept
+    let a = ctx.clause("Q(:y) | P(:x)");
+    let b = ctx.clause("!Q(b) | !P(a)");
+
+    let actual = a.resolvents_on(&b, q);
+    let expected = vec![(ctx.clause("P(:x) | !P(a)"), ctx.mgu([("y", "b")]))];
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn all_resolvents() {
+    let mut ctx = TestContext::new();
+
+    // Resolving the first two clauses on Q gives P | !P, a tautology
+    let cnf = ctx.cnf("(P | Q) & (!Q | !P) & (!P | R(a))");
+
+    let actual: BTreeSet<_> = cnf.all_resolvents().map(|(clause, _)| clause).collect();
+    let expected = BTreeSet::from_iter(ctx.clauses(["Q | R(a)"]));
+    assert_eq!(expected, actual);
+
+    let cnf = ctx.cnf("P(:x) & !P(a) & !P(b)");
+    assert_eq!(Some((Clause::new(), ctx.mgu([("x", "a")]))), cnf.all_resolvents().next());
+    assert_eq!(2, cnf.all_resolvents().count());
+}
+
+#[test]
+fn saturated_cnf() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(ctx.cnf("(P | Q) & (!P | R) & !R"));
+
+    assert!(matches!(resolver.step_indefinitely().proof, Proof::Disproven { .. }));
+    assert_eq!(ctx.cnf("(P | Q) & (!P | R) & !R & (Q | R) & !P & Q"), resolver.saturated_cnf());
+}
+
+#[test]
+fn proof_result() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(ctx.cnf("(P | Q) & !P & !Q"));
+    let result = resolver.step_indefinitely();
+
+    assert_eq!("proven", ProofResult::new(Some(&result), resolver.stats(), false).to_string());
+    assert_eq!("disproven", ProofResult::new(Some(&result), resolver.stats(), true).to_string());
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(ctx.cnf("(P | Q) & !P"));
+    let result = resolver.step_indefinitely();
+
+    assert_eq!("exhausted", ProofResult::new(Some(&result), resolver.stats(), false).to_string());
+    assert_eq!("undecided", ProofResult::new(None, resolver.stats(), false).to_string());
+
+    // Exhausting with discarded candidates proves nothing
+    let mut resolver = Resolver::new();
+    resolver.set_max_clauses(Some(2));
+    resolver.assume_cnf(ctx.cnf("(P | Q) & (!P | R) & !R"));
+    let result = resolver.step_indefinitely();
+
+    let outcome = ProofResult::new(Some(&result), resolver.stats(), false);
+    assert!(outcome.stats().discarded_by_clause_limit > 0);
+    assert_eq!("undecided", outcome.to_string());
+}
+
+#[test]
+fn stall_watchdog() {
+    let mut ctx = TestContext::new();
+
+    // Resolution derives P(f(a)), P(g(a)), P(f(g(a))), ... and never terminates
+    let mut resolver = Resolver::new();
+    resolver.set_stall_window(Some(20));
+    resolver.should_stop_when_stalled(true);
+    resolver.assume_cnf(ctx.cnf("P(a) & (!P(:x) | P(f(:x))) & (!P(:x) | P(g(:x))) & !Q"));
+
+    assert!(resolver.step_n_times(PROOF_STEPS).is_none());
+    assert!(resolver.is_stalled());
+    assert!(resolver.stats().deductions_made < PROOF_STEPS);
+
+    // Stepping explicitly continues regardless
+    let deductions = resolver.stats().deductions_made;
+    assert!(resolver.step().is_none());
+    assert_eq!(deductions + 1, resolver.stats().deductions_made);
+
+    // Resolution that makes progress does not stall
+    let mut resolver = Resolver::new();
+    resolver.set_stall_window(Some(20));
+    resolver.should_stop_when_stalled(true);
+    resolver.assume_cnf(ctx.cnf("P(a) & (!P(:x) | P(f(:x))) & !P(f(f(f(a))))"));
+
+    assert!(matches!(resolver.step_n_times(PROOF_STEPS), Some(ResolverResult { proof: Proof::Proven(_), .. })));
+    assert!(!resolver.is_stalled());
+}
+
+#[test]
+fn counter_model() {
+    let mut ctx = TestContext::new();
+
+    let stmt = ctx.stmt("P | Q, Q -> R |- P");
+    let cnf = NormalForm::equiv_cnf(stmt.clone().refutable_expr());
+
+    let mut resolver = Resolver::new();
+    resolver.should_build_model(true);
+    resolver.assume_cnf(cnf);
+
+    let Some(ResolverResult { proof: Proof::Disproven { model: Some(model) }, .. }) = resolver.step_n_times(PROOF_STEPS) else {
+        panic!("Expected a counter-model");
+    };
+
+    let [p, q, r] = ctx.names(["P", "Q", "R"]).try_into().unwrap();
+    assert_eq!(Some(&false), model.get(&p));
+    assert_eq!(Some(&true), model.get(&q));
+    assert_eq!(Some(&true), model.get(&r));
+
+    assert_eq!(Some(false), stmt.eval(&model));
+}
+
+#[test]
+fn no_model_without_flag() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P | Q");
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(cnf);
+
+    let Some(ResolverResult { proof: Proof::Disproven { model }, .. }) = resolver.step_n_times(PROOF_STEPS) else {
+        panic!("Expected exhaustion");
+    };
+
+    assert_eq!(None, model);
+}
+
+#[test]
+fn no_model_first_order() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("P(:a) | Q");
+
+    let mut resolver = Resolver::new();
+    resolver.should_build_model(true);
+    resolver.assume_cnf(cnf);
+
+    let Some(ResolverResult { proof: Proof::Disproven { model }, .. }) = resolver.step_n_times(PROOF_STEPS) else {
+        panic!("Expected exhaustion");
+    };
+
+    assert_eq!(None, model);
+}
+
+#[test]
+fn step_until_deadline() {
+    let mut ctx = TestContext::new();
+
+    // Resolution never terminates on this statement
+    let expr = ctx.stmt("all x: (P(x) -> P(f(x))), P(a) |- Q").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(50);
+    assert!(resolver.step_until(deadline).is_none());
+    assert!(resolver.stats().deductions_made > 0);
+}
+
+#[test]
+fn max_term_depth() {
+    let mut ctx = TestContext::new();
+
+    // Resolution never terminates on this statement, unless term depth is limited
+    let expr = ctx.stmt("all x: (P(x) -> P(f(x))), P(a) |- Q").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.set_max_term_depth(Some(3));
+    resolver.should_build_model(true);
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+    // Exhausting the search space after discarding resolvents proves nothing
+    let Some(ResolverResult { proof: Proof::Undecided, .. }) = resolver.step_n_times(PROOF_STEPS) else {
+        panic!("Expected undecided");
+    };
+
+    assert!(resolver.stats().discarded_by_depth > 0);
+}
+
+#[test]
+fn max_clauses() {
+    let mut ctx = TestContext::new();
+
+    // Resolution never terminates on this statement, unless the amount of clauses is limited
+    let expr = ctx.stmt("all x: (P(x) -> P(f(x))), P(a) |- Q").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.set_max_clauses(Some(10));
+    resolver.should_build_model(true);
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+    // Exhausting the search space after discarding candidates proves nothing
+    let Some(ResolverResult { proof: Proof::Undecided, .. }) = resolver.step_n_times(PROOF_STEPS) else {
+        panic!("Expected undecided");
+    };
+
+    let stats = resolver.stats();
+    assert!(stats.discarded_by_clause_limit > 0);
+    assert!(stats.learning_order.len() <= 10);
+}
+
+#[test]
+fn max_term_depth_proven() {
+    let mut ctx = TestContext::new();
+
+    let expr = ctx.stmt("all x: (P(x) -> P(f(x))), P(a) |- P(f(f(a)))").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.set_max_term_depth(Some(3));
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+    assert_proven(&mut ctx, resolver);
+}
+
+#[test]
+fn equality_axioms() {
+    let mut ctx = TestContext::new();
+
+    // With E as equality, a = b and P(f(a)) entail P(f(b))
+    let expr = ctx.stmt("E(a, b), P(f(a)) |- P(f(b))").refutable_expr();
+    let e = ctx.name("E");
+
+    let mut cnf = NormalForm::equiv_cnf(expr);
+    let sig = Signature::of(&cnf, e);
+    cnf.add_equality_axioms(&sig);
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(cnf);
+
+    assert_proven(&mut ctx, resolver);
+}
+
+#[test]
+fn equality_axioms_symmetric() {
+    let mut ctx = TestContext::new();
+
+    let expr = ctx.stmt("E(a, b), E(b, c) |- E(c, a)").refutable_expr();
+    let e = ctx.name("E");
+
+    let mut cnf = NormalForm::equiv_cnf(expr);
+    let sig = Signature::of(&cnf, e);
+    cnf.add_equality_axioms(&sig);
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(cnf);
+
+    assert_proven(&mut ctx, resolver);
+}
+
+#[test]
+fn stats_counters() {
+    let mut ctx = TestContext::new();
+
+    let expr = ctx.stmt("P | Q, !P | Q, P | !Q |- P & Q").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+    resolver.step_n_times(PROOF_STEPS);
+
+    let stats = resolver.stats();
+
+    assert!(stats.resolvents_produced > 0);
+    assert!(stats.tautologies_discarded > 0);
+    assert!(stats.peak_frontier > 0);
+    assert!(stats.max_clause_len >= 1);
+    assert!(stats.avg_clause_len() <= stats.max_clause_len as f64);
+    assert!(stats.mgu_successes <= stats.mgu_attempts);
+    assert_eq!(stats.mgu_successes, stats.resolvents_produced);
+}
+
+#[test]
+fn many_candidates() {
+    let mut ctx = TestContext::new();
+
+    // Enough candidate pairs to be resolved in parallel with the `parallel` feature
+    let facts: Vec<_> = (0..64).map(|i| format!("Q(c{i})")).collect();
+    let input = format!("{}, all x: (Q(x) -> R(x)) |- R(c63) & R(c0)", facts.join(", "));
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(NormalForm::equiv_cnf(ctx.stmt(&input).refutable_expr()));
+
+    assert_proven(&mut ctx, resolver);
+}
+
+#[test]
+fn alpha_variants_dropped() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::new();
+
+    resolver.assume(ctx.clause("!P(:x) | Q(:x)"));
+    resolver.assume(ctx.clause("!P(:y) | Q(:y)"));
+    resolver.assume(ctx.clause("!Q(:z)"));
+    resolver.assume(ctx.clause("P(S)"));
+
+    assert_eq!(1, resolver.stats().subsumed_dropped);
+    assert_proven(&mut ctx, resolver);
+}
+
+#[test]
+fn event_hook() {
+    let mut ctx = TestContext::new();
+
+    // Counts of picked, learned premises, learned resolvents, subsumed and contradiction events
+    let counts = Rc::new(RefCell::new([0usize; 5]));
+
+    let mut resolver = Resolver::new();
+
+    let hook_counts = counts.clone();
+    resolver.set_event_hook(move |event| {
+        let index = match event {
+            ResolutionEvent::Picked(_) => 0,
+            ResolutionEvent::Learned { parents: None, .. } => 1,
+            ResolutionEvent::Learned { parents: Some(_), .. } => 2,
+            ResolutionEvent::Subsumed(_) => 3,
+            ResolutionEvent::Contradiction => 4
+        };
+
+        hook_counts.borrow_mut()[index] += 1;
+    });
+
+    resolver.assume(ctx.clause("!P(:x) | Q(:x)"));
+    resolver.assume(ctx.clause("!P(:y) | Q(:y)"));
+    resolver.assume(ctx.clause("!Q(:z)"));
+    resolver.assume(ctx.clause("P(S)"));
+
+    assert_proven(&mut ctx, resolver);
+
+    let [picked, premises, resolvents, subsumed, contradiction] = *counts.borrow();
+    assert_eq!(3, premises);
+    assert_eq!(picked, resolvents + subsumed - 1);
+    assert!(resolvents > 0);
+    assert_eq!(1, contradiction);
+}
+
+#[test]
+fn no_quantifier() {
+    let mut ctx = TestContext::new();
+
+    for input in ["no x: P(x) |- !P(a)", "no x: P(x), P(a) |- Q", "no x: (P(x) & Q(x)), P(a) |- !Q(a)"] {
+        let expr = ctx.stmt(input).refutable_expr();
+
+        let mut resolver = Resolver::new();
+        resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+        assert_proven(&mut ctx, resolver);
+    }
+}
+
+#[test]
+fn reproducible_learning_order() {
+    let mut ctx = TestContext::new();
+
+    let expr = ctx.stmt("P | Q, !P | Q, P | !Q, R | S, !R | S |- P & Q & S").refutable_expr();
+    let cnf = NormalForm::equiv_cnf(expr);
+
+    // With the naive heuristic all candidates tie, so only their age determines the order
+    let run = || {
+        let mut resolver = Resolver::new();
+        resolver.set_heuristic(Heuristic::Naive);
+        resolver.assume_cnf(cnf.clone());
+        resolver.step_n_times(PROOF_STEPS).expect("Expected a result").learning_order
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn empty_premise() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(ctx.cnf("P | Q"));
+    resolver.assume(Clause::new());
+    resolver.assume_cnf(ctx.cnf("!P & !Q"));
+
+    let Some(ResolverResult { proof: Proof::Proven(_), deductions_made: 0, .. }) = resolver.step() else {
+        panic!("Expected proof without deductions");
+    };
+}
+
+#[test]
+fn empty_resolvent_skips_queue() {
+    let mut ctx = TestContext::new();
+
+    let mut resolver = Resolver::new();
+    resolver.set_heuristic(Heuristic::Naive);
+
+    // These clauses produce candidates that are queued before the empty resolvent
+    for clause in ctx.clauses(["Q | R", "!Q | S", "!R | T", "!S | !T", "P", "!P"]) {
+        resolver.assume(clause);
+    }
+
+    let Some(ResolverResult { proof: Proof::Proven(_), deductions_made: 1, .. }) = resolver.step_n_times(2) else {
+        panic!("Expected proof after one deduction");
+    };
+}
+
+#[test]
+fn prove_goals() {
+    let mut ctx = TestContext::new();
+
+    // Premises with an infinite Herbrand universe, so they never saturate
+    let mut base = Resolver::new();
+    base.assume_cnf(ctx.cnf("P(a) & (!P(:x) | P(f(:x))) & (!P(:x) | P(g(:x))) & (!P(:x) | Q(:x))"));
+    base.set_stall_window(Some(50));
+    base.should_stop_when_stalled(true);
+    assert!(base.step_n_times(10).is_none());
+
+    let learned = base.stats().learning_order.len();
+
+    assert_eq!("proven", base.prove(ctx.cnf("!Q(f(f(a)))")).to_string());
+    assert_eq!("proven", base.prove(ctx.cnf("!P(g(f(a)))")).to_string());
+    assert_eq!("undecided", base.prove(ctx.cnf("!R")).to_string());
+
+    // Queries do not affect the base
+    assert_eq!(learned, base.stats().learning_order.len());
+
+    // A finite base is exhausted by goals that do not follow from it
+    let mut base = Resolver::new();
+    base.assume_cnf(ctx.cnf("(P | Q) & !P"));
+    base.step_indefinitely();
+
+    assert_eq!("proven", base.prove(ctx.cnf("!Q")).to_string());
+    assert_eq!("exhausted", base.prove(ctx.cnf("!R")).to_string());
+}
+
+#[test]
+fn resolve_nf() {
+    let mut ctx = TestContext::new();
+
+    let nf = NormalForm::from(Clauses::from_iter(ctx.clauses(["P | Q", "!P", "!Q"])));
+    assert_eq!("proven", res::resolve_nf(&nf, ResolverOptions::default()).to_string());
+
+    let nf = NormalForm::from(Clauses::from_iter(ctx.clauses(["P | Q", "!P"])));
+    assert_eq!("exhausted", res::resolve_nf(&nf, ResolverOptions::default()).to_string());
+
+    // Options bound the resolution
+    let nf = NormalForm::from(Clauses::from_iter(ctx.clauses(["P(a)", "!P(:x) | P(f(:x))", "!P(:x) | P(g(:x))", "!Q"])));
+    let opts = ResolverOptions { max_steps: Some(20), ..Default::default() };
+
+    let outcome = res::resolve_nf(&nf, opts);
+    assert_eq!("undecided", outcome.to_string());
+    assert_eq!(20, outcome.stats().deductions_made);
+
+    let opts = ResolverOptions { stall_window: Some(50), ..Default::default() };
+    assert_eq!("undecided", res::resolve_nf(&nf, opts).to_string());
+
+    // Discarding deep resolvents makes a provable set undecided rather than exhausted
+    let nf = NormalForm::from(Clauses::from_iter(ctx.clauses(["P(a)", "!P(:x) | P(f(:x))", "!P(f(f(f(a))))"])));
+    assert_eq!("proven", res::resolve_nf(&nf, ResolverOptions::default()).to_string());
+
+    let opts = ResolverOptions { max_term_depth: Some(1), ..Default::default() };
+    let outcome = res::resolve_nf(&nf, opts);
+    assert_eq!("undecided", outcome.to_string());
+    assert!(outcome.stats().discarded_by_depth > 0);
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+
+use rsplib::nf::NormalForm;
+use rsplib::res::{Proof, Resolver, ResolverResult, SelectAll, SelectMaximal, SelectionFunction};
+use rsplib::test::TestContext;
+
+#[test]
+fn select_all() {
+    let mut ctx = TestContext::new();
+
+    let clause = ctx.clause("P(:x) | !Q(f(:x)) | R");
+
+    let expected = BTreeSet::from([
+        (ctx.atom("P(:x)"), false),
+        (ctx.atom("Q(f(:x))"), true),
+        (ctx.atom("R"), false),
+    ]);
+
+    assert_eq!(expected, SelectAll.select(&clause).into_iter().collect());
+}
+
+#[test]
+fn select_maximal() {
+    let mut ctx = TestContext::new();
+
+    // Q(f(:x)) is heavier than P(:x) and R, and contains all their variables
+    let clause = ctx.clause("P(:x) | !Q(f(:x)) | R");
+
+    let expected = vec![(ctx.atom("Q(f(:x))"), true)];
+    assert_eq!(expected, SelectMaximal::default().select(&clause));
+}
+
+#[test]
+fn select_maximal_incomparable() {
+    let mut ctx = TestContext::new();
+
+    // P(:x) and Q(:y) are incomparable, so both are maximal
+    let clause = ctx.clause("P(:x) | Q(:y)");
+
+    let expected = BTreeSet::from([
+        (ctx.atom("P(:x)"), false),
+        (ctx.atom("Q(:y)"), false),
+    ]);
+
+    assert_eq!(expected, SelectMaximal::default().select(&clause).into_iter().collect());
+}
+
+#[test]
+fn resolve_with_selection() {
+    let mut ctx = TestContext::new();
+
+    let expr = ctx.stmt("all x: (P(x) -> Q(f(x))), P(a) |- Q(f(a))").refutable_expr();
+
+    let mut resolver = Resolver::new();
+    resolver.set_selection_function(SelectMaximal::default());
+    resolver.assume_cnf(NormalForm::equiv_cnf(expr));
+
+    let Some(ResolverResult { proof: Proof::Proven(_), .. }) = resolver.step_n_times(10000) else {
+        panic!("Expected proof");
+    };
+}
+
+#[test]
+fn selection_prunes() {
+    let mut ctx = TestContext::new();
+
+    let input = "P(a) | Q(f(a)), !P(a) | Q(f(a)), P(b), P(a) | R |- Q(f(a))";
+
+    let mut all = Resolver::new();
+    all.assume_cnf(NormalForm::equiv_cnf(ctx.stmt(input).refutable_expr()));
+    all.step_n_times(10000);
+
+    let mut maximal = Resolver::new();
+    maximal.set_selection_function(SelectMaximal::default());
+    maximal.assume_cnf(NormalForm::equiv_cnf(ctx.stmt(input).refutable_expr()));
+    maximal.step_n_times(10000);
+
+    assert!(maximal.stats().resolvents_produced < all.stats().resolvents_produced);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use rsplib::expr::Stmt;
+use rsplib::nf::NormalForm;
+use rsplib::uni::Unifier;
+use rsplib::test::TestContext;
+
+#[test]
+fn round_trip_stmt() {
+    let mut ctx = TestContext::new();
+
+    let stmt = ctx.stmt("all x: (P(x) -> Q(f(x), :y)), some z: P(z) |- some x: Q(x, :y) & !R");
+
+    let json = serde_json::to_string(&stmt).unwrap();
+    let parsed: Stmt = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(stmt, parsed);
+}
+
+#[test]
+fn round_trip_cnf() {
+    let mut ctx = TestContext::new();
+
+    let cnf = ctx.cnf("(P(:x) | !Q(:x, f(a))) & (!P(b) | P(c) | R) & !R");
+
+    let json = serde_json::to_string(&cnf).unwrap();
+    let parsed: NormalForm = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(cnf, parsed);
+}
+
+#[test]
+fn round_trip_unifier() {
+    let mut ctx = TestContext::new();
+
+    let mgu = ctx.mgu([("x", "f(:z)"), ("y", "a")]);
+
+    let json = serde_json::to_string(&mgu).unwrap();
+    let parsed: Unifier = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(mgu, parsed);
+}
+
+#[test]
+fn invalid_unifier() {
+    let mut ctx = TestContext::new();
+
+    // {x := f(x)} is recursive, so it is not a valid unifier
+    let x = ctx.name("x");
+    let table = BTreeMap::from([(x, ctx.aexpr("f(:x)"))]);
+
+    let json = serde_json::to_string(&table).unwrap();
+    assert!(serde_json::from_str::<Unifier>(&json).is_err());
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeSet;
+
+use rsplib::expr::{AExpr, BExpr, Name, Names, Stmt, StmtBuilder};
+use rsplib::fmt::DisplayNamed;
+use rsplib::parser::ParseContext;
+use rsplib::res::Proof;
+use rsplib::test::TestContext;
+
+#[test]
+fn stmt_builder() {
+    let mut builder = StmtBuilder::new();
+
+    let p = BExpr::sym(builder.name("P"));
+    let q = BExpr::sym(builder.name("Q"));
+
+    builder
+        .premise(BExpr::im(p.clone(), q.clone()))
+        .premise(p.clone())
+        .conclusion(q.clone());
+
+    let (stmt, _) = builder.build();
+
+    assert_eq!(Stmt::from_implication(vec![BExpr::im(p.clone(), q.clone()), p], vec![q]), stmt);
+}
+
+#[test]
+fn stmt_builder_names() {
+    let mut builder = StmtBuilder::new();
+
+    let p = builder.name("P");
+    let f = builder.name("f");
+    let a = builder.name("a");
+
+    assert_eq!(p, builder.name("P"));
+
+    builder.conclusion(BExpr::pred(p, vec![AExpr::fun(f, vec![AExpr::con(a)])]));
+
+    let (stmt, names) = builder.build();
+
+    assert_eq!(" |- P(f(a()))", stmt.with_table(&names).to_string());
+}
+
+#[test]
+fn shift_names() {
+    let mut ctx_a = ParseContext::new();
+    let mut ctx_b = ParseContext::new();
+
+    let a = ctx_a.stmt("P(a) |- all x: Q(x)").unwrap();
+    let b = ctx_b.stmt("R(f(b)) |- some y: P(y)").unwrap();
+
+    // Both contexts start allocating from the same name, so the statements collide
+    let names_a: BTreeSet<Name> = a.names();
+    let names_b: BTreeSet<Name> = b.names();
+    assert!(!names_a.is_disjoint(&names_b));
+
+    let base = a.free();
+    let shifted = b.clone().shift_names(base);
+
+    let names_shifted: BTreeSet<Name> = shifted.names();
+    assert!(names_a.is_disjoint(&names_shifted));
+    assert_eq!(names_b.len(), names_shifted.len());
+    assert_eq!(Names::max(&b).map(|it| it.offset(base)), Names::max(&shifted));
+
+    let table = ctx_b.name_table().clone().shift_names(base);
+    assert_eq!(b.with_table(ctx_b.name_table()).to_string(), shifted.with_table(&table).to_string());
+}
+
+#[test]
+fn trivial_result() {
+    let mut ctx = TestContext::new();
+
+    assert!(matches!(ctx.stmt("P |- P").trivial_result(), Some(Proof::Proven(d)) if d.is_empty()));
+    assert!(matches!(ctx.stmt("P & Q(a), R |- Q(a), P").trivial_result(), Some(Proof::Proven(_))));
+    assert!(matches!(ctx.stmt("P |- true").trivial_result(), Some(Proof::Proven(_))));
+    assert!(matches!(ctx.stmt("false |- Q").trivial_result(), Some(Proof::Proven(_))));
+    assert!(matches!(ctx.stmt("true |- false").trivial_result(), Some(Proof::Disproven { model: None })));
+
+    assert!(ctx.stmt("P |- Q").trivial_result().is_none());
+    assert!(ctx.stmt("P, P -> Q |- Q").trivial_result().is_none());
+    assert!(ctx.stmt("P |- false").trivial_result().is_none());
+}
+
+#[test]
+fn refutation_bexpr() {
+    let mut ctx = TestContext::new();
+
+    let stmt = ctx.stmt("P, Q |- R, S");
+
+    assert_eq!(ctx.bexpr("(Q & P) & !(S & R)"), stmt.as_refutation_bexpr());
+    assert_eq!(ctx.bexpr("(Q & P) & (S & R)"), stmt.as_disproof_bexpr());
+    assert_eq!(stmt.clone().refutable_expr(), stmt.as_refutation_bexpr());
+
+    let stmt = ctx.stmt("|- R");
+    assert_eq!(ctx.bexpr("true & !R"), stmt.as_refutation_bexpr());
+}
@@ -1,4 +1,5 @@
-use rsplib::parser::ParseContext;
+use rsplib::expr::{AExpr, BExpr, Stmt};
+use rsplib::parser::{CommentStyle, InputRange, ParseContext, DEFAULT_MAX_DEPTH};
 
 
 fn test_aexpr(str: &str) {
@@ -108,3 +109,196 @@ fn logic() {
 
     test_bexpr(":x == :x");
 }
+
+
+fn test_reformat(str: &str, expected: &str) {
+    let mut ctx = ParseContext::new();
+    let original = ctx.stmt(str).unwrap();
+
+    let mut ctx = ParseContext::new();
+    let formatted = ctx.reformat(str).unwrap();
+    assert_eq!(expected, formatted);
+
+    // The formatted statement parses to the same statement
+    let mut ctx = ParseContext::new();
+    assert_eq!(original, ctx.stmt(&formatted).unwrap());
+}
+
+#[test]
+fn reformat() {
+    test_reformat("P,Q|-R", "P, Q |- R");
+    test_reformat("|- P", "|- P");
+    test_reformat("P |-", "P |-");
+    test_reformat("((P & Q) & R) | (S | T) |-", "P & Q & R | (S | T) |-");
+    test_reformat("P & (Q | R) |- !(P & Q) | !!R", "P & (Q | R) |- !(P & Q) | !!R");
+    test_reformat("P -> Q |- Q", "!P | Q |- Q");
+    test_reformat("F(:x, g(a), b) |- true | false", "F(:x, g(a), b) |- true | false");
+}
+
+#[test]
+fn reformat_quantifiers() {
+    test_reformat("all x, y: P(x, y, :z) |- some x: (Q(x) & R)", "all x: all y: P(x, y, :z) |- some x: (Q(x) & R)");
+    test_reformat("(all x: P(x)) & Q |- !(some x: !P(x))", "all x: P(x) & Q |- !some x: !P(x)");
+    test_reformat("all x: (P(x) & all x: Q(x, c)) |-", "all x: (P(x) & all x: Q(x, c)) |-");
+}
+
+fn test_reformat_grouped(str: &str, expected: &str) {
+    let mut ctx = ParseContext::new();
+    let original = ctx.stmt(str).unwrap();
+
+    let mut ctx = ParseContext::new();
+    let formatted = ctx.reformat_grouped(str).unwrap();
+    assert_eq!(expected, formatted);
+
+    let mut ctx = ParseContext::new();
+    assert_eq!(original, ctx.stmt(&formatted).unwrap());
+}
+
+#[test]
+fn reformat_grouped() {
+    test_reformat_grouped("((P & Q) & R) | (S | T) |-", "((P & Q) & R) | (S | T) |-");
+    test_reformat_grouped("P&Q&R | +S |- (!P)", "P & Q & R | (S) |- (!P)");
+    test_reformat_grouped("(all x: P(x)) & Q |- !(some x: !P(x))", "(all x: P(x)) & Q |- !(some x: !P(x))");
+    test_reformat_grouped("(P -> Q) |- P & (Q | R)", "(!P | Q) |- P & (Q | R)");
+
+    // Groups are identified structurally
+    test_reformat_grouped("(P & Q) | P & Q |-", "(P & Q) | (P & Q) |-");
+}
+
+#[test]
+fn equivalence() {
+    let mut ctx = ParseContext::new();
+
+    let (lhs, rhs) = ctx.equivalence("P -> Q === !P | Q").unwrap();
+    assert_eq!(ctx.bexpr("P -> Q").unwrap(), lhs);
+    assert_eq!(ctx.bexpr("!P | Q").unwrap(), rhs);
+
+    // Multiple expressions on one side form a conjunction
+    let (lhs, rhs) = ctx.equivalence("P, Q === R").unwrap();
+    assert_eq!(ctx.bexpr("P & Q").unwrap(), lhs);
+    assert_eq!(ctx.bexpr("R").unwrap(), rhs);
+
+    assert!(ctx.equivalence("P |- Q").is_err());
+}
+
+#[test]
+fn c_comments() {
+    let mut ctx = ParseContext::new();
+
+    // By default, only `#` comments are recognized
+    assert!(ctx.bexpr_valid("P // comment").is_err());
+    assert!(ctx.bexpr_valid("P # comment").is_ok());
+
+    ctx.set_comment_style(CommentStyle::C);
+
+    assert_eq!(ctx.bexpr("P & Q").unwrap(), ctx.bexpr("P // comment\n& /* multi\nline */ Q # comment").unwrap());
+    assert_eq!(ctx.bexpr("P(a, b)").unwrap(), ctx.bexpr("P(a /**/, /*/ b */ b)").unwrap());
+
+    // Line numbers are counted inside block comments
+    let err = ctx.bexpr("P & /*\n\n*/ Q )").unwrap_err();
+    assert_eq!((3, 6), (err.from.line, err.from.col));
+
+    assert!(ctx.bexpr_valid("P /* unclosed").is_err());
+}
+
+#[test]
+fn spans() {
+    let mut ctx = ParseContext::new();
+
+    let (_, spans) = ctx.bexpr_with_spans("P(a) & (Q(f(a)) -> P(a))").unwrap();
+
+    let [p, q, f] = ["P", "Q", "f"].map(|it| ctx.name(it).unwrap());
+    let a = ctx.aexpr("a").unwrap();
+    let fa = ctx.aexpr("f(a)").unwrap();
+
+    // A range ends where the next token starts
+    let cols = |ranges: &[InputRange]| ranges.iter().map(|it| (it.from.col, it.to.col)).collect::<Vec<_>>();
+
+    assert_eq!(vec![(1, 6), (20, 24)], cols(spans.pred_spans(p, std::slice::from_ref(&a))));
+    assert_eq!(vec![(9, 17)], cols(spans.pred_spans(q, std::slice::from_ref(&fa))));
+    assert_eq!(vec![(3, 4), (13, 14), (22, 23)], cols(spans.term_spans(&a)));
+    assert_eq!(vec![(11, 15)], cols(&spans.name_spans(f)));
+    assert!(spans.pred_spans(q, &[a]).is_empty());
+
+    // Statements record spans as well
+    let (_, spans) = ctx.stmt_with_spans("P(a) |- Q(f(a))").unwrap();
+    assert_eq!(vec![(9, 16)], cols(spans.pred_spans(q, &[fa])));
+}
+
+#[test]
+fn strict_arity() {
+    let mut ctx = ParseContext::new();
+
+    let err = ctx.bexpr("P(a) & Q\n& P(a, b)").unwrap_err();
+    assert_eq!((2, 3), (err.from.line, err.from.col));
+    assert_eq!("P is used with 2 arguments, but with 1 arguments at [1:1]", err.msg);
+
+    // Functions and constants are checked as well
+    assert!(ctx.bexpr("P(f(a)) | P(f(a, b))").is_err());
+    assert!(ctx.bexpr("P(a) | P(a(b))").is_err());
+    assert!(ctx.stmt("P(a) |- P").is_err());
+
+    // Variables have no arity, and arities are checked per input
+    assert!(ctx.bexpr("all x: P(x) & Q(:x)").is_ok());
+    assert!(ctx.bexpr("P").is_ok());
+
+    ctx.set_strict_arity(false);
+    assert!(ctx.bexpr("P(a) & P(a, b)").is_ok());
+}
+
+#[test]
+fn from_str() {
+    assert!("P(a) & Q | !R".parse::<BExpr>().is_ok());
+    assert!("f(x, g(y))".parse::<AExpr>().is_ok());
+    assert!("P, P -> Q |- Q".parse::<Stmt>().is_ok());
+
+    // Parsing twice yields the same names
+    assert_eq!("P(a) & Q".parse::<BExpr>().unwrap(), "P(a) & Q".parse::<BExpr>().unwrap());
+
+    let err = "P & & Q".parse::<BExpr>().unwrap_err();
+    assert_eq!((1, 5), (err.from.line, err.from.col));
+    assert!("P |- Q |- R".parse::<Stmt>().is_err());
+}
+
+fn nested(open: &str, depth: usize, close: &str) -> String {
+    format!("{}P{}", open.repeat(depth), close.repeat(depth))
+}
+
+#[test]
+fn max_depth() {
+    // The default depth is sized for the stack of a main thread, which is larger than that of a test
+    // thread in unoptimized builds
+    std::thread::Builder::new().stack_size(8 << 20).spawn(|| {
+        let mut ctx = ParseContext::new();
+
+        // Inputs within the default depth parse fine
+        assert!(ctx.bexpr(nested("(", DEFAULT_MAX_DEPTH, ")")).is_ok());
+        assert!(ctx.bexpr(nested("!", DEFAULT_MAX_DEPTH, "")).is_ok());
+        assert!(ctx.bexpr(nested("all x: ", DEFAULT_MAX_DEPTH, "")).is_ok());
+        assert!(ctx.aexpr(nested("f(", DEFAULT_MAX_DEPTH, ")")).is_ok());
+
+        // Deeper inputs are rejected rather than overflowing the stack
+        let err = ctx.bexpr(nested("(", 100_000, ")")).unwrap_err();
+        assert!(err.msg.contains("nested"));
+        assert!(ctx.bexpr(nested("!", 100_000, "")).is_err());
+        assert!(ctx.bexpr(nested("all x: ", 100_000, "")).is_err());
+        assert!(ctx.aexpr(nested("f(", 100_000, ")")).is_err());
+        assert!(ctx.stmt(format!("Q |- {}", nested("(", 100_000, ")"))).is_err());
+    }).unwrap().join().unwrap();
+
+    let mut ctx = ParseContext::new();
+
+    // Long chains of operators and long lists do not nest
+    assert!(ctx.bexpr(format!("{}P", "P & ".repeat(5000))).is_ok());
+    assert!(ctx.stmt(format!("{}P |- Q", "P, ".repeat(100_000))).is_ok());
+    assert!(ctx.bexpr(format!("P({}a)", "a, ".repeat(100_000))).is_ok());
+
+    // The maximum is configurable
+    ctx.set_max_depth(2);
+    assert!(ctx.bexpr("!(P)").is_ok());
+    assert!(ctx.bexpr("!(!P)").is_err());
+    assert!(ctx.aexpr("f(g(x))").is_ok());
+    assert!(ctx.aexpr("f(g(h(x)))").is_err());
+    assert!(ctx.bexpr("all x: all y: P").is_ok());
+    assert!(ctx.bexpr("all x: all y: all z: P").is_err());
+}
@@ -0,0 +1,25 @@
+use rsplib::test::TestContext;
+
+#[test]
+fn tautology_ground() {
+    let mut ctx = TestContext::new();
+
+    assert!(ctx.clause("P(a) | Q | !P(a)").is_tautology_modulo_unification());
+}
+
+#[test]
+fn tautology_unifies() {
+    let mut ctx = TestContext::new();
+
+    assert!(ctx.clause("P(:x) | !P(a)").is_tautology_modulo_unification());
+    assert!(ctx.clause("P(f(:x), :y) | !P(:y, f(a))").is_tautology_modulo_unification());
+}
+
+#[test]
+fn no_tautology() {
+    let mut ctx = TestContext::new();
+
+    assert!(!ctx.clause("P(a) | !P(b)").is_tautology_modulo_unification());
+    assert!(!ctx.clause("P(:x) | !Q(:x)").is_tautology_modulo_unification());
+    assert!(!ctx.clause("P(:x) | !P(f(:x))").is_tautology_modulo_unification());
+}
@@ -0,0 +1,125 @@
+use rsplib::expr::{AExpr, BExpr};
+use rsplib::nf::NormalForm;
+use rsplib::parser::{parse_tptp, tptp_stmt, ParseContext, Role};
+use rsplib::res::{Proof, Resolver, ResolverResult};
+
+const PROBLEM: &str = "
+% Socrates is mortal
+fof(all_men_mortal, axiom, ! [X] : (man(X) => mortal(X))).
+fof(socrates_man, hypothesis, man(socrates)).
+
+/* The conjecture */
+fof(socrates_mortal, conjecture, ? [Y] : mortal(Y), file('socrates.p', [a, b])).
+";
+
+#[test]
+fn parse_roles() {
+    let formulas = parse_tptp(PROBLEM).unwrap();
+    let roles: Vec<_> = formulas.iter().map(|(role, _)| *role).collect();
+
+    assert_eq!(vec![Role::Axiom, Role::Hypothesis, Role::Conjecture], roles);
+}
+
+#[test]
+fn parse_formula() {
+    let mut ctx = ParseContext::new();
+
+    let formulas = ctx.tptp("fof(1, axiom, ! [X, Y] : ~ (p(X) & q(f(Y), c))).").unwrap();
+    let [(Role::Axiom, BExpr::All(x, rhs))] = formulas.as_slice() else {
+        panic!("Expected a universal axiom");
+    };
+
+    let BExpr::All(y, rhs) = rhs.as_ref() else {
+        panic!("Expected nested universal quantifier");
+    };
+
+    let [p, q, f, c] = ["p", "q", "f", "c"].map(|it| ctx.name(it).unwrap());
+
+    let expected = !(
+        BExpr::pred(p, vec![AExpr::var(*x)])
+        & BExpr::pred(q, vec![AExpr::fun(f, vec![AExpr::var(*y)]), AExpr::fun(c, vec![])])
+    );
+
+    assert_eq!(&expected, rhs.as_ref());
+}
+
+#[test]
+fn parse_connectives() {
+    let mut ctx = ParseContext::new();
+
+    let formulas = ctx.tptp("
+        fof(a, axiom, (p => q) <=> (~p | q)).
+        fof(b, axiom, p <= q).
+        fof(c, axiom, (p <~> q) ~| (p ~& q)).
+        fof(d, negated_conjecture, $true & p & q & $false).
+    ").unwrap();
+
+    assert_eq!(4, formulas.len());
+    assert_eq!(Role::NegatedConjecture, formulas[3].0);
+}
+
+#[test]
+fn parse_errors() {
+    // Unsupported role
+    assert!(parse_tptp("fof(a, lemma, p).").is_err());
+
+    // Unbound variable
+    assert!(parse_tptp("fof(a, axiom, p(X)).").is_err());
+
+    // Mixing binary connectives without parentheses
+    assert!(parse_tptp("fof(a, axiom, p & q | r).").is_err());
+
+    // Clause normal form is not supported
+    assert!(parse_tptp("cnf(a, axiom, p | q).").is_err());
+
+    // Equality is not supported
+    assert!(parse_tptp("fof(a, axiom, a = b).").is_err());
+
+    // Missing dot
+    assert!(parse_tptp("fof(a, axiom, p)").is_err());
+}
+
+#[test]
+fn prove_tptp() {
+    let stmt = tptp_stmt(parse_tptp(PROBLEM).unwrap());
+    let cnf = NormalForm::equiv_cnf(stmt.refutable_expr());
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(cnf);
+
+    let Some(ResolverResult { proof: Proof::Proven(_), .. }) = resolver.step_n_times(10000) else {
+        panic!("Expected proof");
+    };
+}
+
+#[test]
+fn proof_to_tstp() {
+    let mut ctx = ParseContext::new();
+
+    let stmt = tptp_stmt(ctx.tptp(PROBLEM).unwrap());
+    let cnf = NormalForm::equiv_cnf(stmt.refutable_expr());
+
+    let mut resolver = Resolver::new();
+    resolver.assume_cnf(cnf);
+
+    let Some(ResolverResult { proof, .. }) = resolver.step_n_times(10000) else {
+        panic!("Expected proof");
+    };
+
+    let tstp = proof.to_tstp(ctx.name_table());
+
+    let lines: Vec<_> = tstp.lines().collect();
+
+    assert_eq!("% SZS status Unsatisfiable", lines[0]);
+    assert_eq!("% SZS output start CNFRefutation", lines[1]);
+    assert_eq!("% SZS output end CNFRefutation", *lines.last().unwrap());
+
+    assert!(lines.iter().any(|it| it.starts_with("cnf(") && it.ends_with(", axiom, (man(socrates))).")));
+    assert!(lines.iter().any(|it| it.starts_with("cnf(") && it.contains("$false, inference(resolution, [status(thm)], [c")));
+}
+
+#[test]
+fn disproof_to_tstp() {
+    let proof = Proof::Disproven { model: None };
+    assert_eq!("% SZS status Satisfiable\n", proof.to_tstp(&rsplib::fmt::NameTable::new()));
+}
@@ -22,4 +22,23 @@ fn names_2() {
     let mut ctx = TestContext::new();
 
     assert_has_vars(ctx.aexpr("f(:y, g(a, :x, :x, b), :z)"), ctx.names(["x", "y", "z"]));
-}
\ No newline at end of file
+}
+
+#[test]
+fn ground() {
+    let mut ctx = TestContext::new();
+
+    assert!(ctx.aexpr("f(a, g(b))").is_ground());
+    assert!(!ctx.aexpr("f(a, g(:x))").is_ground());
+    assert!(!ctx.aexpr(":x").is_ground());
+
+    assert!(ctx.atom("P(a, f(b))").is_ground());
+    assert!(ctx.atom("P").is_ground());
+    assert!(!ctx.atom("P(a, f(:x))").is_ground());
+
+    assert!(ctx.clause("P(a) | !Q(b)").is_ground());
+    assert!(!ctx.clause("P(a) | !Q(:x)").is_ground());
+
+    assert!(ctx.cnf("P(a) & (Q | !R(b))").is_ground());
+    assert!(!ctx.cnf("P(a) & (Q | !R(:x))").is_ground());
+}